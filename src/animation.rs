@@ -0,0 +1,409 @@
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::RngCore;
+
+use crate::events::{CarveAction, EventLog};
+use crate::json::json_string;
+use crate::steps::SolveSteps;
+use crate::{Display, Maze, Position, EMPTY_CHAR, POINT_CHAR};
+
+/// What `solve_frames` marks the cells the search has been on with.
+pub const VISITED_CHAR: char = '·';
+/// And the cells it can still go on from.
+pub const FRONTIER_CHAR: char = '○';
+
+#[cfg(feature = "gif")]
+use image::codecs::gif::{GifEncoder, Repeat};
+#[cfg(feature = "gif")]
+use image::{Delay, DynamicImage};
+#[cfg(feature = "gif")]
+use std::fs;
+#[cfg(feature = "gif")]
+use std::path::Path;
+
+#[cfg(feature = "gif")]
+use crate::generator::GeneratorKind;
+#[cfg(feature = "gif")]
+use crate::png::{self, ImageOptions};
+#[cfg(feature = "gif")]
+use crate::rng::Pcg32;
+#[cfg(feature = "gif")]
+use crate::Size;
+
+/// Terminal output to emit `time` after the animation started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub time: Duration,
+    pub data: String,
+}
+
+/// Clears the terminal and draws `display` in full, margins included.
+pub fn full_frame(display: &Display) -> String {
+    let mut buffer = vec![];
+    display.write_to(&mut buffer).unwrap();
+
+    let text = String::from_utf8(buffer)
+        .unwrap()
+        .replace('\n', "\r\n");
+
+    format!("\x1b[2J\x1b[H{}", text)
+}
+
+/// Escape sequences that turn a terminal showing `previous` into one showing `next` by
+/// moving the cursor to each changed pixel. Both displays must have the same size.
+pub fn diff_frames(previous: &Display, next: &Display) -> String {
+    let mut out = String::new();
+
+    for ((row, column), pixel) in next.pixels.indexed_iter() {
+        if previous.pixels[[row, column]] != *pixel {
+            out.push_str(&format!(
+                "\x1b[{};{}H{}",
+                next.margins.top + row + 1,
+                next.margins.left + column + 1,
+                pixel
+            ));
+        }
+    }
+
+    out
+}
+
+/// Moves the cursor to the first line below `display` so later output doesn't overwrite it.
+pub fn cursor_below(display: &Display) -> String {
+    format!("\x1b[{};1H", display.get_outer_size().1 + 1)
+}
+
+/// One frame per carve in `log`, then one per step of `solution` if given, `delay` apart.
+pub fn generation_frames(log: &EventLog, solution: Option<&[Position]>, delay: Duration) -> Vec<Frame> {
+    let walled = Maze::new(log.size, true);
+
+    let mut display = Display::new_from_maze(&walled);
+    display.draw_maze(&walled).unwrap();
+
+    let mut frames = vec![Frame {
+        time: Duration::ZERO,
+        data: full_frame(&display),
+    }];
+
+
+    for event in &log.events {
+        let (CarveAction::Carve, Some(direction)) = (event.action, event.direction) else {
+            continue;
+        };
+
+        let previous = display.clone();
+
+        display.draw_point(
+            Maze::to_display_pos(event.position).translate(direction),
+            EMPTY_CHAR
+        );
+
+        push_frame(&mut frames, &previous, &display, delay);
+    }
+
+    if let Some(path) = solution {
+        push_path_frames(&mut frames, &mut display, path, delay);
+
+        let previous = display.clone();
+        display.draw_solution(path, POINT_CHAR).unwrap();
+
+        push_frame(&mut frames, &previous, &display, delay);
+    }
+
+    if let Some(last) = frames.last_mut() {
+        last.data.push_str(&cursor_below(&display));
+    }
+
+    frames
+}
+
+/// `maze` in full, then one frame per step of `path` and a last one with its end markers as
+/// drawn by `Display::draw_walk`.
+pub fn walk_frames(maze: &Maze, path: &[Position], complete: bool, delay: Duration) -> Vec<Frame> {
+    let mut display = Display::new_from_maze(maze);
+    display.draw_maze(maze).unwrap();
+
+    let mut frames = vec![Frame {
+        time: Duration::ZERO,
+        data: full_frame(&display),
+    }];
+
+    push_path_frames(&mut frames, &mut display, path, delay);
+
+    let previous = display.clone();
+    display.draw_walk(path, complete, POINT_CHAR).unwrap();
+
+    push_frame(&mut frames, &previous, &display, delay);
+
+    if let Some(last) = frames.last_mut() {
+        last.data.push_str(&cursor_below(&display));
+    }
+
+    frames
+}
+
+/// `maze` in full, then one frame per step of `steps` with the cells it has been on and its
+/// frontier marked, then the path it found drawn one cell at a time.
+pub fn solve_frames<R: RngCore>(maze: &Maze, mut steps: SolveSteps<'_, R>, delay: Duration) -> Vec<Frame> {
+    let mut display = Display::new_from_maze(maze);
+    display.draw_maze(maze).unwrap();
+
+    let mut frontier = steps.frontier();
+
+    for pos in &frontier {
+        display.draw_point(Maze::to_display_pos(*pos), FRONTIER_CHAR);
+    }
+
+    let mut frames = vec![Frame {
+        time: Duration::ZERO,
+        data: full_frame(&display),
+    }];
+
+    while let Some(step) = steps.next() {
+        let previous = display.clone();
+
+        // Cells that dropped off the frontier keep the visited marker
+        for pos in frontier.iter().chain(&step.discovered) {
+            display.draw_point(Maze::to_display_pos(*pos), VISITED_CHAR);
+        }
+
+        frontier = steps.frontier();
+
+        for pos in &frontier {
+            display.draw_point(Maze::to_display_pos(*pos), FRONTIER_CHAR);
+        }
+
+        push_frame(&mut frames, &previous, &display, delay);
+    }
+
+    if let Some(path) = steps.path() {
+        push_path_frames(&mut frames, &mut display, &path, delay);
+
+        let previous = display.clone();
+        display.draw_solution(&path, POINT_CHAR).unwrap();
+
+        push_frame(&mut frames, &previous, &display, delay);
+    }
+
+    if let Some(last) = frames.last_mut() {
+        last.data.push_str(&cursor_below(&display));
+    }
+
+    frames
+}
+
+/// Writes `frames` to `w` as they are due, sleeping in between.
+pub fn play<W: io::Write>(w: &mut W, frames: &[Frame]) -> io::Result<()> {
+    play_until(w, frames, || false).map(|_| ())
+}
+
+/// Like `play`, but asks `stop` before every frame and leaves the rest out once it says
+/// yes. Returns whether it stopped early.
+pub fn play_until<W: io::Write>(w: &mut W, frames: &[Frame], stop: impl Fn() -> bool) -> io::Result<bool> {
+    let start = Instant::now();
+
+    for frame in frames {
+        thread::sleep(frame.time.saturating_sub(start.elapsed()));
+
+        if stop() {
+            return Ok(true);
+        }
+
+        write!(w, "{}", frame.data)?;
+        w.flush()?;
+    }
+
+    Ok(false)
+}
+
+fn push_frame(frames: &mut Vec<Frame>, previous: &Display, next: &Display, delay: Duration) {
+    frames.push(Frame {
+        time: delay * frames.len() as u32,
+        data: diff_frames(previous, next),
+    });
+}
+
+// Draws `path` onto `display` one cell at a time, pushing a frame after each
+fn push_path_frames(frames: &mut Vec<Frame>, display: &mut Display, path: &[Position], delay: Duration) {
+    let previous = display.clone();
+
+    for pos in path.iter().take(1) {
+        display.draw_point(Maze::to_display_pos(*pos), POINT_CHAR);
+    }
+
+    push_frame(frames, &previous, display, delay);
+
+    for pair in path.windows(2) {
+        let previous = display.clone();
+
+        display.draw_path(
+            pair.iter()
+                .map(|x| Maze::to_display_pos(*x))
+                .collect(),
+            POINT_CHAR
+        ).unwrap();
+
+        push_frame(frames, &previous, display, delay);
+    }
+}
+
+/// Writes `frames` as an asciinema v2 recording for a `width` x `height` terminal.
+pub fn write_cast<W: io::Write>(w: &mut W, width: usize, height: usize, frames: &[Frame]) -> io::Result<()> {
+    writeln!(w, "{{\"version\": 2, \"width\": {}, \"height\": {}}}", width, height)?;
+
+    for frame in frames {
+        writeln!(
+            w,
+            "[{:.6}, \"o\", {}]",
+            frame.time.as_secs_f64(),
+            json_string(&frame.data)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Settings for `generation_gif`.
+#[cfg(feature = "gif")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GifOptions {
+    /// How each frame is drawn, see `Maze::to_image`.
+    pub image: ImageOptions,
+    /// How many carved walls go into one frame. Keeps big mazes from taking thousands.
+    pub steps_per_frame: usize,
+    pub frame_delay: Duration,
+    /// How long the finished maze with its solution stays up before the GIF starts over.
+    pub final_delay: Duration,
+    /// What the cell the generator carved into last is filled with.
+    pub highlight_color: [u8; 3],
+    pub seed: Option<u64>,
+}
+#[cfg(feature = "gif")]
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            image: ImageOptions::default(),
+            steps_per_frame: 10,
+            frame_delay: Duration::from_millis(40),
+            final_delay: Duration::from_secs(3),
+            highlight_color: [40, 120, 220],
+            seed: None,
+        }
+    }
+}
+
+/// Generates a `maze_size` maze with `algorithm` and saves how it was carved as a looping
+/// GIF at `path`, see `Maze::generate_logged`. Each frame carves `steps_per_frame` more
+/// walls and highlights the cell the last of them leads into, the last frame shows the
+/// whole maze with its solution for `final_delay`. Returns the maze. Fails if
+/// `steps_per_frame` is 0 or the image options don't fit, see `Maze::to_image`.
+#[cfg(feature = "gif")]
+pub fn generation_gif(maze_size: Size, algorithm: GeneratorKind, path: &Path, opts: &GifOptions) -> io::Result<Maze> {
+    if opts.steps_per_frame == 0 {
+        return Err(io::ErrorKind::InvalidInput.into());
+    }
+
+    let mut maze = Maze::new(maze_size, true);
+
+    let log = match opts.seed {
+        Some(seed) => maze.generate_logged(algorithm, &mut Pcg32::new(seed)),
+        None => maze.generate_logged(algorithm, &mut rand::rng()),
+    };
+
+    let carves: Vec<(Position, crate::Direction)> = log.events.iter()
+        .filter(|event| event.action == CarveAction::Carve)
+        .filter_map(|event| Some((event.position, event.direction?)))
+        .collect();
+
+    // The encoder only finishes the GIF when it is dropped, so it writes to a buffer
+    let mut buffer = vec![];
+    let mut encoder = GifEncoder::new(&mut buffer);
+    encoder.set_repeat(Repeat::Infinite).map_err(io::Error::other)?;
+
+    let mut push_frame = |image, delay| encoder
+        .encode_frame(image::Frame::from_parts(
+            DynamicImage::ImageRgb8(image).into_rgba8(),
+            0,
+            0,
+            Delay::from_saturating_duration(delay)
+        ))
+        .map_err(io::Error::other);
+
+    let mut carved = Maze::new(maze_size, true);
+    let (cell, wall) = (opts.image.cell_px, opts.image.wall_px);
+
+    for chunk in carves.chunks(opts.steps_per_frame) {
+        for (pos, direction) in chunk {
+            carved.set_wall(*pos, *direction, false)?;
+        }
+
+        let mut image = carved.to_image(None, &opts.image)?;
+
+        if let Some((pos, direction)) = chunk.last() {
+            let current = pos.translate(*direction);
+
+            png::fill(
+                &mut image,
+                current.0 as u32 * cell + wall,
+                current.1 as u32 * cell + wall,
+                cell - wall,
+                cell - wall,
+                opts.highlight_color
+            );
+        }
+
+        push_frame(image, opts.frame_delay)?;
+    }
+
+    push_frame(maze.to_image(Some(&maze.solve_maze()), &opts.image)?, opts.final_delay)?;
+    drop(encoder);
+
+    fs::write(path, buffer)?;
+
+    Ok(maze)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generation_frames, write_cast};
+    use crate::rng::Pcg32;
+    use crate::{Maze, Size};
+    use serde_json::Value;
+    use std::time::Duration;
+
+    #[test]
+    fn casts_are_header_then_timed_json_events() {
+        let mut maze = Maze::new(Size(6, 5), true);
+        let log = maze.generate_maze_logged_with_rng(&mut Pcg32::new(2));
+        let frames = generation_frames(&log, Some(&maze.solve_bfs()), Duration::from_millis(25));
+
+        let mut out = vec![];
+        write_cast(&mut out, 14, 12, &frames).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+
+        let header: Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!((header["version"].as_u64(), header["width"].as_u64(), header["height"].as_u64()), (Some(2), Some(14), Some(12)));
+
+        let mut last = -1.0;
+        let mut events = 0;
+
+        for (line, frame) in lines.zip(&frames) {
+            let event: Value = serde_json::from_str(line).unwrap_or_else(|error| panic!("{}: {}", line, error));
+            let time = event[0].as_f64().unwrap();
+
+            assert!(time > last, "{} after {}", time, last);
+            assert_eq!(event[1], "o");
+            assert_eq!(event[2].as_str(), Some(frame.data.as_str()));
+
+            last = time;
+            events += 1;
+        }
+
+        assert_eq!(events, frames.len());
+        assert_eq!(text.lines().count(), frames.len() + 1);
+    }
+}
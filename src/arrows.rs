@@ -0,0 +1,125 @@
+use std::io;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::{Direction, Display, Maze, Position, Vector, PATH_COLOR, POINT_CHAR};
+
+/// How `Display::draw_solution_styled` draws the path between its ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum PathStyle {
+    /// `POINT_CHAR` all the way, like `draw_solution`.
+    #[default]
+    Dots,
+    /// Arrows pointing the way along the path, `→ ← ↑ ↓`.
+    Arrows,
+    /// The same in plain ASCII, `> < ^ v`.
+    Ascii,
+}
+impl PathStyle {
+    /// The character for a step of the path going `direction`.
+    pub fn glyph(&self, direction: Direction) -> char {
+        match (self, direction) {
+            (PathStyle::Dots, _) => POINT_CHAR,
+            (PathStyle::Arrows, Direction::North) => '↑',
+            (PathStyle::Arrows, Direction::East) => '→',
+            (PathStyle::Arrows, Direction::South) => '↓',
+            (PathStyle::Arrows, Direction::West) => '←',
+            (PathStyle::Ascii, Direction::North) => '^',
+            (PathStyle::Ascii, Direction::East) => '>',
+            (PathStyle::Ascii, Direction::South) => 'v',
+            (PathStyle::Ascii, Direction::West) => '<',
+        }
+    }
+}
+
+impl Display {
+    /// Draws the solution like `draw_solution` with `POINT_CHAR`, then gives every cell
+    /// of the path between its ends, and the gaps between cells, the glyph of the step
+    /// leaving it. Corners point the way the path turns, the ends and their markers keep
+    /// `POINT_CHAR`.
+    pub fn draw_solution_styled(&mut self, path: &[Position], style: PathStyle) -> Result<(), io::ErrorKind> {
+        self.draw_solution(path, POINT_CHAR)?;
+
+        if style == PathStyle::Dots {
+            return Ok(());
+        }
+
+        for pair in path.windows(2) {
+            self.draw_step(pair[0], pair[1], style)?;
+        }
+
+        if let Some(start) = path.first() {
+            self.draw_point_colored(Maze::to_display_pos(*start), POINT_CHAR, Some(PATH_COLOR));
+        }
+
+        Ok(())
+    }
+
+    // Fills the cell at `from` and the gap to `to` with the glyph for the way between them
+    fn draw_step(&mut self, from: Position, to: Position, style: PathStyle) -> Result<(), io::ErrorKind> {
+        let (from, to) = (Maze::to_display_pos(from), Maze::to_display_pos(to));
+        let direction = Vector::new_from_points(from, to)?.direction;
+
+        self.draw_point_colored(from, style.glyph(direction), Some(PATH_COLOR));
+        self.draw_point_colored(from.translate(direction), style.glyph(direction), Some(PATH_COLOR));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PathStyle;
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Position, Size, POINT_CHAR};
+
+    fn styled(style: PathStyle) -> String {
+        let maze = opened(Size(2, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution_styled(&[Position(0, 0), Position(1, 0), Position(1, 1)], style).unwrap();
+
+        display.render()
+    }
+
+    #[test]
+    fn arrows_point_the_way_and_turn_at_corners() {
+        // The start and the goal keep their points
+        let expected = [
+            "█•███",
+            "█•→↓█",
+            "███↓█",
+            "█ █•█",
+            "███•█",
+        ];
+
+        assert_eq!(styled(PathStyle::Arrows), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn ascii_arrows_are_drawn_the_same_way() {
+        let expected = [
+            "█•███",
+            "█•>v█",
+            "███v█",
+            "█ █•█",
+            "███•█",
+        ];
+
+        assert_eq!(styled(PathStyle::Ascii), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn dots_are_the_plain_solution() {
+        let maze = opened(Size(2, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution(&[Position(0, 0), Position(1, 0), Position(1, 1)], POINT_CHAR).unwrap();
+
+        assert_eq!(styled(PathStyle::Dots), display.render());
+    }
+}
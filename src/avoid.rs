@@ -0,0 +1,99 @@
+use crate::distance::BfsSearch;
+use crate::{Display, Maze, Position};
+
+/// What `Display::draw_blocked` marks the cells a path had to avoid with.
+pub const BLOCKED_CHAR: char = '▒';
+
+impl Maze {
+    /// The shortest path from `start` to `goal` that doesn't set foot on any of `blocked`,
+    /// as if they were walled in, found with the same search as `solve_bfs`. `None` if
+    /// either end is outside the maze or blocked, or if every way there is blocked.
+    pub fn solve_avoiding(&self, start: Position, goal: Position, blocked: &[Position]) -> Option<Vec<Position>> {
+        self.get_tile(start)?;
+        self.get_tile(goal)?;
+
+        if blocked.contains(&start) || blocked.contains(&goal) {
+            return None;
+        }
+
+        let mut search = BfsSearch::new(self, start, goal).avoiding(blocked);
+
+        while search.step(self).is_some() {}
+
+        search.path(self)
+    }
+}
+
+impl Display {
+    /// Fills in each of `blocked` with `BLOCKED_CHAR`, skipping cells outside the maze.
+    pub fn draw_blocked(&mut self, blocked: &[Position]) {
+        for pos in blocked {
+            let display_pos = Maze::to_display_pos(*pos);
+
+            if self.pixels.get(display_pos.as_rev_array()).is_some() {
+                self.draw_point(display_pos, BLOCKED_CHAR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Maze, Position, Size};
+
+    #[test]
+    fn the_path_detours_round_blocked_cells() {
+        let maze = Maze::new(Size(3, 2), false);
+
+        assert_eq!(maze.solve_avoiding(Position(0, 0), Position(2, 0), &[Position(1, 0)]), Some(vec![
+            Position(0, 0), Position(0, 1), Position(1, 1), Position(2, 1), Position(2, 0),
+        ]));
+    }
+
+    #[test]
+    fn nothing_blocked_is_the_shortest_path() {
+        let mut maze = Maze::new(Size(9, 7), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(5));
+
+        assert_eq!(maze.solve_avoiding(Position::new(), maze.size.get_max_pos(), &[]), Some(maze.solve_bfs()));
+    }
+
+    #[test]
+    fn a_blocked_only_way_gives_none() {
+        let mut maze = Maze::new(Size(9, 7), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(5));
+
+        let path = maze.solve_bfs();
+
+        assert_eq!(maze.solve_avoiding(path[0], path[path.len() - 1], &[path[path.len() / 2]]), None);
+    }
+
+    #[test]
+    fn blocked_or_outside_ends_give_none() {
+        let maze = Maze::new(Size(3, 3), false);
+
+        assert_eq!(maze.solve_avoiding(Position(0, 0), Position(2, 2), &[Position(0, 0)]), None);
+        assert_eq!(maze.solve_avoiding(Position(0, 0), Position(2, 2), &[Position(2, 2)]), None);
+        assert_eq!(maze.solve_avoiding(Position(0, 0), Position(3, 2), &[]), None);
+    }
+
+    #[test]
+    fn blocked_cells_are_shaded() {
+        let maze = opened(Size(3, 1), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::East)]);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_blocked(&[Position(1, 0), Position(5, 0)]);
+
+        let expected = [
+            "███████",
+            "█  ▒  █",
+            "███████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::{self, Write};
+
+use crate::{Display, Size};
+
+impl Display {
+    /// Prints the display like `print` with axes labelled in maze coordinates, see
+    /// `write_with_axes_to`.
+    pub fn print_with_axes(&self, maze_size: Size) {
+        let mut stdout = io::BufWriter::new(io::stdout().lock());
+
+        self.write_with_axes_to(&mut stdout, maze_size)
+            .and_then(|_| stdout.flush())
+            .unwrap();
+    }
+
+    /// Writes the display like `write_to`, with the column of each of the `maze_size` cells
+    /// numbered above it and each row numbered to its left. Column numbers are written
+    /// top to bottom, one digit to a line, so they stay over their corridor however many
+    /// digits they have. Row numbers are right aligned.
+    pub fn write_with_axes_to<W: io::Write>(&self, w: &mut W, maze_size: Size) -> io::Result<()> {
+        let digits = |n: usize| n.to_string().len();
+
+        let column_digits = digits(maze_size.0.saturating_sub(1));
+        let gutter = digits(maze_size.1.saturating_sub(1)) + 1;
+        let left = gutter + self.margins.left * (self.wide as usize + 1);
+
+        let mut out = vec![];
+        self.write_to(&mut out)?;
+
+        let text = String::from_utf8(out).map_err(|_| io::ErrorKind::InvalidData)?;
+        let mut lines = text.lines();
+
+        for _ in 0..self.margins.top {
+            writeln!(w, "{}", lines.next().unwrap_or_default())?;
+        }
+
+        // Each corridor is on an odd display column, the walls between them on even ones
+        for line in 0..column_digits {
+            let mut header = " ".repeat(left);
+
+            for x in 0..maze_size.0 {
+                let label = x.to_string();
+                let digit = (line + label.len()).checked_sub(column_digits)
+                    .and_then(|i| label.chars().nth(i))
+                    .unwrap_or(' ');
+
+                header.push_str(&" ".repeat(self.wide as usize + 1));
+                header.push(digit);
+
+                if self.wide {
+                    header.push(' ');
+                }
+            }
+
+            writeln!(w, "{}", header.trim_end())?;
+        }
+
+        for (y, line) in lines.by_ref().take(self.size.1).enumerate() {
+            let label = match y % 2 {
+                1 if y / 2 < maze_size.1 => (y / 2).to_string(),
+                _ => String::new(),
+            };
+
+            match line.is_empty() {
+                true => writeln!(w, "{}", label)?,
+                false => writeln!(w, "{:>width$} {}", label, line, width = gutter - 1)?,
+            }
+        }
+
+        for line in lines {
+            writeln!(w, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Display, Margins, Maze, Size};
+
+    fn with_axes(size: Size, wide: bool) -> String {
+        let maze = Maze::new(size, true);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.wide = wide;
+        display.draw_maze(&maze).unwrap();
+
+        let mut out = vec![];
+        display.write_with_axes_to(&mut out, size).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn long_column_numbers_are_written_downwards() {
+        let expected = [
+            "                       1",
+            "   0 1 2 3 4 5 6 7 8 9 0",
+            "  ███████████████████████",
+            "0 █ █ █ █ █ █ █ █ █ █ █ █",
+            "  ███████████████████████",
+            "1 █ █ █ █ █ █ █ █ █ █ █ █",
+            "  ███████████████████████",
+        ];
+
+        assert_eq!(with_axes(Size(11, 2), false), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn wide_displays_keep_the_numbers_over_their_corridors() {
+        let expected = [
+            "    0   1   2",
+            "  ██████████████",
+            "0 ██  ██  ██  ██",
+            "  ██████████████",
+            "1 ██  ██  ██  ██",
+            "  ██████████████",
+        ];
+
+        assert_eq!(with_axes(Size(3, 2), true), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
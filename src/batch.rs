@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::generator::GeneratorKind;
+use crate::rng::{mix_seed, Pcg32};
+use crate::{Maze, Size};
+
+/// Seed of the maze at `index` in a batch. Each index is derived independently from the
+/// base seed, so the batch doesn't depend on generation order or thread count.
+pub fn batch_seed(base_seed: u64, index: usize) -> u64 {
+    mix_seed(base_seed, &[index as u64])
+}
+
+/// An endless, deterministic sequence of mazes. The maze at each index is generated from
+/// `batch_seed(base_seed, index)`, so any index can be reached without generating the ones
+/// before it and matches the same index of a seeded batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MazeStream {
+    pub size: Size,
+    pub generator: GeneratorKind,
+    pub base_seed: u64,
+    index: usize,
+}
+impl MazeStream {
+    pub fn new(size: Size, generator: GeneratorKind, base_seed: u64) -> Self {
+        Self {
+            size,
+            generator,
+            base_seed,
+            index: 0,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Maze {
+        let mut maze = Maze::new(self.size, true);
+        maze.generate(self.generator, &mut Pcg32::new(batch_seed(self.base_seed, index)));
+
+        maze
+    }
+}
+impl Iterator for MazeStream {
+    type Item = Maze;
+
+    fn next(&mut self) -> Option<Maze> {
+        let maze = self.get(self.index);
+        self.index += 1;
+
+        Some(maze)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Maze> {
+        self.index += n;
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Runs `job` for every index in `0..count` on up to `threads` worker threads and returns
+/// the results in index order. `progress` is called with the number of finished jobs
+/// after each one completes.
+pub fn run_parallel<T, F, P>(count: usize, threads: usize, job: F, progress: P) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+    P: Fn(usize) + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let finished = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<T>>> = Mutex::new((0..count).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+
+                if index >= count {
+                    break;
+                }
+
+                let result = job(index);
+                results.lock().unwrap()[index] = Some(result);
+
+                progress(finished.fetch_add(1, Ordering::Relaxed) + 1);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|x| x.unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{batch_seed, run_parallel, MazeStream};
+    use crate::generator::GeneratorKind;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Size};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn fingerprints(threads: usize) -> Vec<String> {
+        run_parallel(40, threads, |index| {
+            let mut maze = Maze::new(Size(9, 7), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(batch_seed(12, index)));
+
+            maze.fingerprint()
+        }, |_| ())
+    }
+
+    #[test]
+    fn thread_count_doesnt_change_the_batch() {
+        let single = fingerprints(1);
+
+        assert_eq!(fingerprints(8), single);
+        assert_eq!(fingerprints(100), single);
+
+        let stream: Vec<String> = MazeStream::new(Size(9, 7), GeneratorKind::Backtracker, 12)
+            .take(40)
+            .map(|maze| maze.fingerprint())
+            .collect();
+
+        assert_eq!(stream, single);
+    }
+
+    #[test]
+    fn progress_counts_every_job_and_failures_stay_in_place() {
+        let reported = AtomicUsize::new(0);
+        let highest = AtomicUsize::new(0);
+
+        let results = run_parallel(10, 4, |index| match index % 3 {
+            0 => Err(index),
+            _ => Ok(index),
+        }, |finished| {
+            reported.fetch_add(1, Ordering::Relaxed);
+            highest.fetch_max(finished, Ordering::Relaxed);
+        });
+
+        assert_eq!((reported.into_inner(), highest.into_inner()), (10, 10));
+
+        let failed: Vec<usize> = results.iter().filter_map(|x| x.err()).collect();
+        assert_eq!(failed, vec![0, 3, 6, 9]);
+        assert!(run_parallel(0, 4, |index| index, |_| ()).is_empty());
+    }
+
+    #[test]
+    fn any_order_reaches_the_same_mazes() {
+        let stream = MazeStream::new(Size(9, 7), GeneratorKind::Backtracker, 3);
+
+        let forward: Vec<Maze> = stream.clone().take(20).collect();
+        let backward: Vec<Maze> = (0..20).rev().map(|index| stream.get(index)).collect();
+
+        assert!(forward.iter().eq(backward.iter().rev()));
+
+        // Skipping ahead lands on the index without walking there
+        let mut skipping = stream.clone();
+        assert_eq!(skipping.nth(10_000), Some(stream.get(10_000)));
+        assert_eq!(skipping.next(), Some(stream.get(10_001)));
+        assert_eq!(stream.clone().nth(5), Some(forward[5].clone()));
+    }
+
+    #[test]
+    fn consecutive_mazes_differ() {
+        let mazes: Vec<Maze> = MazeStream::new(Size(9, 7), GeneratorKind::Backtracker, 3).take(50).collect();
+
+        assert!(mazes.iter().all(|maze| maze.find_inconsistencies().is_empty() && maze.solution_count(2) == 1));
+        assert!(mazes.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+}
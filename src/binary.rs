@@ -0,0 +1,179 @@
+use std::io::{self, Read, Write};
+
+use crate::json::MazeError;
+use crate::{Maze, Position, Size};
+
+/// The first bytes of every file written by `Maze::write_binary`.
+pub const MAGIC: [u8; 4] = *b"MAZB";
+pub const VERSION: u8 = 1;
+
+impl Maze {
+    /// Writes the walls compactly: `MAGIC`, a `VERSION` byte, then the width and height as
+    /// little endian `u32`s. After that come two bits per cell row by row from the top left,
+    /// the east wall and then the south wall, and then one bit for the north wall of each
+    /// cell in the top row followed by one for the west wall of each cell in the left
+    /// column. Bits are packed from the lowest one up and the last byte is padded with
+    /// zeros. Every other wall is the side of a neighbour already written. There's no room
+    /// for waypoints or crossings, so a maze with either is an `InvalidInput` error rather
+    /// than quietly losing them.
+    pub fn write_binary<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        if !self.waypoints.is_empty() || !self.crossings.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the binary format can't hold waypoints or crossings",
+            ));
+        }
+
+        let width = u32::try_from(self.size.0).map_err(|_| io::ErrorKind::InvalidInput)?;
+        let height = u32::try_from(self.size.1).map_err(|_| io::ErrorKind::InvalidInput)?;
+
+        w.write_all(&MAGIC)?;
+        w.write_all(&[VERSION])?;
+        w.write_all(&width.to_le_bytes())?;
+        w.write_all(&height.to_le_bytes())?;
+
+        let cells = (0..self.size.1)
+            .flat_map(|y| (0..self.size.0).map(move |x| Position(x, y)))
+            .flat_map(|pos| {
+                let tile = self.tiles[pos.as_array()];
+
+                [tile.right, tile.down]
+            });
+
+        let top = (0..self.size.0).map(|x| self.tiles[[x, 0]].up);
+        let left = (0..self.size.1).map(|y| self.tiles[[0, y]].left);
+
+        let bits: Vec<bool> = cells.chain(top).chain(left).collect();
+
+        let bytes: Vec<u8> = bits.chunks(8)
+            .map(|chunk| chunk.iter().enumerate().fold(0, |byte, (i, bit)| byte | ((*bit as u8) << i)))
+            .collect();
+
+        w.write_all(&bytes)
+    }
+
+    /// Reads what `write_binary` wrote, filling in the north and west walls from the
+    /// neighbours. Fails on a wrong magic or version, an empty size or missing bytes.
+    pub fn read_binary<R: Read>(r: &mut R) -> Result<Maze, MazeError> {
+        let mut header = [0; 13];
+        r.read_exact(&mut header).map_err(|error| MazeError::Io(error.kind()))?;
+
+        if header[0..4] != MAGIC {
+            return Err(MazeError::BadMagic);
+        }
+
+        if header[4] != VERSION {
+            return Err(MazeError::UnsupportedVersion(header[4] as usize));
+        }
+
+        let dimension = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().unwrap()) as usize;
+        let size = Size(dimension(&header[5..9]), dimension(&header[9..13]));
+
+        if size.0 == 0 || size.1 == 0 {
+            return Err(MazeError::EmptySize);
+        }
+
+        let cells = size.0.checked_mul(size.1).ok_or(MazeError::Io(io::ErrorKind::InvalidData))?;
+        let bit_count = cells.checked_mul(2)
+            .and_then(|bits| bits.checked_add(size.0 + size.1))
+            .ok_or(MazeError::Io(io::ErrorKind::InvalidData))?;
+
+        // Read as it comes rather than sized from the header, which could claim anything
+        let mut bytes = vec![];
+        r.take(bit_count.div_ceil(8) as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|error| MazeError::Io(error.kind()))?;
+
+        if bytes.len() < bit_count.div_ceil(8) {
+            return Err(MazeError::Io(io::ErrorKind::UnexpectedEof));
+        }
+
+        let bit = |i: usize| (bytes[i / 8] >> (i % 8)) & 1 == 1;
+
+        let mut maze = Maze::new(size, true);
+
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let i = (y * size.0 + x) * 2;
+
+                maze.tiles[[x, y]].right = bit(i);
+                maze.tiles[[x, y]].down = bit(i + 1);
+                maze.tiles[[x, y]].up = match y {
+                    0 => bit(cells * 2 + x),
+                    _ => maze.tiles[[x, y - 1]].down,
+                };
+                maze.tiles[[x, y]].left = match x {
+                    0 => bit(cells * 2 + size.0 + y),
+                    _ => maze.tiles[[x - 1, y]].right,
+                };
+            }
+        }
+
+        Ok(maze)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(maze: &Maze) -> Maze {
+        let mut buffer = vec![];
+        maze.write_binary(&mut buffer).unwrap();
+
+        Maze::read_binary(&mut buffer.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_across_sizes() {
+        for (i, size) in [Size(1, 1), Size(3, 1), Size(1, 5), Size(4, 4), Size(5, 3), Size(6, 7), Size(13, 9)].into_iter().enumerate() {
+            let mut maze = Maze::new(size, true);
+            maze.generate_maze_seeded(i as u64);
+
+            assert_eq!(round_trip(&maze), maze, "{:?}", size);
+        }
+    }
+
+    #[test]
+    fn open_borders_survive() {
+        let mut maze = Maze::new(Size(5, 2), true);
+        maze.generate_maze_seeded(3);
+        maze.tiles[[0, 0]].set_side(crate::Direction::West, false);
+        maze.tiles[[4, 1]].set_side(crate::Direction::South, false);
+
+        assert_eq!(round_trip(&maze), maze);
+    }
+
+    #[test]
+    fn waypoints_are_turned_down() {
+        let mut maze = Maze::new(Size(3, 3), true);
+        maze.set_waypoint("key", Position(1, 1)).unwrap();
+
+        let error = maze.write_binary(&mut vec![]).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn bad_headers_and_short_files_are_errors() {
+        let mut buffer = vec![];
+        Maze::new(Size(5, 5), true).write_binary(&mut buffer).unwrap();
+
+        let mut wrong_magic = buffer.clone();
+        wrong_magic[0] = b'X';
+        assert_eq!(Maze::read_binary(&mut wrong_magic.as_slice()), Err(MazeError::BadMagic));
+
+        let mut wrong_version = buffer.clone();
+        wrong_version[4] = VERSION + 1;
+        assert_eq!(
+            Maze::read_binary(&mut wrong_version.as_slice()),
+            Err(MazeError::UnsupportedVersion(VERSION as usize + 1))
+        );
+
+        buffer.pop();
+        assert_eq!(
+            Maze::read_binary(&mut buffer.as_slice()),
+            Err(MazeError::Io(io::ErrorKind::UnexpectedEof))
+        );
+    }
+}
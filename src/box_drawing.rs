@@ -0,0 +1,120 @@
+use ndarray::Array2;
+use std::io;
+
+use crate::{Display, Maze, BLOCK_CHAR};
+
+/// The character for a piece of wall by which of its neighbors are wall too, indexed by
+/// up = 1, right = 2, down = 4 and left = 8 added together. A piece on its own, which only
+/// the corners of a crossing can be, is a dot.
+pub const BOX_CHARS: [char; 16] = [
+    '·', '│', '─', '└',
+    '│', '│', '┌', '├',
+    '─', '┘', '─', '┴',
+    '┐', '┤', '┬', '┼',
+];
+
+impl Display {
+    /// Draws `maze` like `draw_maze` on the same grid, but with thin box drawing lines
+    /// instead of full blocks. Paths are drawn on top the same way.
+    pub fn draw_maze_box(&mut self, maze: &Maze) -> Result<(), io::ErrorKind> {
+        self.draw_maze(maze)?;
+
+        let walls: Array2<bool> = self.pixels.map(|pixel| *pixel == BLOCK_CHAR);
+        let is_wall = |y: Option<usize>, x: Option<usize>| {
+            y.zip(x).and_then(|(y, x)| walls.get([y, x])).copied().unwrap_or(false)
+        };
+
+        for ((y, x), wall) in walls.indexed_iter() {
+            if !*wall {
+                continue;
+            }
+
+            let index = is_wall(y.checked_sub(1), Some(x)) as usize
+                | (is_wall(Some(y), Some(x + 1)) as usize) << 1
+                | (is_wall(Some(y + 1), Some(x)) as usize) << 2
+                | (is_wall(Some(y), x.checked_sub(1)) as usize) << 3;
+
+            self.pixels[[y, x]] = BOX_CHARS[index];
+        }
+
+        Ok(())
+    }
+}
+
+// What follows `symbol` when a display is printed wide. Lines going right carry on, letters
+// and digits get a space so labels stay readable, anything else is printed twice.
+pub(crate) fn right_half(symbol: char) -> char {
+    if let Some(index) = BOX_CHARS.iter().position(|x| *x == symbol) {
+        return match index & 2 {
+            0 => ' ',
+            _ => '─',
+        };
+    }
+
+    match symbol.is_alphanumeric() {
+        true => ' ',
+        false => symbol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{right_half, BOX_CHARS};
+    use crate::rng::Pcg32;
+    use crate::{Display, Margins, Maze, Size, POINT_CHAR};
+
+    fn boxed(seed: u64) -> (Maze, Display) {
+        let mut maze = Maze::new(Size(3, 3), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze_box(&maze).unwrap();
+
+        (maze, display)
+    }
+
+    #[test]
+    fn junctions_follow_their_neighbors() {
+        // Up, right, down and left on their own and all together
+        assert_eq!([BOX_CHARS[1], BOX_CHARS[2], BOX_CHARS[4], BOX_CHARS[8]], ['│', '─', '│', '─']);
+        assert_eq!([BOX_CHARS[6], BOX_CHARS[12], BOX_CHARS[3], BOX_CHARS[9]], ['┌', '┐', '└', '┘']);
+        assert_eq!(BOX_CHARS[15], '┼');
+
+        assert_eq!(right_half('┌'), '─');
+        assert_eq!(right_half('┐'), ' ');
+        assert_eq!(right_half('a'), ' ');
+        assert_eq!(right_half(POINT_CHAR), POINT_CHAR);
+    }
+
+    #[test]
+    fn a_seeded_maze_keeps_its_lines() {
+        let (maze, mut display) = boxed(3);
+
+        let expected = [
+            "┌─────┐",
+            "│     │",
+            "├─┬── │",
+            "│ │   │",
+            "│ │ ──┤",
+            "│     │",
+            "└─────┘",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+
+        display.draw_path(maze.solve_bfs().into_iter().map(Maze::to_display_pos).collect(), POINT_CHAR).unwrap();
+        // The path goes over the open pixels and leaves the lines alone
+        let expected = [
+            "┌─────┐",
+            "│•••••│",
+            "├─┬──•│",
+            "│ │•••│",
+            "│ │•──┤",
+            "│  •••│",
+            "└─────┘",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
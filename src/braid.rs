@@ -0,0 +1,290 @@
+use ndarray::Array2;
+use rand::RngCore;
+use strum::IntoEnumIterator;
+
+use crate::rng::{chance, pick};
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    /// A cell with exactly one open side. The outer border counts as a wall.
+    pub fn is_dead_end(&self, pos: Position) -> bool {
+        self.get_tile(pos)
+            .is_some_and(|tile| tile.get_sides().iter().filter(|(_, closed)| *closed).count() == 3)
+    }
+
+    pub fn get_dead_ends(&self) -> Vec<Position> {
+        self.tiles
+            .indexed_iter()
+            .map(|((x, y), _)| Position(x, y))
+            .filter(|pos| self.is_dead_end(*pos))
+            .collect()
+    }
+
+    pub fn braid(&mut self, p: f64) -> usize {
+        self.braid_with_rng(p, &mut rand::rng())
+    }
+
+    /// Opens a wall of each dead end with probability `p`, preferring walls to neighbours
+    /// that are dead ends too, which adds loops. With `p` at 1 every dead end goes except
+    /// those at the end of a one cell wide strip, which have no wall left to open. Returns
+    /// the number of walls removed.
+    pub fn braid_with_rng<R: RngCore>(&mut self, p: f64, rng: &mut R) -> usize {
+        let removed = self.braid_cells(&self.get_dead_ends(), p, &|_, _| true, rng);
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        removed
+    }
+
+    /// Braids only the dead ends within `radius` steps of `path`, each with probability
+    /// `fraction`, so the loops sit right next to the solution and the rest of the maze stays
+    /// a tree. A dead end is only joined to cells that hang off the path at most
+    /// `2 * radius + 2` steps from where it hangs off itself, so in a perfect maze each wall
+    /// removed shortens the solution by at most `2 * radius + 1` steps. Cells hanging off
+    /// another path cell are preferred, as a loop inside one branch adds no way round.
+    /// Returns the number of walls removed.
+    pub fn braid_near_path<R: RngCore>(&mut self, path: &[Position], radius: usize, fraction: f64, rng: &mut R) -> usize {
+        let distances = self.distances_from_cells(path);
+
+        let mut indices = Array2::from_elem(self.size.as_array(), None);
+
+        for (index, pos) in path.iter().enumerate() {
+            indices[pos.as_array()] = Some(index);
+        }
+
+        // Walking down the distances from a cell ends on the path cell it hangs off
+        let attachment = |mut pos: Position| loop {
+            if let Some(index) = indices[pos.as_array()] {
+                return Some(index);
+            }
+
+            let distance = distances[pos.as_array()]?;
+
+            pos = self.get_moves(pos)
+                .into_iter()
+                .map(|(_, neighbor)| neighbor)
+                .find(|neighbor| distances[neighbor.as_array()].is_some_and(|x| x < distance))?;
+        };
+
+        let is_near = |pos: &Position| distances[pos.as_array()].is_some_and(|x| x as usize <= radius);
+
+        let (candidates, far): (Vec<Position>, Vec<Position>) = self
+            .get_dead_ends()
+            .into_iter()
+            .partition(is_near);
+
+        let mut is_far = Array2::from_elem(self.size.as_array(), false);
+
+        for pos in &far {
+            is_far[pos.as_array()] = true;
+        }
+
+        // The walls each candidate may open, by `Direction as usize`
+        let mut joinable = Array2::from_elem(self.size.as_array(), [false; 4]);
+
+        for pos in &candidates {
+            let Some(own) = attachment(*pos) else { continue };
+
+            // Opening a wall changes the cell on both sides, so far dead ends can't be the
+            // neighbour a near one is joined to either
+            let near: Vec<(Direction, usize)> = Direction::iter()
+                .filter_map(|direction| Some((direction, self.get_neighbor(*pos, direction)?)))
+                .filter(|(_, neighbor)| !is_far[neighbor.as_array()])
+                .filter_map(|(direction, neighbor)| Some((direction, attachment(neighbor)?)))
+                .filter(|(_, other)| own.abs_diff(*other) <= 2 * radius + 2)
+                .collect();
+
+            // Joining a cell that hangs off the same path cell only closes a loop inside one
+            // branch, which adds no way round, so those are left for when there's nothing else
+            let other_branch = near.iter().any(|(_, other)| *other != own);
+
+            for (direction, other) in near {
+                if other != own || !other_branch {
+                    joinable[pos.as_array()][direction as usize] = true;
+                }
+            }
+        }
+
+        let removed = self.braid_cells(&candidates, fraction, &|pos, direction| joinable[pos.as_array()][direction as usize], rng);
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        removed
+    }
+
+    /// Opens one closed wall of each candidate that is still a dead end, with probability
+    /// `fraction`, preferring walls that also fix a neighbouring dead end. A candidate only
+    /// opens the walls `allowed` accepts for it.
+    pub(crate) fn braid_cells<R: RngCore>(
+        &mut self,
+        candidates: &[Position],
+        fraction: f64,
+        allowed: &dyn Fn(Position, Direction) -> bool,
+        rng: &mut R
+    ) -> usize {
+        let mut removed = 0;
+
+        for pos in candidates {
+            if !self.is_dead_end(*pos) || !chance(rng, fraction) {
+                continue;
+            }
+
+            let tile = *self.get_tile(*pos).unwrap();
+
+            let closed: Vec<(Direction, Position)> = Direction::iter()
+                .filter(|direction| tile.get_side(*direction))
+                .filter_map(|direction| Some((direction, self.get_neighbor(*pos, direction)?)))
+                .filter(|(direction, _)| allowed(*pos, *direction))
+                .collect();
+
+            let preferred: Vec<(Direction, Position)> = closed.iter()
+                .copied()
+                .filter(|(_, neighbor)| self.is_dead_end(*neighbor))
+                .collect();
+
+            let choice = if preferred.is_empty() {
+                pick(rng, &closed)
+            } else {
+                pick(rng, &preferred)
+            };
+
+            if let Some((direction, _)) = choice {
+                self.set_wall(*pos, direction, false).unwrap();
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+
+    fn solved(seed: u64) -> (Maze, Vec<Position>) {
+        let mut maze = Maze::new(Size(15, 15), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+        let path = maze.solve_bfs();
+
+        (maze, path)
+    }
+
+    #[test]
+    fn full_braiding_leaves_no_dead_ends() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(11, 9), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+            let dead_ends = maze.get_dead_ends().len();
+
+            let removed = maze.braid_with_rng(1.0, &mut Pcg32::new(seed));
+
+            assert!(maze.get_dead_ends().is_empty(), "seed {seed}");
+            // Joining two dead ends fixes both with one wall
+            assert!(removed <= dead_ends && removed >= dead_ends / 2, "seed {seed}");
+
+            // The depth first solver still ends despite the loops
+            let path = maze.solve_maze_with_rng(&mut Pcg32::new(seed));
+            assert_eq!((path[0], path[path.len() - 1]), (Position(0, 0), Position(10, 8)), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn no_braiding_keeps_the_maze() {
+        let (mut maze, _) = solved(3);
+        let before = maze.clone();
+
+        assert_eq!(maze.braid_with_rng(0.0, &mut Pcg32::new(3)), 0);
+        assert_eq!(maze, before);
+
+        // A one cell wide strip has no wall to open at its ends
+        let mut strip = Maze::new(Size(1, 5), true);
+        strip.generate_maze_with_rng(&mut Pcg32::new(3));
+        strip.braid_with_rng(1.0, &mut Pcg32::new(3));
+
+        assert_eq!(strip.get_dead_ends(), vec![Position(0, 0), Position(0, 4)]);
+    }
+
+    #[test]
+    fn far_dead_ends_are_untouched() {
+        for seed in 0..20 {
+            let (mut maze, path) = solved(seed);
+            let before = maze.clone();
+            let distances = maze.distances_from_cells(&path);
+
+            maze.braid_near_path(&path, 2, 1.0, &mut Pcg32::new(seed));
+
+            for pos in before.get_dead_ends() {
+                if distances[pos.as_array()].unwrap() > 2 {
+                    assert_eq!(maze.get_tile(pos), before.get_tile(pos), "seed {seed} at {pos:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn loops_add_solutions_without_cutting_the_path_short() {
+        let mut braided = 0;
+
+        for seed in 0..20 {
+            let (mut maze, path) = solved(seed);
+
+            let removed = maze.braid_near_path(&path, 2, 1.0, &mut Pcg32::new(seed));
+            let shortest = maze.solve_bfs();
+
+            if removed > 0 {
+                assert!(maze.solution_count(2) > 1, "seed {seed}");
+                braided += 1;
+            }
+
+            assert!(!shortest.is_empty(), "seed {seed}");
+
+            // Each wall joins cells hanging off the path at most 2 * radius + 2 apart
+            assert!(shortest.len() + removed * 5 >= path.len(), "seed {seed}");
+            assert!(shortest.len() <= path.len(), "seed {seed}");
+        }
+
+        assert!(braided > 10);
+    }
+
+    #[test]
+    fn joins_reach_into_other_branches() {
+        // A path along the top with a branch hanging off (1, 0) that bends back up to (2, 1),
+        // which could join its own branch to the west or other cells north and east
+        let mut maze = Maze::new(Size(4, 3), true);
+
+        for (pos, direction) in [
+            (Position(0, 0), Direction::East),
+            (Position(1, 0), Direction::East),
+            (Position(2, 0), Direction::East),
+            (Position(0, 0), Direction::South),
+            (Position(0, 1), Direction::South),
+            (Position(3, 0), Direction::South),
+            (Position(3, 1), Direction::South),
+            (Position(1, 0), Direction::South),
+            (Position(1, 1), Direction::South),
+            (Position(1, 2), Direction::East),
+            (Position(2, 2), Direction::North),
+        ] {
+            maze.open_wall(pos, direction).unwrap();
+        }
+
+        let path = [Position(0, 0), Position(1, 0), Position(2, 0), Position(3, 0)];
+
+        for seed in 0..20 {
+            let mut braided = maze.clone();
+            braided.braid_near_path(&path, 4, 1.0, &mut Pcg32::new(seed));
+
+            let tile = braided.get_tile(Position(2, 1)).unwrap();
+
+            assert!(tile.get_side(Direction::West), "seed {seed}");
+            assert!(!tile.get_side(Direction::North) || !tile.get_side(Direction::East), "seed {seed}");
+        }
+    }
+}
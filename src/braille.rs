@@ -0,0 +1,126 @@
+use std::io::{self, IsTerminal};
+
+use crate::{Display, EMPTY_CHAR, RESET_COLOR};
+
+// The dot each pixel of a 2 by 4 block sets, indexed `[y][x]` within the block
+const BRAILLE_DOTS: [[u32; 2]; 4] = [
+    [0x01, 0x08],
+    [0x02, 0x10],
+    [0x04, 0x20],
+    [0x40, 0x80],
+];
+
+const BRAILLE_BLANK: u32 = 0x2800;
+
+impl Display {
+    /// Prints the display as Braille characters, see `write_braille_to`. Colors are only
+    /// printed if `color` is set and stdout is a terminal.
+    pub fn print_braille(&self) {
+        let mut stdout = io::stdout().lock();
+        let color = self.color && stdout.is_terminal();
+
+        self.write_braille_with(&mut stdout, color)
+            .unwrap();
+    }
+
+    /// Writes the display with each block of 2 by 4 pixels packed into one Braille
+    /// character, a dot for every pixel that isn't empty. That is an eighth of the
+    /// characters, for mazes too big to show any other way. Colored pixels are left out
+    /// as holes so the path shows between the walls, unless `color` is set, in which
+    /// case they are dots too and each block with one in it is drawn in its color.
+    /// Displays that don't divide into whole blocks are padded with empty pixels on the
+    /// right and bottom. Margins are kept as they are.
+    pub fn write_braille_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_braille_with(w, self.color)
+    }
+
+    fn write_braille_with<W: io::Write>(&self, w: &mut W, color: bool) -> io::Result<()> {
+        write!(w, "{}", "\n".repeat(self.margins.top))?;
+
+        for top in (0..self.size.1).step_by(4) {
+            let mut rowstring = String::new();
+            let mut current = None;
+
+            for left in (0..self.size.0).step_by(2) {
+                let mut dots = 0;
+                let mut block_color = None;
+
+                for (dy, row) in BRAILLE_DOTS.iter().enumerate() {
+                    for (dx, dot) in row.iter().enumerate() {
+                        let (y, x) = (top + dy, left + dx);
+
+                        let (Some(pixel), Some(pixel_color)) = (self.pixels.get([y, x]), self.colors.get([y, x])) else {
+                            continue;
+                        };
+
+                        if *pixel == EMPTY_CHAR || (pixel_color.is_some() && !color) {
+                            continue;
+                        }
+
+                        dots |= dot;
+                        block_color = block_color.or(*pixel_color);
+                    }
+                }
+
+                if color && block_color != current {
+                    rowstring.push_str(&block_color.map_or(RESET_COLOR.to_string(), |x| x.ansi()));
+                    current = block_color;
+                }
+
+                rowstring.push(char::from_u32(BRAILLE_BLANK + dots).unwrap());
+            }
+
+            if current.is_some() {
+                rowstring.push_str(RESET_COLOR);
+            }
+
+            write!(w, "{}", " ".repeat(self.margins.left))?;
+            write!(w, "{}", rowstring)?;
+            writeln!(w, "{}", " ".repeat(self.margins.right))?;
+        }
+
+        write!(w, "{}", "\n".repeat(self.margins.bottom))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Maze, Position, Size};
+
+    fn braille(maze: &Maze, color: bool) -> String {
+        let mut display = Display::new_from_maze(maze);
+        display.margins = Margins::none();
+        display.color = color;
+        display.draw_maze(maze).unwrap();
+        display.draw_solution(&maze.solve_bfs(), '*').unwrap();
+
+        let mut out = vec![];
+        display.write_braille_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn small_displays_are_padded_to_whole_blocks() {
+        // Three by three pixels make two blocks, the markers and the path leaving the middle
+        // column empty
+        assert_eq!(braille(&Maze::new(Size(1, 1), true), false), "⠇⠇\n");
+    }
+
+    #[test]
+    fn the_path_is_a_hole_without_color() {
+        let maze = opened(Size(2, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+
+        assert_eq!(braille(&maze, false), "⡧⡍⡇\n⠉⠁⠁\n");
+    }
+
+    #[test]
+    fn blocks_with_the_path_are_drawn_in_its_color() {
+        let maze = opened(Size(2, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+
+        // Each block takes the first color in it, green for the markers and yellow for the path
+        assert_eq!(braille(&maze, true), "\x1b[32m⡿\x1b[33m⣿\x1b[0m⡇\n⠉\x1b[32m⠉\x1b[0m⠁\n");
+    }
+}
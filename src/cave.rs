@@ -0,0 +1,231 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::rng::chance;
+use crate::{Direction, Maze, Position};
+
+/// A cell is solid after a smoothing step if at least this many cells of the 3x3 block
+/// around it were, counting itself and anything past the border.
+pub const CAVE_SOLID_NEIGHBORS: usize = 5;
+
+impl Maze {
+    pub fn generate_cave(&mut self, fill: f64, iterations: usize) -> Result<(), io::ErrorKind> {
+        self.generate_cave_with_rng(fill, iterations, &mut rand::rng())
+    }
+
+    /// Fills a fully walled maze with open caves. Each cell starts out solid with
+    /// probability `fill` and `iterations` rounds of smoothing follow, see
+    /// `CAVE_SOLID_NEIGHBORS`. Only the largest open region is kept, and tunnels join it to
+    /// the corners if they ended up elsewhere. Every wall between two open cells is removed,
+    /// so the caves are drawn as rooms and have plenty of loops.
+    pub fn generate_cave_with_rng<R: RngCore>(&mut self, fill: f64, iterations: usize, rng: &mut R) -> Result<(), io::ErrorKind> {
+        if !(0.0..=1.0).contains(&fill) {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let mut solid = Array2::from_shape_simple_fn(self.size.as_array(), || chance(rng, fill));
+
+        for _ in 0..iterations {
+            solid = smooth(&solid);
+        }
+
+        let mut open = self.largest_region(&solid);
+
+        if !open.iter().any(|x| *x) {
+            open[Position::new().as_array()] = true;
+        }
+
+        for corner in [Position::new(), self.size.get_max_pos()] {
+            self.tunnel(&mut open, corner);
+        }
+
+
+        for ((x, y), _) in open.indexed_iter().filter(|(_, open)| **open) {
+            for direction in [Direction::East, Direction::South] {
+                let Some(neighbor) = self.get_neighbor(Position(x, y), direction) else {
+                    continue;
+                };
+
+                if open[neighbor.as_array()] {
+                    self.set_wall(Position(x, y), direction, false)?;
+                }
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    // The biggest group of cells that aren't solid and touch each other, ignoring walls
+    fn largest_region(&self, solid: &Array2<bool>) -> Array2<bool> {
+        let mut region = Array2::from_elem(solid.dim(), None);
+        let mut sizes = vec![];
+
+        for ((x, y), is_solid) in solid.indexed_iter() {
+            if *is_solid || region[[x, y]].is_some() {
+                continue;
+            }
+
+            let label = sizes.len();
+            let mut queue = VecDeque::from([Position(x, y)]);
+            let mut size = 0;
+
+            region[[x, y]] = Some(label);
+
+            while let Some(pos) = queue.pop_front() {
+                size += 1;
+
+                for direction in Direction::iter() {
+                    let Some(neighbor) = self.get_neighbor(pos, direction) else {
+                        continue;
+                    };
+
+                    if !solid[neighbor.as_array()] && region[neighbor.as_array()].is_none() {
+                        region[neighbor.as_array()] = Some(label);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+
+            sizes.push(size);
+        }
+
+        let largest = (0..sizes.len()).max_by_key(|label| sizes[*label]);
+
+        region.map(|label| label.is_some() && *label == largest)
+    }
+
+    // Opens the shortest line of cells from `from` to the nearest open cell
+    fn tunnel(&self, open: &mut Array2<bool>, from: Position) {
+        let mut previous: Array2<Option<Position>> = Array2::from_elem(open.dim(), None);
+        let mut queue = VecDeque::from([from]);
+
+        previous[from.as_array()] = Some(from);
+
+        while let Some(pos) = queue.pop_front() {
+            if open[pos.as_array()] {
+                let mut pos = pos;
+
+                while pos != from {
+                    pos = previous[pos.as_array()].unwrap();
+                    open[pos.as_array()] = true;
+                }
+
+                return;
+            }
+
+            for direction in Direction::iter() {
+                let Some(neighbor) = self.get_neighbor(pos, direction) else {
+                    continue;
+                };
+
+                if previous[neighbor.as_array()].is_none() {
+                    previous[neighbor.as_array()] = Some(pos);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+}
+
+// One round of the 4-5 rule
+fn smooth(solid: &Array2<bool>) -> Array2<bool> {
+    Array2::from_shape_fn(solid.dim(), |(x, y)| {
+        let mut count = 0;
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let neighbor = x.checked_add_signed(dx)
+                    .zip(y.checked_add_signed(dy))
+                    .and_then(|(x, y)| solid.get([x, y]));
+
+                if neighbor.is_none_or(|is_solid| *is_solid) {
+                    count += 1;
+                }
+            }
+        }
+
+        count >= CAVE_SOLID_NEIGHBORS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::smooth;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Rectangle, Size};
+    use ndarray::Array2;
+    use std::io;
+
+    #[test]
+    fn open_cells_form_one_cave_between_the_corners() {
+        for (fill, seed) in [(0.45, 1), (0.45, 2), (0.55, 3), (0.6, 4)] {
+            let mut maze = Maze::new(Size(24, 16), true);
+            maze.generate_cave_with_rng(fill, 4, &mut Pcg32::new(seed)).unwrap();
+
+            let distances = maze.distances_from(Position(0, 0));
+            assert!(distances[maze.size.get_max_pos().as_array()].is_some(), "fill {fill} seed {seed}");
+
+            // Every cell with an open side is in the cave, and every wall inside it is gone
+            for ((x, y), tile) in maze.tiles.indexed_iter() {
+                let pos = Position(x, y);
+                let in_cave = distances[[x, y]].is_some();
+
+                assert_eq!(tile.get_sides().iter().any(|(_, closed)| !*closed), in_cave, "fill {fill} seed {seed} at {pos:?}");
+
+                for direction in [Direction::East, Direction::South] {
+                    if let Some(next) = maze.get_neighbor(pos, direction) {
+                        let joined = in_cave && distances[next.as_array()].is_some();
+                        assert_eq!(tile.get_side(direction), !joined, "fill {fill} seed {seed} at {pos:?} {direction}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_and_full_fills() {
+        let mut open = Maze::new(Size(6, 5), true);
+        open.generate_cave_with_rng(0.0, 0, &mut Pcg32::new(1)).unwrap();
+
+        let mut room = Maze::new(Size(6, 5), true);
+        room.carve_room(Rectangle::new(Position(0, 0), Size(6, 5))).unwrap();
+
+        assert_eq!(open, room);
+
+        // Nothing open but a tunnel from the bottom right to the top left corner
+        let mut solid = Maze::new(Size(6, 5), true);
+        solid.generate_cave_with_rng(1.0, 3, &mut Pcg32::new(1)).unwrap();
+
+        let reached = solid.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+        assert_eq!(reached, 6 + 5 - 1);
+    }
+
+    #[test]
+    fn smoothing_counts_the_border_as_solid() {
+        // The corners of an open grid have five cells past the border around them
+        let smoothed = smooth(&Array2::from_elem([3, 3], false));
+
+        assert_eq!(smoothed, Array2::from_shape_fn([3, 3], |(x, y)| x != 1 && y != 1));
+
+        // A lone solid cell in the middle of open ground doesn't last
+        let mut lone = Array2::from_elem([5, 5], false);
+        lone[[2, 2]] = true;
+
+        assert!(!smooth(&lone)[[2, 2]]);
+    }
+
+    #[test]
+    fn fills_outside_zero_to_one_are_refused() {
+        let mut maze = Maze::new(Size(4, 4), true);
+
+        assert_eq!(maze.generate_cave_with_rng(1.2, 2, &mut Pcg32::new(1)), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze, Maze::new(Size(4, 4), true));
+    }
+}
@@ -0,0 +1,271 @@
+use rand::RngCore;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use strum::IntoEnumIterator;
+
+use crate::formats::Format;
+use crate::generator::GeneratorKind;
+use crate::rng::{pick, random_index, Pcg32};
+use crate::{Direction, Maze, Position, Size};
+
+/// Largest side of the sizes `random_case` picks.
+const MAX_SIDE: usize = 40;
+
+/// One reproducible generation: running the same case always produces the same maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckCase {
+    pub size: Size,
+    pub seed: u64,
+    pub generator: GeneratorKind,
+}
+impl CheckCase {
+    pub fn generate(&self) -> Maze {
+        let mut maze = Maze::new(self.size, true);
+        maze.generate(self.generator, &mut Pcg32::new(self.seed));
+
+        maze
+    }
+}
+impl fmt::Display for CheckCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} seed {} with {}", self.size.0, self.size.1, self.seed, self.generator)
+    }
+}
+
+/// A property every generated maze has to satisfy.
+#[derive(Debug, Clone, Copy)]
+pub struct Invariant {
+    pub name: &'static str,
+    pub check: fn(&Maze, &CheckCase) -> Result<(), String>,
+}
+
+pub const INVARIANTS: [Invariant; 6] = [
+    Invariant { name: "wall symmetry", check: check_symmetry },
+    Invariant { name: "connectivity", check: check_connectivity },
+    Invariant { name: "perfectness", check: check_perfectness },
+    Invariant { name: "solvability", check: check_solvability },
+    Invariant { name: "txt round-trip", check: check_txt_round_trip },
+    Invariant { name: "json round-trip", check: check_json_round_trip },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckFailure {
+    pub case: CheckCase,
+    pub invariant: &'static str,
+    pub message: String,
+}
+impl fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed for {}: {}", self.invariant, self.case, self.message)
+    }
+}
+
+/// Generates `case` and runs every invariant on it. A panic during generation or in a
+/// check is reported as a failure instead of unwinding further.
+pub fn check_case(case: &CheckCase) -> Result<(), CheckFailure> {
+    let fail = |invariant: &'static str, message: String| CheckFailure {
+        case: *case,
+        invariant,
+        message,
+    };
+
+    let maze = panic::catch_unwind(|| case.generate())
+        .map_err(|payload| fail("generation", panic_message(payload)))?;
+
+    for invariant in INVARIANTS {
+        panic::catch_unwind(AssertUnwindSafe(|| (invariant.check)(&maze, case)))
+            .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload))))
+            .map_err(|message| fail(invariant.name, message))?;
+    }
+
+    Ok(())
+}
+
+/// A case with a random seed and generator, and a size that is often one of the shapes
+/// generators tend to get wrong: a single row or column, 2x2, or long and thin.
+pub fn random_case(rng: &mut Pcg32) -> CheckCase {
+    let side = |rng: &mut Pcg32| 1 + random_index(rng, MAX_SIDE);
+    let thin = |rng: &mut Pcg32| 1 + random_index(rng, 3);
+
+    let size = match random_index(rng, 6) {
+        0 => Size(1, side(rng)),
+        1 => Size(side(rng), 1),
+        2 => Size(2, 2),
+        3 => Size(thin(rng), side(rng)),
+        4 => Size(side(rng), thin(rng)),
+        _ => Size(side(rng), side(rng)),
+    };
+
+    let generators: Vec<GeneratorKind> = GeneratorKind::iter().collect();
+
+    CheckCase {
+        size,
+        seed: rng.next_u64(),
+        generator: pick(rng, &generators).unwrap(),
+    }
+}
+
+/// Makes a failing case smaller while it keeps failing, by halving and then decrementing
+/// either side with the same seed and generator. The result fails the same way or another
+/// way, but is never larger.
+pub fn shrink(failure: CheckFailure) -> CheckFailure {
+    let mut failure = failure;
+
+    'outer: loop {
+        let Size(width, height) = failure.case.size;
+
+        let candidates = [
+            Size(width.div_ceil(2), height),
+            Size(width, height.div_ceil(2)),
+            Size(width - 1, height),
+            Size(width, height - 1),
+        ];
+
+        for size in candidates {
+            if size == failure.case.size || size.0 == 0 || size.1 == 0 {
+                continue;
+            }
+
+            let case = CheckCase { size, ..failure.case };
+
+            if let Err(smaller) = check_case(&case) {
+                failure = smaller;
+                continue 'outer;
+            }
+        }
+
+        return failure;
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload.downcast_ref::<&str>()
+        .map(|x| x.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn check_symmetry(maze: &Maze, _: &CheckCase) -> Result<(), String> {
+    match maze.find_inconsistencies().first() {
+        Some((pos, direction)) => Err(format!("wall {} of {:?} is one-sided", direction, pos)),
+        None => Ok(()),
+    }
+}
+
+fn check_connectivity(maze: &Maze, case: &CheckCase) -> Result<(), String> {
+    if !case.generator.reaches_every_cell() {
+        return Ok(());
+    }
+
+    let distances = maze.distances_from(Position::new());
+
+    match distances.indexed_iter().find(|(_, distance)| distance.is_none()) {
+        Some(((x, y), _)) => Err(format!("{:?} can't be reached from the start", Position(x, y))),
+        None => Ok(()),
+    }
+}
+
+fn check_perfectness(maze: &Maze, case: &CheckCase) -> Result<(), String> {
+    if !case.generator.is_perfect() {
+        return Ok(());
+    }
+
+    let passages = maze.tiles
+        .indexed_iter()
+        .flat_map(|((x, y), tile)| {
+            [Direction::East, Direction::South]
+                .into_iter()
+                .filter(move |direction| !tile.get_side(*direction))
+                .map(move |direction| (Position(x, y), direction))
+        })
+        .filter(|(pos, direction)| maze.get_neighbor(*pos, *direction).is_some())
+        .count();
+
+    // A connected grid is a tree exactly when it has one passage less than it has cells
+    if passages + 1 != maze.tiles.len() {
+        return Err(format!("{} passages between {} cells", passages, maze.tiles.len()));
+    }
+
+    Ok(())
+}
+
+fn check_solvability(maze: &Maze, _: &CheckCase) -> Result<(), String> {
+    let path = maze.solve_maze();
+
+    if path.first() != Some(&Position::new()) || path.last() != Some(&maze.size.get_max_pos()) {
+        return Err(format!("solution runs from {:?} to {:?}", path.first(), path.last()));
+    }
+
+    maze.verify_path(&path)
+        .map_err(|error| error.to_string())
+}
+
+fn check_txt_round_trip(maze: &Maze, _: &CheckCase) -> Result<(), String> {
+    let mut buffer = vec![];
+
+    Format::Txt.export(maze, None, &mut buffer)
+        .map_err(|error| format!("export failed: {}", error))?;
+
+    let parsed = Format::Txt.import(&buffer)
+        .map_err(|error| format!("import failed: {}", error))?;
+
+    // Text only has room for the walls, the crossings are left to the JSON round-trip
+    if parsed.size != maze.size || parsed.tiles != maze.tiles {
+        return Err("the parsed maze differs from the original".to_string());
+    }
+
+    Ok(())
+}
+
+fn check_json_round_trip(maze: &Maze, _: &CheckCase) -> Result<(), String> {
+    let parsed = Maze::from_json(&maze.to_json())
+        .map_err(|error| format!("import failed: {}", error))?;
+
+    if parsed != *maze {
+        return Err("the parsed maze differs from the original".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_generator_holds_the_invariants() {
+        for generator in GeneratorKind::iter() {
+            for size in [Size(1, 1), Size(1, 7), Size(7, 1), Size(2, 2), Size(3, 17), Size(12, 9)] {
+                for seed in 0..3 {
+                    let case = CheckCase { size, seed, generator };
+
+                    assert_eq!(check_case(&case), Ok(()));
+                    assert_eq!(case.generate(), case.generate(), "{}", case);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_cases_stay_in_bounds() {
+        let mut rng = Pcg32::new(8);
+
+        for _ in 0..200 {
+            let case = random_case(&mut rng);
+
+            assert!((1..=MAX_SIDE).contains(&case.size.0) && (1..=MAX_SIDE).contains(&case.size.1), "{}", case);
+        }
+    }
+
+    #[test]
+    fn shrinking_never_grows_the_case() {
+        let failure = CheckFailure {
+            case: CheckCase { size: Size(9, 4), seed: 1, generator: GeneratorKind::Kruskal },
+            invariant: "connectivity",
+            message: "made up".to_string(),
+        };
+
+        // Nothing smaller fails, so the failure is kept as it was
+        assert_eq!(shrink(failure.clone()), failure);
+    }
+}
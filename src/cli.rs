@@ -0,0 +1,125 @@
+// The command line: `run` picks the subcommand, `generate` is the default one
+
+#[macro_use]
+mod error;
+mod args;
+mod check;
+mod diff;
+mod generate;
+mod interactive;
+mod load;
+mod render;
+mod screensaver;
+mod solve;
+mod stats;
+mod walk;
+
+use error::{CliError, CliResult};
+
+const GENERATE_USAGE: &str = concat!(
+    "Pass the dimension of your desired maze with 'AxY' or '--size AxY' (example: '10x20') or --fit\n",
+    "Usage: ", env!("CARGO_BIN_NAME"), " [AxY | --size AxY | --fit] [OPTIONS], see --help"
+);
+
+const USAGE: &str = concat!(
+    "Usage: ", env!("CARGO_BIN_NAME"), " [AxY | --size AxY | --fit] [OPTIONS] or ",
+    env!("CARGO_BIN_NAME"), " <COMMAND> [ARGS], see --help"
+);
+
+const HELP: &str = concat!(
+    "Generates a maze and prints it with its solution.
+
+Usage: ", env!("CARGO_BIN_NAME"), " [AxY | --size AxY | --fit] [OPTIONS]
+       ", env!("CARGO_BIN_NAME"), " <COMMAND> [ARGS]
+
+Without arguments it asks for the size on the terminal. With options but no size, the maze
+is the biggest that fits the terminal.
+
+Commands:
+  render        Draw a saved maze in another format
+  solve         Solve a saved maze, optionally through waypoints
+  walk          Check a walk through a saved maze
+  diff          Show the walls that changed between two saved mazes
+  stats         Summarize the saved mazes in a directory
+  check         Run the generators against the maze invariants
+  screensaver   Keep drawing new mazes until a key is pressed
+
+Generating:
+  --size AxY                Width and height in cells, instead of the bare AxY
+  --fit                     The biggest maze that fits the terminal, or 80x24 for files and pipes
+  --seed N                  Seed for generating and solving, printed when left out
+  --algorithm NAME          Generator to use, `--algorithm list` names them all
+  --difficulty PRESET       Pick the generator and its settings from a preset
+  --grow STRATEGY           Growing tree with the given cell picking strategy
+  --straightness P          How strongly the backtracker keeps its direction, 0 to 1
+  --symmetry KIND           Mirror the maze
+  --sparse P                Carve only this fraction of the cells
+  --braid P                 Remove dead ends with this probability
+  --unicursal               A single corridor without branches
+  --solution-path FILE      Build the maze around this solution
+  --compare GEN,GEN         The same seed through several generators side by side
+  --load FILE               Read the maze instead of generating it
+  --load-bin FILE           Read the maze from the binary format
+
+Solving:
+  --solver NAME[,NAME]      Solver to use, several are drawn over each other
+  --start CELL, --end CELL  Cells the solution runs between, X,Y, corner:nw|ne|sw|se
+                            or center
+  --endpoints POLICY        Place the entrance and exit on the border
+  --exits N                 Open N exits and rank them by distance
+  --animate-solve           Watch the solver in the terminal
+  --no-solve                Mark the start and end but leave out the path
+  --answers FILE            With --no-solve, write the solved mazes to FILE, `{}` numbers them
+  --solution-only           Print the path, a cell per line, without the maze
+
+Output:
+  -o, --output FILE         Write to a file instead of stdout, in the format of its
+                            extension
+  --format FORMAT           txt, path-json, svg, html, json, grid or png
+  --count N                 Generate N mazes, `{}` in the output name numbers them
+  --threads N               Threads for --count
+  --stats                   Print metrics under the maze
+  --stats-footer            Print size, algorithm and seed under the maze
+  --png FILE, --html FILE, --json FILE, --save-bin FILE
+                            Also write the maze in that format
+  --box, --halfblock, --braille, --axes, --heatmap [--solve]
+                            Other ways to draw the maze
+  --path-style STYLE        How the solution is drawn
+  --scale N|WxH             Widen the corridors
+  --view X,Y,WxH            Print part of the maze
+  --open, --open-bare       Open the border at the start and end
+  --wall-char C, --path-char C, --empty-char C, --start-char C, --goal-char C
+                            Characters to draw with
+  --no-color, --wide, --no-wide
+                            Terminal output settings, picked automatically otherwise
+  --animate, --delay MS, --record-cast FILE, --gif FILE, --gif-steps N
+                            Watch or record the generation
+  --expect-fingerprint HEX  Exit with 1 if the maze doesn't match
+
+Benchmarking:
+  --bench                   Time every generator and solver at the size and seed instead
+  --bench-format FORMAT     text or csv
+  --bench-iterations N      Runs to take the mean of, 10 by default
+
+  -h, --help                Print this help
+  -V, --version             Print the version
+"
+);
+
+const VERSION: &str = concat!(env!("CARGO_BIN_NAME"), " ", env!("CARGO_PKG_VERSION"));
+
+pub(crate) fn run(args: &[String]) -> CliResult {
+    match args.get(1).map(String::as_str) {
+        Some("-h" | "--help") => Err(CliError::Help(HELP.to_string())),
+        Some("-V" | "--version") => Err(CliError::Help(VERSION.to_string())),
+        Some("render") => render::render(&args[2..]),
+        Some("check") => check::check(&args[2..]),
+        Some("walk") => walk::walk(&args[2..]),
+        Some("stats") => stats::stats(&args[2..]),
+        Some("solve") => solve::solve(&args[2..]),
+        Some("diff") => diff::diff(&args[2..]),
+        Some("screensaver") => screensaver::screensaver(&args[2..]),
+        None => interactive::interactive(),
+        _ => generate::generate(&args[1..]),
+    }
+}
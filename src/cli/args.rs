@@ -0,0 +1,276 @@
+use crate::cli::error::{CliError, CliResult};
+use my_project::*;
+use my_project::arrows::PathStyle;
+use my_project::difficulty::Difficulty;
+use my_project::endpoints::EndpointPolicy;
+use my_project::formats::Format;
+use my_project::generator::GeneratorKind;
+use my_project::growing_tree::GrowStrategy;
+use my_project::solver::SolverKind;
+use my_project::symmetry::Symmetry;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+// The argument after `flag`
+pub(crate) fn value<'a>(args: &mut impl Iterator<Item = &'a String>, flag: &str, usage: &'static str) -> CliResult<&'a String> {
+    args.next().ok_or_else(|| usage_error!(usage; "{} needs a value", flag))
+}
+
+// The whole number after `flag`, like a seed or a count
+pub(crate) fn number<'a, T: FromStr>(args: &mut impl Iterator<Item = &'a String>, flag: &str, usage: &'static str) -> CliResult<T> {
+    let text = value(args, flag, usage)?;
+
+    text.trim().parse().map_err(|_| usage_error!(usage; "{} takes a whole number, got '{}'", flag, text))
+}
+
+// A value the command line left out, like the maze file, which gets just the usage
+pub(crate) trait OrUsage<T> {
+    fn or_usage(self, usage: &'static str) -> CliResult<T>;
+}
+
+impl<T> OrUsage<T> for Option<T> {
+    fn or_usage(self, usage: &'static str) -> CliResult<T> {
+        self.ok_or(CliError::Usage(None, usage))
+    }
+}
+
+pub(crate) fn parse_braid(text: &str) -> CliResult<f64> {
+    str::parse(text)
+        .ok()
+        .filter(|p| (0.0..=1.0).contains(p))
+        .ok_or_else(|| usage_error!("--braid takes a probability between 0 and 1, got '{}'", text))
+}
+
+pub(crate) fn parse_straightness(text: &str) -> CliResult<f64> {
+    str::parse(text)
+        .ok()
+        .filter(|x| (0.0..=1.0).contains(x))
+        .ok_or_else(|| usage_error!("--straightness takes a value between 0 and 1, got '{}'", text))
+}
+
+pub(crate) fn parse_sparse(text: &str) -> CliResult<f64> {
+    str::parse(text)
+        .ok()
+        .filter(|x| (0.0..=1.0).contains(x))
+        .ok_or_else(|| usage_error!("--sparse takes the fraction of cells to carve, between 0 and 1, got '{}'", text))
+}
+
+pub(crate) fn parse_grow_strategy(name: &str) -> CliResult<GrowStrategy> {
+    GrowStrategy::from_str(name).map_err(|_| usage_error!(
+        "Unknown growing tree strategy '{}', expected newest, oldest, random or mixed:P with P between 0 and 1",
+        name
+    ))
+}
+
+pub(crate) fn parse_endpoints(name: &str) -> CliResult<EndpointPolicy> {
+    EndpointPolicy::from_str(name).map_err(|_| usage_error!(
+        "Unknown endpoint policy '{}', expected one of: {}",
+        name,
+        EndpointPolicy::iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+pub(crate) fn parse_generator(name: &str) -> CliResult<GeneratorKind> {
+    GeneratorKind::from_str(name).map_err(|_| usage_error!("Unknown generator '{}', expected one of: {}", name, generator_names()))
+}
+
+// Lists the names instead of panicking, --algorithm is the flag people will mistype as
+// generators get added
+pub(crate) fn parse_algorithm(name: &str) -> CliResult<GeneratorKind> {
+    GeneratorKind::from_str(name)
+        .map_err(|_| usage_error!("Unknown algorithm '{}', expected one of: {} (or list)", name, generator_names()))
+}
+
+// What --algorithm list prints, a name per line
+pub(crate) fn algorithm_list() -> CliError {
+    CliError::Help(GeneratorKind::iter().map(|kind| format!("{}\n", kind)).collect())
+}
+
+fn generator_names() -> String {
+    GeneratorKind::iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub(crate) fn parse_solver(name: &str) -> CliResult<SolverKind> {
+    SolverKind::from_str(name).map_err(|_| usage_error!(
+        "Unknown solver '{}', expected one of: {}",
+        name,
+        SolverKind::iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+pub(crate) fn parse_path_style(name: &str) -> CliResult<PathStyle> {
+    PathStyle::from_str(name).map_err(|_| usage_error!(
+        "Unknown path style '{}', expected one of: {}",
+        name,
+        PathStyle::iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+pub(crate) fn parse_symmetry(name: &str) -> CliResult<Symmetry> {
+    Symmetry::from_str(name).map_err(|_| usage_error!(
+        "Unknown symmetry '{}', expected one of: {}",
+        name,
+        Symmetry::iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+pub(crate) fn parse_difficulty(name: &str) -> CliResult<Difficulty> {
+    Difficulty::from_str(name).map_err(|_| usage_error!(
+        "Unknown difficulty '{}', expected one of: {}",
+        name,
+        Difficulty::iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+// Takes a single character that fills one column on its own for the charset flag `flag`
+pub(crate) fn parse_char(flag: &str, text: &str) -> CliResult<char> {
+    let mut chars = text.chars();
+
+    let (Some(symbol), None) = (chars.next(), chars.next()) else {
+        return Err(usage_error!("{} takes a single character, got '{}'", flag, text));
+    };
+
+    if !Charset::is_drawable(symbol) {
+        return Err(usage_error!("{} can't be a control, combining or zero-width character, got U+{:04X}", flag, symbol as u32));
+    }
+
+    Ok(symbol)
+}
+
+// A window of cells given as `X,Y,WxH`
+pub(crate) fn parse_view(text: &str) -> Option<(Position, Size)> {
+    let (pos, size) = text.rsplit_once(',')?;
+    let (width, height) = size.split_once('x')?;
+
+    Some((parse_position(pos)?, Size(width.trim().parse().ok()?, height.trim().parse().ok()?)))
+}
+
+// A maze size as `WxH`, with at least one cell each way
+pub(crate) fn parse_size(text: &str) -> CliResult<Size> {
+    let Some((width, height)) = text.split_once('x') else {
+        return Err(usage_error!("'{}' isn't a size, expected WIDTHxHEIGHT like 10x20", text));
+    };
+
+    let side = |side: &str| side.trim().parse().map_err(|_| usage_error!(
+        "'{}' in the size '{}' isn't a whole number of cells", side, text
+    ));
+
+    let size = Size(side(width)?, side(height)?);
+
+    if size.0 == 0 || size.1 == 0 {
+        return Err(usage_error!("A maze needs at least one cell each way, got {}x{}", size.0, size.1));
+    }
+
+    Ok(size)
+}
+
+// Either `N` for both directions or `WxH`, none of them 0
+pub(crate) fn parse_scale(text: &str) -> Option<Size> {
+    let (x, y) = text.split_once('x').unwrap_or((text, text));
+
+    Some(Size(x.trim().parse().ok()?, y.trim().parse().ok()?)).filter(|scale| scale.0 > 0 && scale.1 > 0)
+}
+
+// Cells are given as `X,Y` in maze coordinates
+pub(crate) fn parse_position(text: &str) -> Option<Position> {
+    let (x, y) = text.split_once(',')?;
+
+    Some(Position(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+// A cell as X,Y or by name, a corner of the maze or its center
+pub(crate) fn parse_cell(text: &str, size: Size) -> Option<Position> {
+    let max = size.get_max_pos();
+
+    match text {
+        "center" => Some(Position(size.0 / 2, size.1 / 2)),
+        "corner:nw" => Some(Position(0, 0)),
+        "corner:ne" => Some(Position(max.0, 0)),
+        "corner:sw" => Some(Position(0, max.1)),
+        "corner:se" => Some(max),
+        _ => parse_position(text),
+    }
+}
+
+fn supported_formats() -> String {
+    Format::iter()
+        .map(|format| format.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub(crate) fn parse_format(name: &str) -> CliResult<Format> {
+    Format::from_str(name)
+        .map_err(|_| usage_error!("Unknown format '{}', expected one of: {}", name, supported_formats()))
+}
+
+pub(crate) fn detect_format(path: &Path) -> CliResult<Format> {
+    Format::detect(path).map_err(|error| usage_error!("{}", describe_detect_error(path, error)))
+}
+
+pub(crate) fn describe_detect_error(path: &Path, error: io::ErrorKind) -> String {
+    match error {
+        io::ErrorKind::InvalidInput => format!(
+            "'{}' could be more than one format, pick one with --input-format or --format",
+            path.display()
+        ),
+        _ => format!(
+            "Can't tell the format of '{}', expected one of: {}",
+            path.display(),
+            supported_formats()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::USAGE;
+
+    #[test]
+    fn bad_sizes_are_usage_errors() {
+        assert_eq!(parse_size("10x20").ok(), Some(Size(10, 20)));
+
+        for (text, expected) in [
+            ("10", "'10' isn't a size, expected WIDTHxHEIGHT like 10x20"),
+            ("axb", "'a' in the size 'axb' isn't a whole number of cells"),
+            ("0x5", "A maze needs at least one cell each way, got 0x5"),
+        ] {
+            match parse_size(text) {
+                Err(error @ CliError::Usage(..)) => assert_eq!(error.to_string(), expected),
+                _ => panic!("'{}' was taken as a size", text),
+            }
+        }
+    }
+
+    #[test]
+    fn bad_numbers_name_the_flag_and_the_value() {
+        let args = ["abc".to_string()];
+
+        let error = number::<u64>(&mut args.iter(), "--seed", USAGE).err().unwrap();
+        assert_eq!(error.to_string(), "--seed takes a whole number, got 'abc'");
+
+        let error = value(&mut [].iter(), "--seed", USAGE).err().unwrap();
+        assert_eq!(error.to_string(), "--seed needs a value");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cli::args::number;
+use crate::cli::error::{CliError, CliResult};
+use my_project::batch::batch_seed;
+use my_project::check as invariants;
+use my_project::rng::Pcg32;
+
+// Fuzzes every generator with random cases until one breaks an invariant, then shrinks
+// that case so the reported size, seed and generator reproduce it as simply as possible.
+pub(crate) fn check(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: check [--iterations N] [--seed N]";
+
+    let mut iterations = 1000;
+    let mut seed = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--iterations" => iterations = number(&mut args, arg, INVALID_INPUT)?,
+            "--seed" => seed = Some(number(&mut args, arg, INVALID_INPUT)?),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let seed = seed.unwrap_or_else(rand::random);
+    eprintln!("Checking {} cases from seed {}", iterations, seed);
+
+    // Panics are caught and reported as failures, the default hook would only add noise
+    std::panic::set_hook(Box::new(|_| {}));
+
+    for i in 0..iterations {
+        let case = invariants::random_case(&mut Pcg32::new(batch_seed(seed, i)));
+
+        if let Err(failure) = invariants::check_case(&case) {
+            eprintln!();
+
+            return Err(failure!("Case {}: {}\nShrunk: {}", i + 1, failure, invariants::shrink(failure.clone())));
+        }
+
+        eprint!("\rChecked {}/{}", i + 1, iterations);
+    }
+
+    eprintln!();
+    eprintln!("All invariants held");
+
+    Ok(())
+}
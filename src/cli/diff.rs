@@ -0,0 +1,62 @@
+use crate::cli::args::{parse_format, value};
+use crate::cli::error::{CliError, CliResult, check_drawn, check_stdout, print_display};
+use crate::cli::load::load_maze;
+use my_project::*;
+use my_project::diff::WallChange;
+use std::io::{self, Write};
+use std::path::Path;
+
+// Lists the walls that changed from the first maze to the second, or draws the second one
+// with the changes highlighted with --render
+pub(crate) fn diff(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: diff <before> <after> [--render] [--input-format FORMAT]";
+
+    let mut inputs = vec![];
+    let mut input_format = None;
+    let mut render = false;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--render" => render = true,
+            "--input-format" => input_format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            _ if inputs.len() < 2 => inputs.push(Path::new(arg)),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let [before, after] = inputs[..] else {
+        return Err(CliError::Usage(None, INVALID_INPUT));
+    };
+
+    let before = load_maze(before, input_format)?;
+    let after = load_maze(after, input_format)?;
+
+    let changes = before.diff(&after).map_err(|_| failure!(
+        "Can't compare a {}x{} maze with a {}x{} one",
+        before.size.0, before.size.1, after.size.0, after.size.1
+    ))?;
+
+    if render {
+        let mut display = Display::new_from_maze(&after);
+        check_drawn(display.draw_maze(&after))?;
+        display.draw_diff(&changes);
+
+        return print_display(display);
+    }
+
+    let mut stdout = io::stdout().lock();
+
+    let written = changes.iter().try_for_each(|change| {
+        let verb = match change.change {
+            WallChange::Opened => "opened",
+            WallChange::Closed => "closed",
+        };
+
+        writeln!(stdout, "{} ({}, {}) {}", verb, change.pos.0, change.pos.1, change.direction)
+    });
+
+    check_stdout(written)
+}
@@ -0,0 +1,113 @@
+use my_project::*;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+// Why a command stopped before finishing, see `CliError::report`. `usage_error!` builds the
+// error for a mistake on the command line, `usage_error!(INVALID_INPUT; ...)` with a
+// command's own usage under the message.
+macro_rules! usage_error {
+    ($usage:expr; $($arg:tt)*) => {
+        $crate::cli::error::CliError::Usage(Some(format!($($arg)*)), $usage)
+    };
+    ($($arg:tt)*) => {
+        usage_error!($crate::cli::USAGE; $($arg)*)
+    };
+}
+
+// The error for something that went wrong once the command line made sense, like a file
+// that can't be read or a maze without a way through
+macro_rules! failure {
+    ($($arg:tt)*) => {
+        $crate::cli::error::CliError::Failed(format!($($arg)*))
+    };
+}
+
+// Ends a command early. Help, --version and other lists asked for go to stdout with exit
+// code 0, a mistake on the command line exits with 2 above the usage, a walk that stops
+// short of the exit with 3, and anything else that goes wrong after that with 1.
+pub(crate) enum CliError {
+    Help(String),
+    Usage(Option<String>, &'static str),
+    Failed(String),
+    // A walk that's valid but doesn't get from the entrance to the exit
+    Incomplete(String),
+    // Ctrl-C during an animation, once the terminal is back the way it was
+    Interrupted,
+}
+
+pub(crate) type CliResult<T = ()> = Result<T, CliError>;
+
+// The message without the usage, as a batch lists the mazes that failed
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Help(text) | CliError::Failed(text) | CliError::Incomplete(text) => write!(f, "{}", text),
+            CliError::Usage(message, usage) => write!(f, "{}", message.as_deref().unwrap_or(usage)),
+            CliError::Interrupted => write!(f, "Interrupted"),
+        }
+    }
+}
+
+impl CliError {
+    // Prints the one line about the error and picks the exit code
+    pub(crate) fn report(self) -> ExitCode {
+        match &self {
+            CliError::Help(text) => {
+                // A closed pipe, like `--help | head`, isn't worth an error
+                let _ = writeln!(io::stdout(), "{}", text.trim_end());
+            },
+            CliError::Usage(Some(_), usage) => eprintln!("error: {}\n{}", self, usage),
+            CliError::Usage(None, usage) => eprintln!("{}", usage),
+            CliError::Failed(_) | CliError::Incomplete(_) => eprintln!("error: {}", self),
+            CliError::Interrupted => {},
+        }
+
+        match self {
+            CliError::Help(_) => ExitCode::SUCCESS,
+            CliError::Usage(..) => ExitCode::from(2),
+            CliError::Incomplete(_) => ExitCode::from(3),
+            CliError::Failed(_) => ExitCode::FAILURE,
+            CliError::Interrupted => ExitCode::from(130),
+        }
+    }
+}
+
+// The reason, like a full disk or a missing permission, if `path` couldn't be written
+pub(crate) fn check_written<T>(path: &Path, written: io::Result<T>) -> CliResult<T> {
+    written.map_err(|error| failure!("Couldn't write '{}': {}", path.display(), error))
+}
+
+// Like `check_written` for stdout, except that a closed pipe, like piping into head, just
+// ends the output early
+pub(crate) fn check_stdout(written: io::Result<()>) -> CliResult {
+    match written {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => Err(failure!("Couldn't write to stdout: {}", error)),
+        _ => Ok(()),
+    }
+}
+
+// `Display::print` with a failed write as `CliError::Failed`, see `check_stdout`
+pub(crate) fn print_display(display: Display) -> CliResult {
+    check_stdout(display.print())
+}
+
+// Drawing on a display only fails when it wasn't made for the maze
+pub(crate) fn check_drawn(drawn: Result<(), io::ErrorKind>) -> CliResult {
+    drawn.map_err(|error| failure!("Couldn't draw the maze: {}", io::Error::from(error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::USAGE;
+
+    #[test]
+    fn errors_pick_the_exit_code() {
+        assert_eq!(CliError::Usage(None, USAGE).report(), ExitCode::from(2));
+        assert_eq!(failure!("broken").report(), ExitCode::FAILURE);
+        assert_eq!(CliError::Incomplete("stopped".to_string()).report(), ExitCode::from(3));
+        assert_eq!(CliError::Interrupted.report(), ExitCode::from(130));
+    }
+}
@@ -0,0 +1,302 @@
+mod batch;
+mod bench;
+mod build;
+mod parse;
+mod render;
+pub(super) mod solve;
+mod validate;
+
+use crate::cli::error::{CliResult, check_stdout, check_written};
+use my_project::*;
+use my_project::arrows::PathStyle;
+use my_project::difficulty::Difficulty;
+use my_project::endpoints::{EndpointPolicy, Endpoints};
+use my_project::formats::Format;
+use my_project::generator::GeneratorKind;
+use my_project::growing_tree::GrowStrategy;
+use my_project::save;
+use my_project::solver::SolverKind;
+use my_project::symmetry::Symmetry;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use batch::generate_batch;
+use bench::{BENCH_ITERATIONS, run_bench};
+use build::{
+    animate_solve, build_maze, generate_animated, generate_compared, generate_gif,
+    generate_recorded,
+};
+use parse::parse_generate_args;
+use render::{seed_footer, write_maze};
+use solve::solve_generated;
+
+// Keeps the exits of a seeded maze independent of how many numbers generation used up
+const EXITS_SEED_SALT: u64 = 1;
+const ENDPOINTS_SEED_SALT: u64 = 2;
+const BRAID_SEED_SALT: u64 = 3;
+const MOUSE_SEED_SALT: u64 = 4;
+const SOLVE_SEED_SALT: u64 = 5;
+
+// Everything `generate` was asked for, grouped by the step of the pipeline that reads it
+#[derive(Clone)]
+struct GenerateOptions {
+    size: Size,
+    seed: Option<u64>,
+    // Set when --seed was missing and `generate` picked the seed, see `seed_footer`
+    seed_chosen: bool,
+    compare: Option<Vec<GeneratorKind>>,
+    maze: MazeOptions,
+    solve: SolveOptions,
+    draw: DrawOptions,
+    output: OutputOptions,
+    animation: AnimationOptions,
+    bench: BenchOptions,
+}
+
+// What shapes the maze, or where it's loaded from instead
+#[derive(Clone, Default)]
+struct MazeOptions {
+    algorithm: Option<GeneratorKind>,
+    difficulty: Option<Difficulty>,
+    grow: Option<GrowStrategy>,
+    braid: Option<f64>,
+    straightness: Option<f64>,
+    symmetry: Option<Symmetry>,
+    sparse: Option<f64>,
+    unicursal: bool,
+    route: Option<Vec<Position>>,
+    exits: Option<usize>,
+    endpoints: Option<EndpointPolicy>,
+    load: Option<PathBuf>,
+    load_bin: Option<PathBuf>,
+}
+
+// Which path is searched for, and by what
+#[derive(Clone, Default)]
+struct SolveOptions {
+    start: Option<Position>,
+    goal: Option<Position>,
+    solver: Option<SolverKind>,
+    overlay: Vec<SolverKind>,
+    no_solve: bool,
+    solution_only: bool,
+    random_mouse: bool,
+}
+
+// How the maze is drawn
+#[derive(Clone)]
+struct DrawOptions {
+    format: Format,
+    box_drawing: bool,
+    halfblock: bool,
+    braille: bool,
+    path_style: PathStyle,
+    heatmap: bool,
+    heatmap_path: bool,
+    charset: Charset,
+    wide: bool,
+    view: Option<(Position, Size)>,
+    axes: bool,
+    terminal: Option<Size>,
+    color: bool,
+    open: bool,
+    open_bare: bool,
+    scale: Size,
+    stats: bool,
+    stats_footer: bool,
+}
+
+impl Default for DrawOptions {
+    fn default() -> Self {
+        DrawOptions {
+            format: Format::Txt,
+            box_drawing: false,
+            halfblock: false,
+            braille: false,
+            path_style: PathStyle::Dots,
+            heatmap: false,
+            heatmap_path: false,
+            charset: Charset::default(),
+            wide: false,
+            view: None,
+            axes: false,
+            terminal: None,
+            color: false,
+            open: false,
+            open_bare: false,
+            scale: Size(1, 1),
+            stats: false,
+            stats_footer: false,
+        }
+    }
+}
+
+// Where the maze and the files next to it are written
+#[derive(Clone)]
+struct OutputOptions {
+    path: Option<String>,
+    png: Option<String>,
+    html: Option<String>,
+    json: Option<String>,
+    save_bin: Option<String>,
+    answers: Option<String>,
+    count: Option<usize>,
+    threads: usize,
+    expect_fingerprint: Option<String>,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        OutputOptions {
+            path: None,
+            png: None,
+            html: None,
+            json: None,
+            save_bin: None,
+            answers: None,
+            count: None,
+            threads: thread::available_parallelism().map_or(1, |x| x.get()),
+            expect_fingerprint: None,
+        }
+    }
+}
+
+// Generation played back in the terminal or recorded to a file
+#[derive(Clone)]
+struct AnimationOptions {
+    animate: bool,
+    animate_solve: bool,
+    delay: Duration,
+    record_cast: Option<String>,
+    gif: Option<String>,
+    gif_steps: Option<usize>,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            animate: false,
+            animate_solve: false,
+            delay: Duration::from_millis(20),
+            record_cast: None,
+            gif: None,
+            gif_steps: None,
+        }
+    }
+}
+
+// --bench times the generators and solvers instead of printing a maze
+#[derive(Clone)]
+struct BenchOptions {
+    run: bool,
+    csv: bool,
+    iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions { run: false, csv: false, iterations: BENCH_ITERATIONS }
+    }
+}
+
+// Parses the options, then generates or loads the maze, solves it unless --no-solve and
+// renders it, see `write_maze`
+pub(crate) fn generate(args: &[String]) -> CliResult {
+    let (mut options, loaded) = parse_generate_args(args)?;
+
+    // A maze without --seed still gets one, so it can be made again from what's printed at the end
+    let chosen_seed = (options.seed.is_none() && loaded.is_none()).then(rand::random::<u64>);
+    options.seed = options.seed.or(chosen_seed);
+    options.seed_chosen = chosen_seed.is_some();
+
+    if options.bench.run {
+        run_bench(&options)?;
+        report_seed(chosen_seed);
+        return Ok(());
+    }
+
+    if let Some(kinds) = &options.compare {
+        return generate_compared(&options, kinds);
+    }
+
+    if let Some(count) = options.output.count {
+        // The seed is worth knowing even if some of the mazes couldn't be written
+        let generated = generate_batch(&options, count);
+        report_seed(chosen_seed);
+        return generated;
+    }
+
+    let (maze, endpoints) = match (&options.animation.record_cast, &options.animation.gif, loaded) {
+        (Some(path), _, _) => (generate_recorded(&options, Path::new(path))?, Endpoints::corners(options.size)),
+        (_, Some(path), _) => (generate_gif(&options, Path::new(path))?, Endpoints::corners(options.size)),
+        (_, _, Some(maze)) => (maze, Endpoints::corners(options.size)),
+        _ if options.animation.animate => generate_animated(&options)?,
+        _ => build_maze(&options, options.seed)?,
+    };
+
+    if options.animation.animate_solve {
+        animate_solve(&options, &maze, &endpoints)?;
+    }
+
+    let solved = solve_generated(&options, &maze, &endpoints)?;
+
+    match &options.output.path {
+        Some(output) => {
+            let written = save::write_atomically(Path::new(output), |file| {
+                write_maze(&options, &maze, &endpoints, &solved, options.seed, file)
+            });
+
+            check_written(Path::new(output), written)?;
+        },
+        None => {
+            let mut stdout = io::BufWriter::new(io::stdout().lock());
+
+            check_stdout(write_maze(&options, &maze, &endpoints, &solved, options.seed, &mut stdout).and_then(|_| stdout.flush()))?;
+        },
+    }
+
+    if let Some(answers) = &options.output.answers {
+        let answer = answer_options(&options);
+        let solved = solve_generated(&answer, &maze, &endpoints)?;
+        let written = save::write_atomically(Path::new(answers), |file| {
+            write_maze(&answer, &maze, &endpoints, &solved, options.seed, file)
+        });
+
+        check_written(Path::new(answers), written)?;
+    }
+
+    if seed_footer(&options, options.seed).is_none() {
+        report_seed(chosen_seed);
+    }
+
+    if let Some(expected) = &options.output.expect_fingerprint {
+        let fingerprint = maze.fingerprint();
+
+        if fingerprint != *expected {
+            return Err(failure!("Fingerprint mismatch: expected {}, got {}", expected, fingerprint));
+        }
+    }
+
+    Ok(())
+}
+
+// The same rendering with the path drawn in, for --answers. The side files only get written
+// once, by the options the maze was asked for with.
+fn answer_options(options: &GenerateOptions) -> GenerateOptions {
+    let mut answer = options.clone();
+
+    answer.solve.no_solve = false;
+    answer.output = OutputOptions { answers: None, png: None, html: None, json: None, save_bin: None, ..options.output.clone() };
+
+    answer
+}
+
+// For what has no footer to put the seed in, like the bench table or the formats other than
+// text. It goes to stderr so the output stays the same for the same seed.
+fn report_seed(chosen_seed: Option<u64>) {
+    if let Some(seed) = chosen_seed {
+        eprintln!("Seed: {} (pass --seed {} to make this maze again)", seed, seed);
+    }
+}
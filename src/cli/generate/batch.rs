@@ -0,0 +1,120 @@
+use crate::cli::error::{CliResult, check_stdout, check_written};
+use my_project::*;
+use my_project::batch::{self, batch_seed};
+use my_project::endpoints::Endpoints;
+use my_project::save;
+use std::io::{self, Write};
+use std::path::Path;
+use super::build::build_maze;
+use super::render::write_maze;
+use super::solve::solve_generated;
+use super::{GenerateOptions, answer_options};
+
+// Mazes are generated and rendered on all threads, then either written to numbered files
+// as they finish or printed in order once the whole batch is done. The solved versions of
+// --answers go the same way, into their own file or files.
+pub(super) fn generate_batch(options: &GenerateOptions, count: usize) -> CliResult {
+    let base_seed = options.seed.unwrap_or_else(rand::random);
+    let answer = options.output.answers.as_ref().map(|_| answer_options(options));
+
+    // Without a '{}' for the maze number every maze goes into the one file
+    let single_file = options.output.path.as_ref().filter(|pattern| !pattern.contains("{}"));
+    let answers_file = options.output.answers.as_ref().filter(|pattern| !pattern.contains("{}"));
+
+    let results = batch::run_parallel(
+        count,
+        options.output.threads,
+        |index| -> CliResult<_> {
+            let seed = batch_seed(base_seed, index);
+            let (maze, endpoints) = build_maze(options, Some(seed))?;
+
+            let buffer = render_batch_maze(options, &maze, &endpoints, seed)?;
+            let answer = answer.as_ref()
+                .map(|answer| render_batch_maze(answer, &maze, &endpoints, seed))
+                .transpose()?;
+
+            Ok((
+                numbered_or_kept(options.output.path.as_ref(), index, buffer)?,
+                answer.map(|buffer| numbered_or_kept(options.output.answers.as_ref(), index, buffer)).transpose()?.flatten(),
+            ))
+        },
+        |finished| eprint!("\rGenerated {}/{}", finished, count)
+    );
+
+    eprintln!();
+
+
+    let mut failed = 0;
+    let (mut mazes, mut answers) = (vec![], vec![]);
+
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Ok((buffer, answer)) => {
+                mazes.extend(buffer);
+                answers.extend(answer);
+            },
+            Err(error) => {
+                eprintln!("Maze {}: {}", index + 1, error);
+                failed += 1;
+            },
+        }
+    }
+
+    // The single files are only put in place once every maze is in them
+    match single_file {
+        Some(path) => check_written(Path::new(path), save::write_atomically(Path::new(path), |w| write_separated(w, &mazes)))?,
+        None => {
+            let mut stdout = io::stdout().lock();
+
+            check_stdout(write_separated(&mut stdout, &mazes).and_then(|_| stdout.flush()))?;
+        },
+    }
+
+    if let Some(path) = answers_file {
+        check_written(Path::new(path), save::write_atomically(Path::new(path), |w| write_separated(w, &answers)))?;
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(failure!("{} of {} mazes failed", failed, count)),
+    }
+}
+
+// One maze of the batch as it's printed, or the reason it couldn't be solved
+fn render_batch_maze(options: &GenerateOptions, maze: &Maze, endpoints: &Endpoints, seed: u64) -> CliResult<Vec<u8>> {
+    let solved = solve_generated(options, maze, endpoints)?;
+    let mut buffer = vec![];
+
+    // --count turns down the side files, so only the rendering is left and it can't fail to
+    // write to memory
+    write_maze(options, maze, endpoints, &solved, Some(seed), &mut buffer)
+        .map_err(|error| failure!("Couldn't render the maze: {}", error))?;
+
+    Ok(buffer)
+}
+
+// Writes the maze to its numbered file when `pattern` has a '{}' for the number, otherwise
+// hands it back to be printed with the others
+fn numbered_or_kept(pattern: Option<&String>, index: usize, buffer: Vec<u8>) -> CliResult<Option<Vec<u8>>> {
+    match pattern {
+        Some(pattern) if pattern.contains("{}") => {
+            let path = pattern.replace("{}", &(index + 1).to_string());
+
+            check_written(Path::new(&path), save::write_atomically(Path::new(&path), |w| w.write_all(&buffer))).map(|_| None)
+        },
+        _ => Ok(Some(buffer)),
+    }
+}
+
+// Mazes are a blank line apart, which the top margin of a printed maze already is
+fn write_separated<W: Write>(w: &mut W, buffers: &[Vec<u8>]) -> io::Result<()> {
+    for (i, buffer) in buffers.iter().enumerate() {
+        if i > 0 && buffer.first() != Some(&b'\n') {
+            w.write_all(b"\n")?;
+        }
+
+        w.write_all(buffer)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::cli::error::{CliResult, check_stdout};
+use my_project::*;
+use my_project::generator::GeneratorKind;
+use my_project::rng::{mix_seed, Pcg32};
+use my_project::solver::SolverKind;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
+use super::{GenerateOptions, SOLVE_SEED_SALT};
+
+// Runs of each generator and solver that --bench takes the mean of, unless --bench-iterations
+// says otherwise
+pub(super) const BENCH_ITERATIONS: usize = 10;
+
+// One line of the --bench table. Only the solvers expand nodes and find a path, and both are
+// means over the runs like the time. The path length only counts runs that found one.
+struct BenchRow {
+    kind: &'static str,
+    name: String,
+    mean: Duration,
+    expanded: Option<usize>,
+    length: Option<usize>,
+}
+
+// The mean time of every generator on a maze of the chosen size and seed, then of every
+// solver between the corners of the maze --algorithm builds from that seed. Every generator
+// run starts from the same seed, so they all do the same work. Each solver run draws from
+// a stream seeded with the seed and the run's number, so the random solvers are averaged
+// over that many walks and a seed still gives the same table.
+pub(super) fn run_bench(options: &GenerateOptions) -> CliResult {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let iterations = options.bench.iterations;
+    let mut rows = vec![];
+
+    for kind in GeneratorKind::iter() {
+        let mut elapsed = Duration::ZERO;
+
+        for _ in 0..iterations {
+            let mut maze = Maze::new(options.size, true);
+            let mut rng = Pcg32::new(seed);
+
+            let start = Instant::now();
+            maze.generate(kind, &mut rng);
+            elapsed += start.elapsed();
+        }
+
+        rows.push(BenchRow {
+            kind: "generator",
+            name: kind.to_string(),
+            mean: elapsed / iterations as u32,
+            expanded: None,
+            length: None,
+        });
+    }
+
+    let mut maze = Maze::new(options.size, true);
+    maze.generate(options.maze.algorithm.unwrap_or(GeneratorKind::Backtracker), &mut Pcg32::new(seed));
+
+    for kind in SolverKind::iter() {
+        let (mut elapsed, mut expanded, mut lengths) = (Duration::ZERO, 0, vec![]);
+
+        for run in 0..iterations {
+            let mut rng = Pcg32::new(mix_seed(seed, &[SOLVE_SEED_SALT, run as u64]));
+
+            let start = Instant::now();
+            let path = kind.solver().solve_counted(&maze, Position::new(), options.size.get_max_pos(), &mut rng, &mut expanded);
+            elapsed += start.elapsed();
+
+            lengths.extend(path.ok().map(|path| path.len() - 1));
+        }
+
+        let length = (!lengths.is_empty()).then(|| lengths.iter().sum::<usize>() / lengths.len());
+
+        rows.push(BenchRow {
+            kind: "solver",
+            name: kind.to_string(),
+            mean: elapsed / iterations as u32,
+            expanded: Some(expanded / iterations),
+            length,
+        });
+    }
+
+    let mut stdout = io::stdout().lock();
+
+    check_stdout(write_bench(options, seed, &rows, &mut stdout).and_then(|_| stdout.flush()))
+}
+
+// Cells per second counts the whole maze for the solvers too, which keeps the rows comparable
+// across sizes. The CSV leaves what doesn't apply empty, the table puts a dash there.
+fn write_bench<W: Write>(options: &GenerateOptions, seed: u64, rows: &[BenchRow], w: &mut W) -> io::Result<()> {
+    let cells = options.size.0 * options.size.1;
+    let per_second = |mean: Duration| cells as f64 / mean.as_secs_f64().max(f64::MIN_POSITIVE);
+    let or_blank = |value: Option<usize>, blank: &str| value.map_or(blank.to_string(), |x| x.to_string());
+
+    if options.bench.csv {
+        writeln!(w, "kind,algorithm,mean_seconds,cells_per_second,expanded,path_length")?;
+
+        for row in rows {
+            writeln!(
+                w,
+                "{},{},{:.9},{:.0},{},{}",
+                row.kind, row.name, row.mean.as_secs_f64(), per_second(row.mean), or_blank(row.expanded, ""), or_blank(row.length, "")
+            )?;
+        }
+
+        return Ok(());
+    }
+
+    writeln!(w, "{}x{}, seed {}, mean of {} runs", options.size.0, options.size.1, seed, options.bench.iterations)?;
+    writeln!(w, "{:<10} {:<12} {:>12} {:>14} {:>9} {:>6}", "", "", "Mean", "Cells/s", "Expanded", "Path")?;
+
+    for row in rows {
+        writeln!(
+            w,
+            "{:<10} {:<12} {:>12} {:>14.0} {:>9} {:>6}",
+            row.kind, row.name, format!("{:.3?}", row.mean), per_second(row.mean), or_blank(row.expanded, "-"), or_blank(row.length, "-")
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use crate::cli::error::{CliError, CliResult, check_stdout, check_written};
+use my_project::*;
+use my_project::animation;
+use my_project::endpoints::Endpoints;
+use my_project::exits::ENTRANCE;
+use my_project::generator::{GenerationOptions, GeneratorKind};
+use my_project::rng::{mix_seed, Pcg32};
+use my_project::save;
+use my_project::solver::SolverKind;
+use my_project::terminal;
+use my_project::unicursal::UNICURSAL_ENDS;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use super::render::seed_footer;
+use super::solve::{solve_corners, solve_ends, solve_rng};
+use super::{BRAID_SEED_SALT, ENDPOINTS_SEED_SALT, EXITS_SEED_SALT, GenerateOptions};
+
+// Empty columns between the mazes of --compare
+const COMPARE_GAP: usize = 3;
+
+// Returns the maze with the ends its solution runs between, the corners unless --endpoints
+// or the --difficulty preset picked others
+pub(super) fn build_maze(options: &GenerateOptions, seed: Option<u64>) -> CliResult<(Maze, Endpoints)> {
+    // A unicursal labyrinth is grown from a maze half its size
+    let size = match options.maze.unicursal {
+        true => Size(options.size.0 / 2, options.size.1 / 2),
+        false => options.size,
+    };
+
+    let mut maze = Maze::new(size, true);
+
+    if let Some(route) = &options.maze.route {
+        match seed {
+            Some(seed) => maze.generate_from_route(route, &mut Pcg32::new(seed)),
+            None => maze.generate_from_route(route, &mut rand::rng()),
+        }.map_err(|_| failure!("The solution path must be a simple path of neighbouring cells inside the maze"))?;
+
+        return Ok((maze, Endpoints::corners(options.size)));
+    }
+
+    // Set when the difficulty preset picked the ends
+    let mut chosen = None;
+
+    // The settings were checked while parsing, so only a size the generator can't work with
+    // is left to fail on
+    let generated = match (options.maze.difficulty, options.maze.straightness, options.maze.symmetry, options.maze.sparse) {
+        (Some(difficulty), ..) => {
+            let (ends, _) = match seed {
+                Some(seed) => maze.generate_with_difficulty(difficulty, &mut Pcg32::new(seed)),
+                None => maze.generate_with_difficulty(difficulty, &mut rand::rng()),
+            };
+
+            chosen = Some(ends);
+
+            Ok(())
+        },
+        (None, Some(straightness), ..) => maze.generate_maze_with(&GenerationOptions {
+            straightness,
+            seed,
+            ..GenerationOptions::default()
+        }),
+        (None, None, Some(symmetry), _) => {
+            match seed {
+                Some(seed) => maze.generate_symmetric_with_rng(symmetry, &mut Pcg32::new(seed)),
+                None => maze.generate_symmetric(symmetry),
+            }
+
+            Ok(())
+        },
+        (None, None, None, Some(fill)) => match seed {
+            Some(seed) => maze.generate_sparse_with_rng(fill, &mut Pcg32::new(seed)),
+            None => maze.generate_sparse(fill),
+        },
+        (None, None, None, None) => {
+            match (options.maze.algorithm.filter(|kind| *kind != GeneratorKind::Backtracker), options.maze.grow, seed) {
+                (Some(kind), _, Some(seed)) => maze.generate(kind, &mut Pcg32::new(seed)),
+                (Some(kind), _, None) => maze.generate(kind, &mut rand::rng()),
+                (None, Some(strategy), Some(seed)) => maze.generate_growing_tree_with_rng(strategy, &mut Pcg32::new(seed)),
+                (None, Some(strategy), None) => maze.generate_growing_tree(strategy),
+                (None, None, Some(seed)) => maze.generate_maze_seeded(seed),
+                (None, None, None) => maze.generate_maze(),
+            }
+
+            Ok(())
+        },
+    };
+
+    generated.map_err(|error| failure!("Couldn't generate a {}x{} maze: {}", size.0, size.1, io::Error::from(error)))?;
+
+    if options.maze.unicursal {
+        let mut maze = maze.to_unicursal();
+
+        for (pos, direction) in [UNICURSAL_ENDS.entrance, UNICURSAL_ENDS.exit] {
+            maze.open_border(pos, direction)
+                .map_err(|error| failure!("Couldn't open the unicursal maze at {},{}: {}", pos.0, pos.1, io::Error::from(error)))?;
+        }
+
+        return Ok((maze, UNICURSAL_ENDS));
+    }
+
+    if let Some(p) = options.maze.braid {
+        match seed {
+            Some(seed) => maze.braid_with_rng(p, &mut Pcg32::new(mix_seed(seed, &[BRAID_SEED_SALT]))),
+            None => maze.braid(p),
+        };
+    }
+
+    if let Some(count) = options.maze.exits {
+        match seed {
+            Some(seed) => maze.place_exits(count, &mut Pcg32::new(mix_seed(seed, &[EXITS_SEED_SALT]))),
+            None => maze.place_exits(count, &mut rand::rng()),
+        }.map_err(|_| usage_error!("A {}x{} maze is too small for {} exits", maze.size.0, maze.size.1, count))?;
+    }
+
+    let endpoints = match (chosen, options.maze.endpoints, seed) {
+        (Some(ends), ..) => ends,
+        (None, Some(policy), Some(seed)) => maze.place_endpoints(policy, &mut Pcg32::new(mix_seed(seed, &[ENDPOINTS_SEED_SALT]))),
+        (None, Some(policy), None) => maze.place_endpoints(policy, &mut rand::rng()),
+        (None, None, _) => Endpoints::corners(options.size),
+    };
+
+    Ok((maze, endpoints))
+}
+
+pub(super) fn generate_recorded(options: &GenerateOptions, path: &Path) -> CliResult<Maze> {
+    let mut maze = Maze::new(options.size, true);
+
+    let log = match options.seed {
+        Some(seed) => maze.generate_maze_logged_with_rng(&mut Pcg32::new(seed)),
+        None => maze.generate_maze_logged(),
+    };
+
+    let frames = animation::generation_frames(&log, Some(&solve_corners(&maze, &mut solve_rng(options.seed))?), options.animation.delay);
+    let terminal = Display::new_from_maze(&maze).get_outer_size();
+
+    let mut buffer = vec![];
+    check_written(path, animation::write_cast(&mut buffer, terminal.0, terminal.1 + 1, &frames).and_then(|_| fs::write(path, buffer)))?;
+
+    Ok(maze)
+}
+
+// Every generator carves a maze of the same size from the same seed, and they're printed
+// in a row with their names underneath
+pub(super) fn generate_compared(options: &GenerateOptions, kinds: &[GeneratorKind]) -> CliResult {
+    let mazes: Vec<(Maze, Option<Vec<Position>>)> = kinds.iter()
+        .map(|kind| {
+            let mut maze = Maze::new(options.size, true);
+
+            match options.seed {
+                Some(seed) => maze.generate(*kind, &mut Pcg32::new(seed)),
+                None => maze.generate(*kind, &mut rand::rng()),
+            }
+
+            // A cave whose corners aren't connected is drawn without a path
+            let path = match options.solve.solver {
+                _ if options.solve.no_solve => None,
+                Some(solver) => maze.solve_with_rng(solver.solver(), Position::new(), options.size.get_max_pos(), &mut solve_rng(options.seed)).ok(),
+                None => solve_corners(&maze, &mut solve_rng(options.seed)).ok(),
+            };
+
+            (maze, path)
+        })
+        .collect();
+
+    let mut display = Display::grid_of_mazes(&mazes, kinds.len(), COMPARE_GAP)
+        .map_err(|error| failure!("Couldn't draw the mazes side by side: {}", io::Error::from(error)))?;
+    display.color = options.draw.color;
+    display.charset = options.draw.charset;
+    display.wide = options.draw.wide;
+
+    // Each name starts under the left wall of its maze
+    let slot = (options.size.0 * 2 + 1 + COMPARE_GAP) * (display.wide as usize + 1);
+    let names: String = kinds.iter()
+        .map(|kind| format!("{:<1$}", kind.to_string(), slot))
+        .collect();
+
+    let mut text = format!(
+        "{}{}{}\n",
+        display.render(),
+        " ".repeat(display.margins.left * (display.wide as usize + 1)),
+        names.trim_end()
+    );
+
+    if let Some(line) = seed_footer(options, options.seed) {
+        text += &format!("{}\n", line);
+    }
+
+    match &options.output.path {
+        Some(output) => check_written(Path::new(output), save::write_atomically(Path::new(output), |w| w.write_all(text.as_bytes()))),
+        None => check_stdout(io::stdout().lock().write_all(text.as_bytes())),
+    }
+}
+
+fn shapes_maze(options: &GenerateOptions) -> bool {
+    other_algorithm(options) || options.maze.endpoints.is_some() || options.maze.grow.is_some() || options.maze.braid.is_some() || options.maze.straightness.is_some() || options.maze.symmetry.is_some() || options.maze.sparse.is_some() || options.maze.unicursal
+}
+
+// Whether --algorithm picked something other than the backtracker every other option builds on
+pub(super) fn other_algorithm(options: &GenerateOptions) -> bool {
+    options.maze.algorithm.is_some_and(|kind| kind != GeneratorKind::Backtracker)
+}
+
+// The plain backtracker is animated as it carves. Any other maze is built first and then
+// uncovered passage by passage, see `Maze::to_event_log`.
+pub(super) fn generate_animated(options: &GenerateOptions) -> CliResult<(Maze, Endpoints)> {
+    let plain = options.maze.difficulty.is_none() && options.maze.route.is_none() && !shapes_maze(options);
+
+    let (maze, endpoints, log) = match plain {
+        true => {
+            let mut maze = Maze::new(options.size, true);
+
+            let log = match options.seed {
+                Some(seed) => maze.generate_maze_logged_with_rng(&mut Pcg32::new(seed)),
+                None => maze.generate_maze_logged(),
+            };
+
+            (maze, Endpoints::corners(options.size), log)
+        },
+        false => {
+            let (maze, endpoints) = build_maze(options, options.seed)?;
+            let log = maze.to_event_log();
+
+            (maze, endpoints, log)
+        },
+    };
+
+    animate_in_terminal(&animation::generation_frames(&log, None, options.animation.delay))?;
+
+    Ok((maze, endpoints))
+}
+
+// The same seed gives the depth first search the same choices as the printed solution, so
+// runs with different solvers on a seeded maze are comparable. It runs between the ends the
+// printed solution runs between, or from the entrance to the nearest exit with --exits.
+pub(super) fn animate_solve(options: &GenerateOptions, maze: &Maze, endpoints: &Endpoints) -> CliResult {
+    let solver = options.solve.solver.unwrap_or(SolverKind::Dfs);
+
+    let (start, goal) = match (options.maze.exits, maze.rank_exits(ENTRANCE.0).first()) {
+        (Some(_), Some((exit, _))) => (ENTRANCE.0, *exit),
+        _ => solve_ends(options, maze, endpoints),
+    };
+
+    let steps = maze.solve_steps_between(solver, start, goal, solve_rng(options.seed))
+        .map_err(|_| failure!("Can't solve from {},{} to {},{} outside the maze", start.0, start.1, goal.0, goal.1))?;
+
+    let frames = animation::solve_frames(maze, steps, options.animation.delay);
+
+    animate_in_terminal(&frames)
+}
+
+// Plays `frames` on the alternate screen with the cursor hidden. Any key skips to the end,
+// Ctrl-C quits once the terminal is back the way it was.
+fn animate_in_terminal(frames: &[animation::Frame]) -> CliResult {
+    let Ok(guard) = terminal::RawTerminal::enter() else {
+        return Err(failure!("Animations have to run in a terminal"));
+    };
+
+    let key = Arc::new(OnceLock::new());
+    let pressed = Arc::clone(&key);
+
+    thread::spawn(move || {
+        let mut byte = [0];
+
+        if io::stdin().read(&mut byte).is_ok() {
+            let _ = pressed.set(byte[0]);
+        }
+    });
+
+    let played = animation::play_until(&mut io::stdout().lock(), frames, || key.get().is_some());
+
+    drop(guard);
+
+    check_stdout(played.map(|_| ()))?;
+
+    // Raw mode turns Ctrl-C into a plain byte
+    match key.get() {
+        Some(3) => Err(CliError::Interrupted),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "gif")]
+pub(super) fn generate_gif(options: &GenerateOptions, path: &Path) -> CliResult<Maze> {
+    let defaults = animation::GifOptions::default();
+
+    let opts = animation::GifOptions {
+        steps_per_frame: options.animation.gif_steps.unwrap_or(defaults.steps_per_frame),
+        seed: options.seed,
+        ..defaults
+    };
+
+    check_written(path, animation::generation_gif(options.size, GeneratorKind::Backtracker, path, &opts))
+}
+
+// Never reached, --gif is turned down while parsing the arguments
+#[cfg(not(feature = "gif"))]
+pub(super) fn generate_gif(_options: &GenerateOptions, _path: &Path) -> CliResult<Maze> {
+    unreachable!()
+}
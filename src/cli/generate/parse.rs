@@ -0,0 +1,261 @@
+use crate::cli::args::{
+    algorithm_list, detect_format, number, parse_algorithm, parse_braid, parse_cell, parse_char,
+    parse_difficulty, parse_endpoints, parse_format, parse_generator, parse_grow_strategy,
+    parse_path_style, parse_scale, parse_size, parse_solver, parse_sparse, parse_straightness,
+    parse_symmetry, parse_view, value,
+};
+use crate::cli::error::{CliError, CliResult};
+use crate::cli::load::{read_binary_maze, try_load_maze};
+use crate::cli::{GENERATE_USAGE, HELP, VERSION};
+use my_project::*;
+use my_project::formats::Format;
+use my_project::solution;
+use my_project::solver::SolverKind;
+use my_project::terminal;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use super::validate::validate;
+use super::{
+    AnimationOptions, BenchOptions, DrawOptions, GenerateOptions, MazeOptions, OutputOptions,
+    SolveOptions,
+};
+
+// What --fit assumes when the output isn't a terminal, and the rows it keeps free under the
+// maze for the prompt and the lines of --stats-footer
+const FIT_FALLBACK: Size = Size(80, 24);
+const FIT_PROMPT_ROWS: usize = 1;
+const FIT_FOOTER_ROWS: usize = 5;
+
+// Stops with the usage on any option that's malformed or doesn't fit with the others. A
+// maze from --load or --load-bin comes back with the options, its size in `size`.
+pub(super) fn parse_generate_args(args: &[String]) -> CliResult<(GenerateOptions, Option<Maze>)> {
+    const INVALID_INPUT: &str = GENERATE_USAGE;
+
+    let mut size = None;
+    let mut fit = false;
+    let mut options = GenerateOptions {
+        size: Size(0, 0),
+        seed: None,
+        seed_chosen: false,
+        compare: None,
+        maze: MazeOptions::default(),
+        solve: SolveOptions::default(),
+        draw: DrawOptions::default(),
+        output: OutputOptions::default(),
+        animation: AnimationOptions::default(),
+        bench: BenchOptions::default(),
+    };
+
+    let mut route_file = None;
+    let mut ends = (None, None);
+    let mut format = None;
+    let mut no_color = false;
+    let mut wide = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(HELP.to_string())),
+            "-V" | "--version" => return Err(CliError::Help(VERSION.to_string())),
+            "--fit" => fit = true,
+            "--size" if size.is_none() => size = Some(value(&mut args, arg, INVALID_INPUT)?),
+            "--algorithm" => match value(&mut args, arg, INVALID_INPUT)?.as_str() {
+                "list" => return Err(algorithm_list()),
+                name => options.maze.algorithm = Some(parse_algorithm(name)?),
+            },
+            "--list-algorithms" => return Err(algorithm_list()),
+            "--difficulty" => options.maze.difficulty = Some(parse_difficulty(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--grow" => options.maze.grow = Some(parse_grow_strategy(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--braid" => options.maze.braid = Some(parse_braid(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--straightness" => options.maze.straightness = Some(parse_straightness(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--symmetry" => options.maze.symmetry = Some(parse_symmetry(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--sparse" => options.maze.sparse = Some(parse_sparse(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--seed" => options.seed = Some(number(&mut args, arg, INVALID_INPUT)?),
+            "--count" => options.output.count = Some(number(&mut args, arg, INVALID_INPUT)?),
+            "--threads" => options.output.threads = number(&mut args, arg, INVALID_INPUT)?,
+            "-o" | "--output" => options.output.path = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--record-cast" => options.animation.record_cast = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--gif" => options.animation.gif = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--gif-steps" => options.animation.gif_steps = Some(number(&mut args, arg, INVALID_INPUT)?),
+            "--animate" => options.animation.animate = true,
+            "--animate-solve" => options.animation.animate_solve = true,
+            "--delay" => options.animation.delay = Duration::from_millis(number(&mut args, arg, INVALID_INPUT)?),
+            "--exits" => options.maze.exits = Some(number(&mut args, arg, INVALID_INPUT)?),
+            "--endpoints" => options.maze.endpoints = Some(parse_endpoints(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--unicursal" => options.maze.unicursal = true,
+            "--stats" => options.draw.stats = true,
+            "--random-mouse" => options.solve.random_mouse = true,
+            "--start" => ends.0 = Some(value(&mut args, arg, INVALID_INPUT)?),
+            "--end" => ends.1 = Some(value(&mut args, arg, INVALID_INPUT)?),
+            "--solver" => {
+                let mut solvers = value(&mut args, arg, INVALID_INPUT)?.split(',').map(parse_solver).collect::<CliResult<Vec<SolverKind>>>()?;
+
+                options.solve.solver = Some(solvers.remove(0));
+                options.solve.overlay = solvers;
+            },
+            "--compare" => options.compare = Some(value(&mut args, arg, INVALID_INPUT)?.split(',').map(parse_generator).collect::<CliResult<_>>()?),
+            "--open" => options.draw.open = true,
+            "--open-bare" => (options.draw.open, options.draw.open_bare) = (true, true),
+            "--scale" => {
+                let text = value(&mut args, arg, INVALID_INPUT)?;
+
+                options.draw.scale = parse_scale(text).ok_or_else(|| usage_error!(INVALID_INPUT; "--scale takes N or WxH, none of them 0, got '{}'", text))?;
+            },
+            "--stats-footer" => options.draw.stats_footer = true,
+            "--box" => options.draw.box_drawing = true,
+            "--halfblock" => options.draw.halfblock = true,
+            "--braille" => options.draw.braille = true,
+            "--path-style" => options.draw.path_style = parse_path_style(value(&mut args, arg, INVALID_INPUT)?)?,
+            "--heatmap" => options.draw.heatmap = true,
+            "--wall-char" => options.draw.charset.wall = parse_char(arg, value(&mut args, arg, INVALID_INPUT)?)?,
+            "--path-char" => options.draw.charset.path = parse_char(arg, value(&mut args, arg, INVALID_INPUT)?)?,
+            "--empty-char" => options.draw.charset.empty = parse_char(arg, value(&mut args, arg, INVALID_INPUT)?)?,
+            "--start-char" => options.draw.charset.start = parse_char(arg, value(&mut args, arg, INVALID_INPUT)?)?,
+            "--goal-char" => options.draw.charset.goal = parse_char(arg, value(&mut args, arg, INVALID_INPUT)?)?,
+            "--solve" => options.draw.heatmap_path = true,
+            "--no-solve" => options.solve.no_solve = true,
+            "--answers" => options.output.answers = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--solution-only" => options.solve.solution_only = true,
+            "--bench" => options.bench.run = true,
+            "--bench-format" => options.bench.csv = match value(&mut args, arg, INVALID_INPUT)?.as_str() {
+                "text" => false,
+                "csv" => true,
+                other => return Err(usage_error!("Unknown bench format '{}', expected text or csv", other)),
+            },
+            "--bench-iterations" => options.bench.iterations = Some(number(&mut args, arg, INVALID_INPUT)?)
+                .filter(|x| *x > 0)
+                .ok_or_else(|| usage_error!(INVALID_INPUT; "--bench-iterations takes a number of runs above 0"))?,
+            "--no-color" => no_color = true,
+            "--wide" => wide = Some(true),
+            "--no-wide" => wide = Some(false),
+            "--axes" => options.draw.axes = true,
+            "--view" => {
+                let text = value(&mut args, arg, INVALID_INPUT)?;
+
+                options.draw.view = Some(parse_view(text).ok_or_else(|| usage_error!(INVALID_INPUT; "--view takes X,Y,WxH, got '{}'", text))?);
+            },
+            "--png" => options.output.png = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--html" => options.output.html = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--json" => options.output.json = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--save-bin" => options.output.save_bin = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--format" => format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--expect-fingerprint" => options.output.expect_fingerprint = Some(value(&mut args, arg, INVALID_INPUT)?.to_lowercase()),
+            "--solution-path" => route_file = Some(value(&mut args, arg, INVALID_INPUT)?.clone()),
+            "--load" => options.maze.load = Some(PathBuf::from(value(&mut args, arg, INVALID_INPUT)?)),
+            "--load-bin" => options.maze.load_bin = Some(PathBuf::from(value(&mut args, arg, INVALID_INPUT)?)),
+            _ if arg.starts_with('-') => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+            _ if size.is_none() => size = Some(arg),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    // -o picks the format from its extension unless --format says otherwise
+    options.draw.format = match (format, &options.output.path) {
+        (Some(format), _) => format,
+        (None, Some(output)) => detect_format(Path::new(output))?,
+        (None, None) => Format::Txt,
+    };
+
+    if options.draw.format.is_binary() && options.output.path.is_none() && io::stdout().is_terminal() {
+        return Err(usage_error!("The {} format isn't for the terminal, write it to a file with -o or pipe it", options.draw.format));
+    }
+
+    if options.maze.load.is_some() && options.maze.load_bin.is_some() {
+        return Err(usage_error!("--load and --load-bin can't be combined"));
+    }
+
+    // A loaded maze brings its own size
+    let loaded = options.maze.load.as_ref().map(|path| try_load_maze(path, None))
+        .or_else(|| options.maze.load_bin.as_ref().map(|path| read_binary_maze(path)))
+        .transpose()
+        .map_err(CliError::Failed)?;
+
+    // Only a terminal gets escape sequences, files and pipes get the plain characters
+    options.draw.color = !no_color && options.output.path.is_none() && io::stdout().is_terminal();
+
+    // Wide output doesn't read back in, so files and pipes only get it when asked
+    options.draw.wide = wide.unwrap_or(options.output.path.is_none() && io::stdout().is_terminal());
+
+    // Without a size the maze fills the terminal, which depends on --wide
+    options.size = match (size, &loaded) {
+        (_, Some(_)) if fit => return Err(usage_error!("--fit can't be combined with --load or --load-bin")),
+        (Some(_), _) if fit => return Err(usage_error!("--fit picks the size, leave out the size")),
+        (None, Some(maze)) => maze.size,
+        (Some(_), Some(_)) => return Err(usage_error!("--load takes the size from the file, leave out the size")),
+        (None, None) => fit_terminal(&options),
+        (Some(size), None) => parse_size(size)?,
+    };
+
+    // The keywords need the size
+    options.solve.start = ends.0.map(|text| parse_cell(text, options.size).ok_or_else(|| usage_error!(
+        "--start takes X,Y, corner:nw, corner:ne, corner:sw, corner:se or center, got '{}'", text
+    ))).transpose()?;
+    options.solve.goal = ends.1.map(|text| parse_cell(text, options.size).ok_or_else(|| usage_error!(
+        "--end takes X,Y, corner:nw, corner:ne, corner:sw, corner:se or center, got '{}'", text
+    ))).transpose()?;
+
+    // Mazes bigger than the terminal are cropped to fit it unless a view is picked
+    options.draw.terminal = match options.output.path.is_none() && io::stdout().is_terminal() {
+        true => terminal::terminal_size().filter(|x| x.0 > 0 && x.1 > 0),
+        false => None,
+    };
+
+    // Only --wide itself is checked, files and pipes get narrow output anyway
+    if wide == Some(true) && (options.draw.format != Format::Txt || options.draw.halfblock || options.draw.braille) {
+        return Err(usage_error!("--wide can only be combined with the txt format, and not with --halfblock or --braille"));
+    }
+
+    options.maze.route = route_file.map(|path| read_route(Path::new(&path), options.size)).transpose()?;
+
+    validate(&options)?;
+
+    Ok((options, loaded))
+}
+
+// A route is either JSON cells as read by parse_coords or a move string starting in the top
+// left corner, and has to end in the bottom right one to be the maze's solution
+fn read_route(path: &Path, size: Size) -> CliResult<Vec<Position>> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| failure!("Couldn't read '{}': {}", path.display(), error))?;
+
+    let route = match text.trim_start().starts_with(['[', '{']) {
+        true => solution::parse_coords(&text)
+            .map_err(|_| failure!("The solution path file must be a path-json file or a JSON array of [x, y] pairs"))?,
+        false => {
+            let moves = solution::parse_moves(&text)
+                .map_err(|_| failure!("Moves must only contain the letters N, E, S and W"))?;
+
+            Maze::new(size, false)
+                .walk_moves(Position::new(), &moves)
+                .map_err(|error| failure!("The solution path doesn't fit the maze: {}", error))?
+        },
+    };
+
+    if route.first() != Some(&Position::new()) || route.last() != Some(&size.get_max_pos()) {
+        return Err(failure!("The solution path must run from the top left to the bottom right cell"));
+    }
+
+    Ok(route)
+}
+
+// The biggest maze that shows whole on the terminal with the options picked so far, never
+// smaller than one cell. Files and pipes get one for an 80x24 terminal.
+fn fit_terminal(options: &GenerateOptions) -> Size {
+    let area = match options.output.path.is_none() && io::stdout().is_terminal() {
+        true => terminal::terminal_size().filter(|x| x.0 > 0 && x.1 > 0),
+        false => None,
+    };
+
+    let reserved = FIT_PROMPT_ROWS + if options.draw.stats_footer { FIT_FOOTER_ROWS } else { 0 };
+    let size = Display::fit_maze(area.unwrap_or(FIT_FALLBACK), Margins::default(), options.draw.wide, reserved)
+        .unwrap_or(Size(1, 1));
+
+    // A unicursal maze is built at half the size and needs even sides
+    match options.maze.unicursal {
+        true => Size((size.0 & !1).max(2), (size.1 & !1).max(2)),
+        false => size,
+    }
+}
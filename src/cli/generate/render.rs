@@ -0,0 +1,334 @@
+use my_project::*;
+use my_project::arrows::PathStyle;
+use my_project::endpoints::Endpoints;
+use my_project::footer;
+use my_project::formats::Format;
+use my_project::generator::GeneratorKind;
+use my_project::html::HtmlOptions;
+use my_project::json::GenerationInfo;
+use my_project::metrics::MazeStats;
+use my_project::mouse::MOUSE_STEPS_PER_CELL;
+use my_project::overlay::OVERLAY_SYMBOLS;
+use my_project::rng::{mix_seed, Pcg32};
+use my_project::save;
+use my_project::solution;
+use my_project::solver::SolverKind;
+use my_project::viewport::Viewport;
+use std::io::{self, Write};
+use std::path::Path;
+use super::solve::{Solved, chosen_ends, describe_solved, endpoint_policy, overlay_paths, solve_ends};
+use super::{GenerateOptions, MOUSE_SEED_SALT, MazeOptions};
+
+// Writes the extra files like --png and renders the maze solved by `solve_generated`, or only
+// its solution with --solution-only
+pub(super) fn write_maze<W: Write>(
+    options: &GenerateOptions,
+    maze: &Maze,
+    endpoints: &Endpoints,
+    solved: &Solved,
+    seed: Option<u64>,
+    w: &mut W
+) -> io::Result<()> {
+    write_side_files(options, maze, solved.path.as_deref(), seed)?;
+
+    match (options.solve.solution_only, &solved.path) {
+        (true, Some(path)) => write_solution_only(options.draw.format, path, seed, w)?,
+        _ => render_maze(options, maze, solved, seed, w)?,
+    }
+
+    if options.draw.stats {
+        write_stats(options, maze, endpoints, solved, seed, w)?;
+    }
+
+    if let Some(line) = seed_footer(options, seed) {
+        writeln!(w, "{}", line)?;
+    }
+
+    Ok(())
+}
+
+// The last line of the text when `generate` picked the seed, so the maze can be made again.
+// --stats-footer shows the seed already, and the other formats and --solution-only have no
+// room for a line of text.
+pub(super) fn seed_footer(options: &GenerateOptions, seed: Option<u64>) -> Option<String> {
+    let text = options.draw.format == Format::Txt && !options.solve.solution_only && !options.draw.stats_footer;
+
+    seed.filter(|_| options.seed_chosen && text)
+        .map(|seed| format!("Seed: {} (pass --seed {} to make this maze again)", seed, seed))
+}
+
+// The files of --png, --html, --save-bin and --json, next to what's printed
+fn write_side_files(options: &GenerateOptions, maze: &Maze, path: Option<&[Position]>, seed: Option<u64>) -> io::Result<()> {
+    if let Some(file) = &options.output.png {
+        write_png(maze, path, Path::new(file))?;
+    }
+
+    if let Some(file) = &options.output.html {
+        save::write_atomically(Path::new(file), |w| w.write_all(maze.to_html(path, &HtmlOptions::default()).as_bytes()))?;
+    }
+
+    if let Some(file) = &options.output.save_bin {
+        save::write_atomically(Path::new(file), |w| maze.write_binary(w))?;
+    }
+
+    if let Some(file) = &options.output.json {
+        let info = GenerationInfo {
+            algorithm: Some(algorithm_name(options)),
+            seed,
+        };
+
+        save::write_atomically(Path::new(file), |w| writeln!(w, "{}", maze.to_json_with(path, Some(&info))))?;
+    }
+
+    Ok(())
+}
+
+// The path without the maze, a cell per line or the path-json document
+fn write_solution_only<W: Write>(format: Format, path: &[Position], seed: Option<u64>, w: &mut W) -> io::Result<()> {
+    match format {
+        Format::PathJson => writeln!(w, "{}", solution::Solution::new(path.to_vec()).to_json(seed)?),
+        _ => path.iter().try_for_each(|pos| writeln!(w, "{},{}", pos.0, pos.1)),
+    }
+}
+
+// Mazes with exits show each exit's rank instead of the corner to corner solution
+fn render_maze<W: Write>(options: &GenerateOptions, maze: &Maze, solved: &Solved, seed: Option<u64>, w: &mut W) -> io::Result<()> {
+    let (start, goal) = (solved.start, solved.goal);
+
+    match options.draw.format {
+        Format::PathJson => {
+            // --no-solve is turned down for path-json while parsing
+            let path = solved.path.as_deref().ok_or(io::ErrorKind::InvalidInput)?;
+
+            write_solution_only(options.draw.format, path, seed, w)?;
+        },
+        Format::Txt if options.maze.exits.is_some()
+            || options.draw.box_drawing
+            || options.draw.halfblock
+            || options.draw.braille
+            || options.draw.path_style != PathStyle::Dots
+            || options.draw.heatmap
+            || options.draw.charset != Charset::default()
+            || options.draw.wide
+            || options.draw.view.is_some()
+            || options.draw.axes
+            || !options.solve.overlay.is_empty()
+            || options.draw.open
+            || options.draw.scale != Size(1, 1)
+            || options.draw.stats_footer
+            || options.draw.terminal.is_some()
+            || options.draw.color
+            || options.solve.no_solve => {
+            let mut display = Display::new_from_maze(maze);
+            display.color = options.draw.color;
+            display.charset = options.draw.charset;
+            display.wide = options.draw.wide;
+
+            match options.draw.box_drawing {
+                true => display.draw_maze_box(maze)?,
+                false => display.draw_maze(maze)?,
+            }
+
+            if options.draw.heatmap {
+                display.draw_heatmap(maze, &maze.distances_from(start));
+            }
+
+            if options.draw.open {
+                display.open_ends(start, goal)?;
+            }
+
+            match (options.maze.exits, &solved.path) {
+                (Some(_), _) => display.draw_exit_ranks(maze, &solved.ranking),
+                _ if options.draw.heatmap && !options.draw.heatmap_path => {},
+                (None, Some(path)) if !options.solve.overlay.is_empty() => {
+                    display.draw_paths(&overlay_paths(&solved.each))?;
+                    display.draw_end_markers(path, options.draw.charset.start, options.draw.charset.goal);
+                },
+                (None, Some(path)) => display.draw_solution_styled(path, options.draw.path_style)?,
+                (None, None) => display.draw_end_markers(&[start, goal], options.draw.charset.start, options.draw.charset.goal),
+            }
+
+            if options.draw.open_bare {
+                display.open_ends(start, goal)?;
+            }
+
+            if options.draw.scale != Size(1, 1) {
+                display = display.scaled(options.draw.scale);
+            }
+
+            if let (true, Some(path)) = (options.draw.stats_footer, &solved.path) {
+                display.draw_footer(&footer::MazeStats::new(maze, &algorithm_name(options), seed, path).footer());
+            }
+
+            match (options.draw.halfblock, options.draw.braille, options.draw.axes) {
+                (true, _, _) => display.write_halfblock_to(w)?,
+                (_, true, _) => display.write_braille_to(w)?,
+                (_, _, true) => display.write_with_axes_to(w, maze.size)?,
+                _ => match choose_viewport(options, &display, start) {
+                    Some(viewport) => display.write_viewport_to(w, viewport)?,
+                    None => display.write_to(w)?,
+                },
+            }
+
+            if !options.solve.overlay.is_empty() {
+                let names: Vec<String> = solved.each.iter().map(describe_solved).collect();
+
+                let legend: Vec<(char, &str)> = OVERLAY_SYMBOLS.into_iter()
+                    .zip(names.iter().map(String::as_str))
+                    .collect();
+
+                display.write_legend_to(w, &legend)?;
+            }
+        },
+        format => format.export(maze, solved.path.as_deref(), w)?,
+    }
+
+    Ok(())
+}
+
+// What built the maze, as named in the stats footer
+fn algorithm_name(options: &GenerateOptions) -> String {
+    match &options.maze {
+        _ if options.maze.load.is_some() || options.maze.load_bin.is_some() => "unknown".to_string(),
+        _ if options.maze.route.is_some() => "solution path".to_string(),
+        _ if options.maze.unicursal => "unicursal".to_string(),
+        MazeOptions { difficulty: Some(difficulty), .. } => format!("{} preset", difficulty),
+        MazeOptions { symmetry: Some(symmetry), .. } => format!("backtracker, {} symmetry", symmetry),
+        MazeOptions { sparse: Some(_), .. } => "sparse backtracker".to_string(),
+        MazeOptions { grow: Some(_), .. } => GeneratorKind::GrowingTree.to_string(),
+        MazeOptions { algorithm: Some(kind), .. } => kind.to_string(),
+        _ => GeneratorKind::Backtracker.to_string(),
+    }
+}
+
+// The window to print of `display`, if it isn't the whole of it. `--view` is in cells, a
+// maze too big for the terminal is centered on `start` and says it was cropped.
+fn choose_viewport(options: &GenerateOptions, display: &Display, start: Position) -> Option<Viewport> {
+    let viewport = match (options.draw.view, options.draw.terminal) {
+        // The cells in the note below wouldn't line up with the stretched pixels
+        _ if options.draw.scale != Size(1, 1) => return None,
+        (Some((pos, size)), _) => display.viewport(Position(pos.0 * 2, pos.1 * 2), Size(size.0 * 2 + 1, size.1 * 2 + 1)),
+        (None, Some(terminal)) => {
+            let margins = display.margins;
+            let columns = terminal.0.saturating_sub(margins.left + margins.right) / (display.wide as usize + 1);
+
+            // A line each for the note below and the prompt after it
+            let rows = terminal.1.saturating_sub(margins.top + margins.bottom + 2);
+
+            display.viewport_around(Maze::to_display_pos(start), Size(columns.max(1), rows.max(1)))
+        },
+        (None, None) => return None,
+    };
+
+    if !viewport.is_clipped(display.size) {
+        return None;
+    }
+
+    if options.draw.view.is_none() {
+        let (from, size) = (viewport.top_left, viewport.size);
+
+        eprintln!(
+            "Cropped to fit the terminal, showing cells {},{} to {},{} of {}x{} (pick another part with --view X,Y,WxH)",
+            from.0 / 2,
+            from.1 / 2,
+            (from.0 + size.0).saturating_sub(2) / 2,
+            (from.1 + size.1).saturating_sub(2) / 2,
+            display.size.0 / 2,
+            display.size.1 / 2
+        );
+    }
+
+    Some(viewport)
+}
+
+#[cfg(feature = "png")]
+fn write_png(maze: &Maze, path: Option<&[Position]>, file: &Path) -> io::Result<()> {
+    save::write_atomically(file, |w| maze.write_png_to(path, &my_project::png::ImageOptions::default(), w))
+}
+
+// Never reached, --png is turned down while parsing the arguments
+#[cfg(not(feature = "png"))]
+fn write_png(_maze: &Maze, _path: Option<&[Position]>, _file: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+fn write_stats<W: Write>(
+    options: &GenerateOptions,
+    maze: &Maze,
+    endpoints: &Endpoints,
+    solved: &Solved,
+    seed: Option<u64>,
+    w: &mut W
+) -> io::Result<()> {
+    writeln!(w)?;
+
+    if let Some(difficulty) = options.maze.difficulty {
+        writeln!(w, "Preset: {}", difficulty)?;
+    }
+
+    if let Some(policy) = endpoint_policy(options) {
+        writeln!(w, "Endpoints: {}", policy)?;
+    }
+
+    match chosen_ends(options) {
+        true => {
+            let (entrance, exit) = (endpoints.entrance, endpoints.exit);
+
+            writeln!(w, "Entrance: {},{} {}", entrance.0.0, entrance.0.1, entrance.1)?;
+            writeln!(w, "Exit: {},{} {}", exit.0.0, exit.0.1, exit.1)?;
+            writeln!(w, "Difficulty score: {:.3}", maze.endpoint_difficulty_score(endpoints))?;
+        },
+        false => writeln!(w, "Difficulty score: {:.3}", maze.difficulty_score())?,
+    }
+
+    writeln!(w, "Fingerprint: {}", maze.fingerprint())?;
+
+    let (start, goal) = solve_ends(options, maze, endpoints);
+
+    // --no-solve leaves the path out of the drawing, not out of the numbers
+    let shortest = solved.path.is_none().then(|| maze.shortest_path(start, goal).unwrap_or_default());
+    let stats = MazeStats::compute(maze, solved.path.as_deref().or(shortest.as_deref()).unwrap_or_default());
+
+    writeln!(w, "Dead ends: {}", stats.dead_ends)?;
+    writeln!(w, "Junctions: {} three-way, {} four-way", stats.three_way, stats.four_way)?;
+    writeln!(w, "Solution: {} cells, {:.1}% of the maze", stats.solution_length, stats.solution_share)?;
+    writeln!(w, "Longest path: {} cells", stats.longest_path)?;
+    writeln!(w, "Average corridor: {:.2} cells", stats.average_corridor)?;
+
+    // The greedy solver is only there to show how far off a quick guess can be
+    if let (Some(greedy), Ok(shortest)) = (maze.solve_greedy(start, goal), maze.solve_with(SolverKind::Bfs.solver(), start, goal)) {
+        writeln!(w, "Greedy: {} steps, shortest {}", greedy.len() - 1, shortest.len() - 1)?;
+    }
+
+    if solved.each.len() > 1 {
+        for solver in &solved.each {
+            writeln!(w, "Solver {}", describe_solved(solver))?;
+        }
+    }
+
+    if options.solve.random_mouse {
+        let max_steps = maze.tiles.len() * MOUSE_STEPS_PER_CELL;
+
+        let trace = match seed {
+            Some(seed) => maze.solve_random_mouse(&mut Pcg32::new(mix_seed(seed, &[MOUSE_SEED_SALT])), max_steps),
+            None => maze.solve_random_mouse(&mut rand::rng(), max_steps),
+        };
+
+        let shortest = maze.solve_bfs().len().saturating_sub(1);
+
+        match trace {
+            Some(trace) => writeln!(w, "Random mouse: {} steps, shortest {}", trace.len() - 1, shortest)?,
+            None => writeln!(w, "Random mouse: lost after {} moves, shortest {}", max_steps, shortest)?,
+        }
+    }
+
+    if options.maze.exits.is_some() {
+        writeln!(w, "Rank  Exit      Distance")?;
+
+        for (rank, (pos, distance)) in solved.ranking.iter().enumerate() {
+            writeln!(w, "{:<5} {:<9} {}", rank + 1, format!("{},{}", pos.0, pos.1), distance)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::cli::error::CliResult;
+use my_project::*;
+use my_project::endpoints::{EndpointPolicy, Endpoints};
+use my_project::exits::ENTRANCE;
+use my_project::overlay::OVERLAY_SYMBOLS;
+use my_project::rng::{mix_seed, Pcg32};
+use my_project::solver::SolverKind;
+use std::io;
+use super::{GenerateOptions, SOLVE_SEED_SALT};
+
+// What `write_maze` knows about the maze before drawing it
+pub(super) struct Solved {
+    pub(super) start: Position,
+    pub(super) goal: Position,
+    // None with --no-solve
+    pub(super) path: Option<Vec<Position>>,
+    // Every --solver name with what it found, see `solve_each`
+    pub(super) each: Vec<(SolverKind, Result<Vec<Position>, io::ErrorKind>)>,
+    pub(super) ranking: Vec<(Position, u32)>,
+}
+
+// Fails when there's no way between the ends, like in a cave whose corners aren't connected
+pub(super) fn solve_generated(options: &GenerateOptions, maze: &Maze, endpoints: &Endpoints) -> CliResult<Solved> {
+    let (start, goal) = solve_ends(options, maze, endpoints);
+
+    let each = solve_each(options, maze, start, goal);
+    let no_way = || failure!("There is no way from {},{} to {},{} in this maze", start.0, start.1, goal.0, goal.1);
+
+    // Chosen endpoints get the shortest path, which is the only one unless the maze is braided
+    let path = match (options.solve.solver, chosen_ends(options)) {
+        _ if options.solve.no_solve => None,
+        (Some(_), _) => Some(each.iter()
+            .find_map(|(_, path)| path.as_ref().ok())
+            .cloned()
+            .ok_or_else(|| failure!("No solver found a way from {},{} to {},{} in this maze", start.0, start.1, goal.0, goal.1))?),
+        (None, true) => Some(maze.solve_endpoints(endpoints).ok_or_else(no_way)?),
+        (None, false) => Some(maze.solve_between_with_rng(start, goal, &mut solve_rng(options.seed)).map_err(|_| no_way())?),
+    };
+
+    Ok(Solved {
+        start,
+        goal,
+        path,
+        each,
+        ranking: maze.rank_exits(ENTRANCE.0),
+    })
+}
+
+// The corner to corner solution `Maze::solve_maze_with_rng` finds, without its panic when the
+// corners aren't connected
+pub(crate) fn solve_corners<R: rand::RngCore>(maze: &Maze, rng: &mut R) -> CliResult<Vec<Position>> {
+    let (start, goal) = (Position::new(), maze.size.get_max_pos());
+
+    maze.solve_between_with_rng(start, goal, rng)
+        .map_err(|_| failure!("There is no way from {},{} to {},{} in this maze", start.0, start.1, goal.0, goal.1))
+}
+
+// Runs every --solver name between the ends, empty without one
+fn solve_each(options: &GenerateOptions, maze: &Maze, start: Position, goal: Position) -> Vec<(SolverKind, Result<Vec<Position>, io::ErrorKind>)> {
+    options.solve.solver.iter()
+        .chain(&options.solve.overlay)
+        .map(|solver| (*solver, maze.solve_with_rng(solver.solver(), start, goal, &mut solve_rng(options.seed))))
+        .collect()
+}
+
+// What the solvers draw from. A seeded maze is solved the same way every time, from a stream
+// of its own so the path doesn't depend on how many numbers generation used up.
+pub(crate) fn solve_rng(seed: Option<u64>) -> Pcg32 {
+    match seed {
+        Some(seed) => Pcg32::new(mix_seed(seed, &[SOLVE_SEED_SALT])),
+        None => Pcg32::new(rand::random()),
+    }
+}
+
+// How long a solver's path is, for the legend and --stats
+pub(super) fn describe_solved((solver, path): &(SolverKind, Result<Vec<Position>, io::ErrorKind>)) -> String {
+    match path {
+        Ok(path) => format!("{}: {} steps", solver, path.len() - 1),
+        Err(_) => format!("{}: no path", solver),
+    }
+}
+
+// The solvers' paths in display pixels, each with its own symbol for `Display::draw_paths`.
+// A solver that found no path keeps its symbol in the legend but draws nothing.
+pub(super) fn overlay_paths(solved: &[(SolverKind, Result<Vec<Position>, io::ErrorKind>)]) -> Vec<(Vec<Position>, char)> {
+    solved.iter()
+        .zip(OVERLAY_SYMBOLS)
+        .filter_map(|((_, path), symbol)| Some((path.as_ref().ok()?.iter().copied().map(Maze::to_display_pos).collect(), symbol)))
+        .collect()
+}
+
+// The cells the solution runs between
+// How the ends were picked when it wasn't the corners with a closed border, by --endpoints
+// or by the --difficulty preset
+pub(super) fn endpoint_policy(options: &GenerateOptions) -> Option<EndpointPolicy> {
+    let preset = options.maze.difficulty
+        .map(|difficulty| difficulty.get_preset().endpoints)
+        .filter(|policy| *policy != EndpointPolicy::Corners);
+
+    options.maze.endpoints.or(preset)
+}
+
+// Whether the solution runs between `build_maze`'s endpoints rather than --start and --end
+pub(super) fn chosen_ends(options: &GenerateOptions) -> bool {
+    endpoint_policy(options).is_some() || options.maze.unicursal
+}
+
+pub(super) fn solve_ends(options: &GenerateOptions, maze: &Maze, endpoints: &Endpoints) -> (Position, Position) {
+    match chosen_ends(options) {
+        true => (endpoints.entrance.0, endpoints.exit.0),
+        false => (options.solve.start.unwrap_or_default(), options.solve.goal.unwrap_or(maze.size.get_max_pos())),
+    }
+}
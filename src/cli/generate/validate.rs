@@ -0,0 +1,317 @@
+use crate::cli::error::CliResult;
+use my_project::*;
+use my_project::arrows::PathStyle;
+use my_project::endpoints::EndpointPolicy;
+use my_project::formats::Format;
+use my_project::overlay::OVERLAY_SYMBOLS;
+use std::io::{self, IsTerminal};
+use super::GenerateOptions;
+use super::build::other_algorithm;
+
+// An option as it's named in the messages, and whether it was given
+struct Flag {
+    name: &'static str,
+    given: fn(&GenerateOptions) -> bool,
+}
+
+// `flag` can't be given together with any of `with`, for the reason in `why` if there's more
+// to say than the names
+struct Conflict {
+    flag: Flag,
+    with: &'static [Flag],
+    why: Option<&'static str>,
+}
+
+const DIFFICULTY: Flag = Flag { name: "--difficulty", given: |x| x.maze.difficulty.is_some() };
+const PRESET_ENDS: Flag = Flag {
+    name: "a --difficulty preset that picks its own endpoints",
+    given: |x| x.maze.difficulty.is_some_and(|difficulty| difficulty.get_preset().endpoints != EndpointPolicy::Corners),
+};
+const ROUTE: Flag = Flag { name: "--solution-path", given: |x| x.maze.route.is_some() };
+const ALGORITHM: Flag = Flag { name: "--algorithm", given: other_algorithm };
+const GROW: Flag = Flag { name: "--grow", given: |x| x.maze.grow.is_some() };
+const BRAID: Flag = Flag { name: "--braid", given: |x| x.maze.braid.is_some() };
+const STRAIGHTNESS: Flag = Flag { name: "--straightness", given: |x| x.maze.straightness.is_some() };
+const SYMMETRY: Flag = Flag { name: "--symmetry", given: |x| x.maze.symmetry.is_some() };
+const SPARSE: Flag = Flag { name: "--sparse", given: |x| x.maze.sparse.is_some() };
+const UNICURSAL: Flag = Flag { name: "--unicursal", given: |x| x.maze.unicursal };
+const EXITS: Flag = Flag { name: "--exits", given: |x| x.maze.exits.is_some() };
+const ENDPOINTS: Flag = Flag { name: "--endpoints", given: |x| x.maze.endpoints.is_some() };
+const LOAD: Flag = Flag { name: "--load", given: |x| x.maze.load.is_some() };
+const LOAD_BIN: Flag = Flag { name: "--load-bin", given: |x| x.maze.load_bin.is_some() };
+const SEED: Flag = Flag { name: "--seed", given: |x| x.seed.is_some() };
+const COMPARE: Flag = Flag { name: "--compare", given: |x| x.compare.is_some() };
+
+const START: Flag = Flag { name: "--start", given: |x| x.solve.start.is_some() };
+const END: Flag = Flag { name: "--end", given: |x| x.solve.goal.is_some() };
+const SOLVER: Flag = Flag { name: "--solver", given: |x| x.solve.solver.is_some() };
+const OVERLAY: Flag = Flag { name: "several --solver names", given: |x| !x.solve.overlay.is_empty() };
+const NO_SOLVE: Flag = Flag { name: "--no-solve", given: |x| x.solve.no_solve };
+const SOLUTION_ONLY: Flag = Flag { name: "--solution-only", given: |x| x.solve.solution_only };
+const RANDOM_MOUSE: Flag = Flag { name: "--random-mouse", given: |x| x.solve.random_mouse };
+
+const PATH_JSON: Flag = Flag { name: "the path-json format", given: |x| x.draw.format == Format::PathJson };
+const BOX: Flag = Flag { name: "--box", given: |x| x.draw.box_drawing };
+const HALFBLOCK: Flag = Flag { name: "--halfblock", given: |x| x.draw.halfblock };
+const BRAILLE: Flag = Flag { name: "--braille", given: |x| x.draw.braille };
+const PATH_STYLE: Flag = Flag { name: "--path-style", given: |x| x.draw.path_style != PathStyle::Dots };
+const HEATMAP: Flag = Flag { name: "--heatmap", given: |x| x.draw.heatmap };
+const HEATMAP_PATH: Flag = Flag { name: "--solve", given: |x| x.draw.heatmap_path };
+const CHARS: Flag = Flag {
+    name: "--wall-char, --path-char, --empty-char, --start-char and --goal-char",
+    given: |x| x.draw.charset != Charset::default(),
+};
+const VIEW: Flag = Flag { name: "--view", given: |x| x.draw.view.is_some() };
+const AXES: Flag = Flag { name: "--axes", given: |x| x.draw.axes };
+const OPEN: Flag = Flag { name: "--open", given: |x| x.draw.open };
+const SCALE: Flag = Flag { name: "--scale", given: |x| x.draw.scale != Size(1, 1) };
+const STATS: Flag = Flag { name: "--stats", given: |x| x.draw.stats };
+const STATS_FOOTER: Flag = Flag { name: "--stats-footer", given: |x| x.draw.stats_footer };
+
+const OUTPUT: Flag = Flag { name: "--output", given: |x| x.output.path.is_some() };
+const PNG: Flag = Flag { name: "--png", given: |x| x.output.png.is_some() };
+const HTML: Flag = Flag { name: "--html", given: |x| x.output.html.is_some() };
+const JSON: Flag = Flag { name: "--json", given: |x| x.output.json.is_some() };
+const SAVE_BIN: Flag = Flag { name: "--save-bin", given: |x| x.output.save_bin.is_some() };
+const ANSWERS: Flag = Flag { name: "--answers", given: |x| x.output.answers.is_some() };
+const COUNT: Flag = Flag { name: "--count", given: |x| x.output.count.is_some() };
+const EXPECT_FINGERPRINT: Flag = Flag { name: "--expect-fingerprint", given: |x| x.output.expect_fingerprint.is_some() };
+
+const ANIMATE: Flag = Flag { name: "--animate", given: |x| x.animation.animate };
+const ANIMATE_SOLVE: Flag = Flag { name: "--animate-solve", given: |x| x.animation.animate_solve };
+const RECORD_CAST: Flag = Flag { name: "--record-cast", given: |x| x.animation.record_cast.is_some() };
+const GIF: Flag = Flag { name: "--gif", given: |x| x.animation.gif.is_some() };
+const GIF_STEPS: Flag = Flag { name: "--gif-steps", given: |x| x.animation.gif_steps.is_some() };
+
+const BENCH: Flag = Flag { name: "--bench", given: |x| x.bench.run };
+
+// Everything that makes the maze something other than the plain backtracker's, which is all
+// a recording, a comparison or a loaded maze can be
+const SHAPES: [Flag; 10] = [DIFFICULTY, ROUTE, ALGORITHM, ENDPOINTS, GROW, BRAID, STRAIGHTNESS, SYMMETRY, SPARSE, UNICURSAL];
+
+// Options that only the txt format draws
+const TXT_ONLY: &[Flag] = &[
+    VIEW, AXES, STATS, BOX, HALFBLOCK, BRAILLE, PATH_STYLE, CHARS, HEATMAP, OVERLAY, COMPARE, SCALE, STATS_FOOTER, OPEN,
+];
+
+// Options that do nothing without the second one
+const NEEDS: &[(Flag, Flag)] = &[
+    (HEATMAP_PATH, HEATMAP),
+    (GIF_STEPS, GIF),
+    (RANDOM_MOUSE, STATS),
+    (ANSWERS, NO_SOLVE),
+];
+
+const CONFLICTS: &[Conflict] = &[
+    Conflict { flag: VIEW, with: &[HALFBLOCK, BRAILLE], why: None },
+    Conflict { flag: AXES, with: &[HALFBLOCK, BRAILLE, VIEW], why: None },
+    Conflict { flag: HALFBLOCK, with: &[BOX], why: Some("it draws whole blocks") },
+    Conflict { flag: BRAILLE, with: &[HALFBLOCK, BOX], why: Some("it draws dots") },
+    Conflict { flag: HEATMAP, with: &[HALFBLOCK, BRAILLE, EXITS], why: None },
+    // The other solvers' paths are drawn over the first one's on the printed maze only
+    Conflict { flag: OVERLAY, with: &[EXITS, HEATMAP, PATH_STYLE, ANIMATE_SOLVE], why: None },
+    // Box drawing characters would have to be joined up again once they're stretched
+    Conflict { flag: SCALE, with: &[BOX, AXES, VIEW], why: None },
+    Conflict { flag: STATS_FOOTER, with: &[HALFBLOCK, BRAILLE], why: None },
+    Conflict { flag: OPEN, with: &[EXITS, ENDPOINTS, PRESET_ENDS, UNICURSAL], why: Some("they open the border themselves") },
+    Conflict { flag: ANIMATE, with: &[RECORD_CAST, GIF, COUNT], why: None },
+    Conflict { flag: ANIMATE_SOLVE, with: &[RECORD_CAST, GIF, COUNT], why: None },
+    Conflict { flag: RECORD_CAST, with: &[GIF], why: None },
+    Conflict { flag: RECORD_CAST, with: &SHAPES, why: Some("the recording runs the plain backtracker") },
+    Conflict { flag: GIF, with: &SHAPES, why: Some("the recording runs the plain backtracker") },
+    Conflict { flag: PNG, with: &[COUNT], why: Some("it writes a single image") },
+    Conflict { flag: HTML, with: &[COUNT], why: Some("it writes a single page") },
+    Conflict { flag: JSON, with: &[COUNT], why: Some("it writes a single maze") },
+    Conflict { flag: SAVE_BIN, with: &[COUNT], why: Some("it writes a single maze") },
+    Conflict { flag: EXPECT_FINGERPRINT, with: &[COUNT], why: None },
+    Conflict { flag: RANDOM_MOUSE, with: &[ENDPOINTS, PRESET_ENDS, UNICURSAL], why: Some("it runs between the corners") },
+    Conflict { flag: ROUTE, with: &[DIFFICULTY], why: None },
+    Conflict { flag: BRAID, with: &[ROUTE], why: Some("the path wouldn't be the only solution") },
+    Conflict { flag: ALGORITHM, with: &[DIFFICULTY, ROUTE, GROW, STRAIGHTNESS, SYMMETRY, SPARSE], why: Some("it picks the generator") },
+    Conflict { flag: GROW, with: &[DIFFICULTY, ROUTE], why: None },
+    Conflict { flag: STRAIGHTNESS, with: &[DIFFICULTY, ROUTE, GROW], why: None },
+    Conflict { flag: SYMMETRY, with: &[DIFFICULTY, ROUTE, GROW, STRAIGHTNESS], why: None },
+    Conflict { flag: SPARSE, with: &[DIFFICULTY, ROUTE, GROW, STRAIGHTNESS, SYMMETRY], why: None },
+    Conflict { flag: SPARSE, with: &[EXITS, ENDPOINTS], why: Some("they could land on solid cells") },
+    Conflict { flag: UNICURSAL, with: &[ROUTE, BRAID, SYMMETRY, SPARSE], why: None },
+    Conflict { flag: UNICURSAL, with: &[EXITS, ENDPOINTS], why: Some("the corridor has its own ends") },
+    Conflict { flag: ENDPOINTS, with: &[EXITS, ROUTE], why: None },
+    Conflict { flag: PRESET_ENDS, with: &[ENDPOINTS, EXITS], why: Some("the preset picks the endpoints") },
+    Conflict { flag: START, with: &[ENDPOINTS, PRESET_ENDS, UNICURSAL, EXITS, RANDOM_MOUSE], why: None },
+    Conflict { flag: END, with: &[ENDPOINTS, PRESET_ENDS, UNICURSAL, EXITS, RANDOM_MOUSE], why: None },
+    Conflict { flag: LOAD, with: &SHAPES, why: Some("it skips generating") },
+    Conflict { flag: LOAD, with: &[SEED, EXITS, COUNT, COMPARE, ANIMATE, RECORD_CAST, GIF], why: Some("it skips generating") },
+    Conflict { flag: LOAD_BIN, with: &SHAPES, why: Some("it skips generating") },
+    Conflict { flag: LOAD_BIN, with: &[SEED, EXITS, COUNT, COMPARE, ANIMATE, RECORD_CAST, GIF], why: Some("it skips generating") },
+    Conflict { flag: COMPARE, with: &SHAPES, why: Some("every generator gets the same plain options") },
+    Conflict { flag: COMPARE, with: &[COUNT, ANIMATE, ANIMATE_SOLVE, RECORD_CAST, GIF, EXITS, PNG, HTML, JSON, STATS, EXPECT_FINGERPRINT], why: None },
+    Conflict { flag: COMPARE, with: &[BOX, HALFBLOCK, BRAILLE, HEATMAP, AXES, VIEW, OVERLAY, ANSWERS], why: None },
+    // The entrance and exit are still marked, only the path between them is left out
+    Conflict { flag: NO_SOLVE, with: &[SOLUTION_ONLY, SOLVER, STATS_FOOTER, PATH_JSON, HEATMAP_PATH, PATH_STYLE, ANIMATE_SOLVE], why: None },
+    Conflict { flag: SOLUTION_ONLY, with: &[COMPARE, EXITS, OVERLAY, STATS, STATS_FOOTER], why: Some("it prints no maze") },
+    Conflict { flag: BENCH, with: &[COUNT, COMPARE, LOAD, LOAD_BIN, OUTPUT, ANIMATE, RECORD_CAST, GIF], why: Some("it prints its table") },
+];
+
+// Stops with the usage on the first option that doesn't fit with the others, see the tables
+// above. What's left below depends on more than which options were given.
+pub(super) fn validate(options: &GenerateOptions) -> CliResult {
+    if options.draw.format != Format::Txt {
+        if let Some(flag) = TXT_ONLY.iter().find(|flag| (flag.given)(options)) {
+            return Err(usage_error!("{} can only be combined with the txt format", flag.name));
+        }
+    }
+
+    if let Some((flag, needed)) = NEEDS.iter().find(|(flag, needed)| (flag.given)(options) && !(needed.given)(options)) {
+        return Err(usage_error!("{} needs {}", flag.name, needed.name));
+    }
+
+    for conflict in CONFLICTS.iter().filter(|conflict| (conflict.flag.given)(options)) {
+        let given: Vec<&str> = conflict.with.iter()
+            .filter(|flag| (flag.given)(options))
+            .map(|flag| flag.name)
+            .collect();
+
+        if !given.is_empty() {
+            let why = conflict.why.map(|why| format!(", {}", why)).unwrap_or_default();
+
+            return Err(usage_error!("{}", sentence(format!("{} can't be combined with {}{}", conflict.flag.name, either(&given), why))));
+        }
+    }
+
+    if options.solve.overlay.len() >= OVERLAY_SYMBOLS.len() {
+        return Err(usage_error!("At most {} solvers can be drawn over each other", OVERLAY_SYMBOLS.len()));
+    }
+
+    if options.solve.solution_only && !matches!(options.draw.format, Format::Txt | Format::PathJson) {
+        return Err(usage_error!("--solution-only can only be combined with the txt and path-json formats"));
+    }
+
+    if (options.animation.animate || options.animation.animate_solve) && !(io::stdin().is_terminal() && io::stdout().is_terminal()) {
+        return Err(failure!("--animate and --animate-solve have to run in a terminal"));
+    }
+
+    if options.animation.gif.is_some() && !cfg!(feature = "gif") {
+        return Err(usage_error!("--gif needs a build with the gif feature"));
+    }
+
+    if options.output.png.is_some() && !cfg!(feature = "png") {
+        return Err(usage_error!("--png needs a build with the png feature"));
+    }
+
+    if options.maze.unicursal && (!options.size.0.is_multiple_of(2) || !options.size.1.is_multiple_of(2)) {
+        return Err(usage_error!("--unicursal needs an even width and height"));
+    }
+
+    for pos in [options.solve.start, options.solve.goal].into_iter().flatten() {
+        if pos.0 >= options.size.0 || pos.1 >= options.size.1 {
+            return Err(usage_error!("{},{} is outside the {}x{} maze", pos.0, pos.1, options.size.0, options.size.1));
+        }
+    }
+
+    let (start, goal) = (options.solve.start.unwrap_or_default(), options.solve.goal.unwrap_or(options.size.get_max_pos()));
+
+    if (options.solve.start.is_some() || options.solve.goal.is_some()) && start == goal {
+        return Err(usage_error!("--start and --end are both {},{}, pick two different cells", start.0, start.1));
+    }
+
+    // The solution is drawn through the openings unless --open-bare leaves them empty
+    for pos in [start, goal] {
+        if options.draw.open && pos.0 != 0 && pos.1 != 0 && pos.0 + 1 != options.size.0 && pos.1 + 1 != options.size.1 {
+            return Err(usage_error!("--open can't open the border next to {},{}, it isn't on the edge of the maze", pos.0, pos.1));
+        }
+    }
+
+    Ok(())
+}
+
+// Starts the message with a capital for the names that aren't flags
+fn sentence(text: String) -> String {
+    let mut chars = text.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => text,
+    }
+}
+
+// The names joined as "a, b or c"
+fn either(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.to_string(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::error::CliError;
+    use crate::cli::generate::{AnimationOptions, BenchOptions, DrawOptions, MazeOptions, OutputOptions, SolveOptions};
+    use my_project::solver::SolverKind;
+    use std::path::PathBuf;
+
+    fn options() -> GenerateOptions {
+        GenerateOptions {
+            size: Size(8, 6),
+            seed: None,
+            seed_chosen: false,
+            compare: None,
+            maze: MazeOptions::default(),
+            solve: SolveOptions::default(),
+            draw: DrawOptions::default(),
+            output: OutputOptions::default(),
+            animation: AnimationOptions::default(),
+            bench: BenchOptions::default(),
+        }
+    }
+
+    fn message(options: &GenerateOptions) -> String {
+        match validate(options) {
+            Err(error @ CliError::Usage(..)) => error.to_string(),
+            _ => panic!("the options were taken"),
+        }
+    }
+
+    #[test]
+    fn plain_options_pass() {
+        assert!(validate(&options()).is_ok());
+    }
+
+    #[test]
+    fn conflicts_name_only_the_flags_given() {
+        let mut blocks = options();
+        blocks.draw.halfblock = true;
+        blocks.draw.box_drawing = true;
+        assert_eq!(message(&blocks), "--halfblock can't be combined with --box, it draws whole blocks");
+
+        let mut loaded = options();
+        loaded.maze.load = Some(PathBuf::from("maze.json"));
+        loaded.seed = Some(3);
+        loaded.maze.exits = Some(2);
+        assert_eq!(message(&loaded), "--load can't be combined with --seed or --exits, it skips generating");
+
+        let mut overlay = options();
+        overlay.solve.overlay = vec![SolverKind::Bfs];
+        overlay.draw.heatmap = true;
+        assert_eq!(message(&overlay), "Several --solver names can't be combined with --heatmap");
+    }
+
+    #[test]
+    fn txt_only_and_needs_come_from_their_tables() {
+        let mut axes = options();
+        axes.draw.format = Format::Svg;
+        axes.draw.axes = true;
+        assert_eq!(message(&axes), "--axes can only be combined with the txt format");
+
+        let mut steps = options();
+        steps.animation.gif_steps = Some(3);
+        assert_eq!(message(&steps), "--gif-steps needs --gif");
+    }
+
+    #[test]
+    fn names_are_joined_with_or() {
+        assert_eq!(either(&["--a"]), "--a");
+        assert_eq!(either(&["--a", "--b", "--c"]), "--a, --b or --c");
+    }
+}
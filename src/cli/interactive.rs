@@ -0,0 +1,71 @@
+use crate::cli::GENERATE_USAGE;
+use crate::cli::error::{CliError, CliResult, check_stdout};
+use crate::cli::generate::solve::solve_corners;
+use my_project::*;
+use my_project::formats::Format;
+use my_project::generator::GeneratorKind;
+use std::io::{self, IsTerminal, Write};
+use strum::IntoEnumIterator;
+
+// Asks for the maze on the terminal when the binary is run without arguments. Anywhere else
+// it stops straight away with the usage so scripts fail fast.
+pub(crate) fn interactive() -> CliResult {
+    if !io::stdin().is_terminal() {
+        return Err(CliError::Usage(None, GENERATE_USAGE));
+    }
+
+    let side = |x: &str| x.parse().ok().filter(|x| *x > 0);
+
+    let width = prompt("Width", "Enter a whole number above 0", side)?;
+    let height = prompt("Height", "Enter a whole number above 0", side)?;
+
+    let generators: Vec<GeneratorKind> = GeneratorKind::iter().collect();
+
+    println!("Algorithms:");
+
+    for (i, kind) in generators.iter().enumerate() {
+        println!("  {}) {}", i + 1, kind);
+    }
+
+    let kind = prompt("Algorithm [1]", "Enter one of the numbers above", |x| match x {
+        "" => Some(generators[0]),
+        _ => generators.get(x.parse::<usize>().ok()?.checked_sub(1)?).copied(),
+    })?;
+
+    let solve = prompt("Show the solution? [y/N]", "Enter y or n", |x| match x.to_lowercase().as_str() {
+        "" | "n" | "no" => Some(false),
+        "y" | "yes" => Some(true),
+        _ => None,
+    })?;
+
+    let mut rng = rand::rng();
+    let mut maze = Maze::new(Size(width, height), true);
+    maze.generate(kind, &mut rng);
+
+    let solution = solve.then(|| solve_corners(&maze, &mut rng)).transpose()?;
+
+    println!();
+    check_stdout(Format::Txt.export(&maze, solution.as_deref(), &mut io::stdout().lock()))
+}
+
+// Asks `question` until `parse` accepts the trimmed answer, repeating `hint` after every
+// rejected one. Fails if the input ends.
+fn prompt<T>(question: &str, hint: &str, parse: impl Fn(&str) -> Option<T>) -> CliResult<T> {
+    loop {
+        print!("{}: ", question);
+        check_stdout(io::stdout().flush())?;
+
+        let mut line = String::new();
+
+        // Input that isn't text ends the questions like closing it does
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            println!();
+            return Err(failure!("The input ended before every question was answered"));
+        }
+
+        match parse(line.trim()) {
+            Some(answer) => return Ok(answer),
+            None => println!("{}", hint),
+        }
+    }
+}
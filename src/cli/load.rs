@@ -0,0 +1,99 @@
+use crate::cli::args::describe_detect_error;
+use crate::cli::error::{CliError, CliResult};
+use my_project::*;
+use my_project::formats::Format;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn load_maze(input: &Path, input_format: Option<Format>) -> CliResult<Maze> {
+    try_load_maze(input, input_format).map_err(CliError::Failed)
+}
+
+pub(crate) fn read_binary_maze(input: &Path) -> Result<Maze, String> {
+    let mut file = fs::File::open(input)
+        .map_err(|error| format!("Couldn't read '{}': {}", input.display(), error))?;
+
+    Maze::read_binary(&mut io::BufReader::new(&mut file))
+        .map_err(|error| format!("'{}' doesn't contain a valid maze: {}", input.display(), error))
+}
+
+pub(crate) fn try_load_maze(input: &Path, input_format: Option<Format>) -> Result<Maze, String> {
+    let input_format = match input_format {
+        Some(format) => format,
+        None => Format::detect(input).map_err(|error| describe_detect_error(input, error))?,
+    };
+
+    if !input_format.can_import() {
+        return Err(format!("Mazes can't be loaded from {} files", input_format));
+    }
+
+    let data = fs::read(input)
+        .map_err(|error| format!("Couldn't read '{}': {}", input.display(), error))?;
+
+    // The JSON and wall grid readers can say what's wrong with the file
+    if matches!(input_format, Format::Json | Format::Grid) {
+        let text = String::from_utf8(data).map_err(|_| format!("'{}' isn't text", input.display()))?;
+        let maze = match input_format {
+            Format::Json => Maze::from_json(&text),
+            _ => Maze::from_wall_grid_text(&text),
+        };
+
+        return maze.map_err(|error| format!("'{}' doesn't contain a valid maze: {}", input.display(), error));
+    }
+
+    input_format
+        .import(&data)
+        .map_err(|_| format!("'{}' doesn't contain a valid maze", input.display()))
+}
+
+// A directory yields every file in it with an importable extension. Otherwise a `*` or `?`
+// in the file name matches files in its parent directory, and anything else is one file.
+pub(crate) fn find_maze_files(pattern: &str) -> CliResult<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+
+    let (dir, name_pattern) = match path.file_name().and_then(|x| x.to_str()) {
+        _ if path.is_dir() => (path, None),
+        Some(name) if name.contains(['*', '?']) => (
+            path.parent().filter(|x| !x.as_os_str().is_empty()).unwrap_or(Path::new(".")),
+            Some(name)
+        ),
+        _ => return Ok(vec![path.to_path_buf()]),
+    };
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|error| failure!("Couldn't read '{}': {}", dir.display(), error))?
+        .filter_map(|entry| entry.ok().map(|x| x.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| match name_pattern {
+            Some(pattern) => path.file_name()
+                .and_then(|x| x.to_str())
+                .is_some_and(|name| wildcard_match(pattern, name)),
+            None => Format::candidates_for(path).iter().any(|x| x.can_import()),
+        })
+        .collect();
+
+    files.sort();
+    Ok(files)
+}
+
+// `*` matches any run of characters and `?` any single one
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let mut matches = vec![vec![false; name.len() + 1]; pattern.len() + 1];
+    matches[0][0] = true;
+
+    for i in 1..=pattern.len() {
+        for j in 0..=name.len() {
+            matches[i][j] = match pattern[i - 1] {
+                '*' => matches[i - 1][j] || (j > 0 && matches[i][j - 1]),
+                '?' => j > 0 && matches[i - 1][j - 1],
+                c => j > 0 && name[j - 1] == c && matches[i - 1][j - 1],
+            };
+        }
+    }
+
+    matches[pattern.len()][name.len()]
+}
@@ -0,0 +1,59 @@
+use crate::cli::args::{OrUsage, detect_format, number, parse_format, value};
+use crate::cli::error::{CliError, CliResult, check_stdout, check_written};
+use crate::cli::generate::solve::{solve_corners, solve_rng};
+use crate::cli::load::load_maze;
+use my_project::formats::Format;
+use my_project::save;
+use std::io;
+use std::path::Path;
+
+pub(crate) fn render(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: render <input> [--format FORMAT] [--input-format FORMAT] [-o OUTPUT] [--solve [--seed N]]";
+
+    let mut input = None;
+    let mut output = None;
+    let mut format = None;
+    let mut input_format = None;
+    let mut solve = false;
+    let mut seed = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--format" => format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--input-format" => input_format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            "-o" | "--output" => output = Some(Path::new(value(&mut args, arg, INVALID_INPUT)?)),
+            "--solve" => solve = true,
+            "--seed" => seed = Some(number(&mut args, arg, INVALID_INPUT)?),
+            _ if input.is_none() => input = Some(Path::new(arg)),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let maze = load_maze(input.or_usage(INVALID_INPUT)?, input_format)?;
+
+    let format = match (format, output) {
+        (Some(format), _) => format,
+        (None, Some(output)) => detect_format(output)?,
+        (None, None) => Format::Txt,
+    };
+
+    if seed.is_some() && !solve {
+        return Err(usage_error!(INVALID_INPUT; "--seed only applies to --solve"));
+    }
+
+    let solution = solve.then(|| solve_corners(&maze, &mut solve_rng(seed))).transpose()?;
+
+    match output {
+        Some(output) => {
+            let written = save::write_atomically(output, |file| format.export(&maze, solution.as_deref(), file));
+
+            check_written(output, written)?;
+        },
+        None => check_stdout(format.export(&maze, solution.as_deref(), &mut io::stdout().lock()))?,
+    }
+
+    Ok(())
+}
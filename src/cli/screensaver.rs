@@ -0,0 +1,134 @@
+use crate::cli::args::number;
+use crate::cli::error::{CliError, CliResult, check_stdout};
+use my_project::*;
+use my_project::animation;
+use my_project::batch::batch_seed;
+use my_project::generator::GeneratorKind;
+use my_project::rng::Pcg32;
+use my_project::terminal;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
+
+// How long the screensaver takes to draw one maze and how often it checks the terminal size
+const SCREENSAVER_ANIMATION: Duration = Duration::from_secs(8);
+const SCREENSAVER_POLL: Duration = Duration::from_millis(250);
+
+// Fills the terminal with a maze, animates generating and then solving it and starts over
+// with the next generator and a new seed, until any key is pressed. A resized terminal gets
+// a new maze that fits it straight away.
+pub(crate) fn screensaver(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: screensaver [--seed N] [--pause MS]";
+
+    let mut seed = None;
+    let mut pause = Duration::from_secs(2);
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--seed" => seed = Some(number(&mut args, arg, INVALID_INPUT)?),
+            "--pause" => pause = Duration::from_millis(number(&mut args, arg, INVALID_INPUT)?),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let seed = seed.unwrap_or_else(rand::random);
+
+    let Ok(_guard) = terminal::RawTerminal::enter() else {
+        return Err(failure!("The screensaver has to run in a terminal"));
+    };
+
+    // Raw mode delivers Ctrl-C as a key too, so every way out goes through the guard
+    let stop = Arc::new(AtomicBool::new(false));
+    let key = Arc::clone(&stop);
+
+    thread::spawn(move || {
+        let _ = io::stdin().read(&mut [0]);
+        key.store(true, Ordering::Relaxed);
+    });
+
+    let generators: Vec<GeneratorKind> = GeneratorKind::iter().collect();
+    let mut stdout = io::stdout().lock();
+
+    for round in 0.. {
+        let terminal_size = terminal::terminal_size();
+
+        let Some(size) = terminal_size.and_then(|x| terminal::fit_size(x, 1)) else {
+            if wait_for_key(&stop, SCREENSAVER_POLL, terminal_size) {
+                return Ok(());
+            }
+
+            continue;
+        };
+
+        let kind = generators[round % generators.len()];
+        let mut rng = Pcg32::new(batch_seed(seed, round));
+        let mut maze = Maze::new(size, true);
+
+        let log = maze.generate_logged(kind, &mut rng);
+
+        // Alternates between the random depth first solver and the shortest path. A cave or
+        // sparse maze whose corners aren't connected is only generated.
+        let path = match round % 2 {
+            0 => maze.solve_between_with_rng(Position::new(), size.get_max_pos(), &mut rng).ok(),
+            _ => maze.shortest_path(Position::new(), size.get_max_pos()),
+        };
+
+        let steps = log.events.len() + path.as_ref().map_or(0, Vec::len);
+        let delay = (SCREENSAVER_ANIMATION / steps.max(1) as u32)
+            .clamp(Duration::from_millis(2), Duration::from_millis(40));
+
+        let frames = animation::generation_frames(&log, path.as_deref(), delay);
+        let start = Instant::now();
+        let mut last_poll = Instant::now();
+
+        for frame in &frames {
+            thread::sleep(frame.time.saturating_sub(start.elapsed()));
+
+            if stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+
+            if last_poll.elapsed() >= SCREENSAVER_POLL {
+                if terminal::terminal_size() != terminal_size {
+                    break;
+                }
+
+                last_poll = Instant::now();
+            }
+
+            check_stdout(write!(stdout, "{}", frame.data).and_then(|_| stdout.flush()))?;
+        }
+
+        if wait_for_key(&stop, pause, terminal_size) {
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+// Sleeps for `duration` in short steps, returning early when the terminal stops being
+// `terminal_size`. True if a key was pressed.
+fn wait_for_key(stop: &AtomicBool, duration: Duration, terminal_size: Option<Size>) -> bool {
+    let start = Instant::now();
+
+    while start.elapsed() < duration {
+        if stop.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        if terminal::terminal_size() != terminal_size {
+            break;
+        }
+
+        thread::sleep(SCREENSAVER_POLL.min(duration.saturating_sub(start.elapsed())));
+    }
+
+    stop.load(Ordering::Relaxed)
+}
@@ -0,0 +1,123 @@
+use crate::cli::args::{OrUsage, parse_format, parse_path_style, parse_position, parse_solver, value};
+use crate::cli::error::{CliError, CliResult, check_drawn, check_written, print_display};
+use crate::cli::load::load_maze;
+use my_project::*;
+use my_project::arrows::PathStyle;
+use my_project::waypoints::RouteError;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+// Solves a saved maze from corner to corner, passing the --via waypoints in order or all
+// waypoints in the shortest order with --visit-all
+pub(crate) fn solve(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: solve <maze-file> [--input-format FORMAT] [--waypoint NAME=X,Y]... [--via NAME]... [--visit-all] [--solver NAME] [--block X,Y]... [--path-style STYLE] [--no-color] [--wide | --no-wide] [-o OUTPUT]";
+
+    let mut input = None;
+    let mut input_format = None;
+    let mut waypoints = vec![];
+    let mut via = vec![];
+    let mut visit_all = false;
+    let mut solver = None;
+    let mut blocked = vec![];
+    let mut path_style = PathStyle::Dots;
+    let mut no_color = false;
+    let mut wide = None;
+    let mut output = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--input-format" => input_format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--waypoint" => {
+                let text = value(&mut args, arg, INVALID_INPUT)?;
+
+                waypoints.push(text.split_once('=').ok_or_else(|| usage_error!(INVALID_INPUT; "--waypoint takes NAME=X,Y, got '{}'", text))?);
+            },
+            "--via" => via.push(value(&mut args, arg, INVALID_INPUT)?.as_str()),
+            "--visit-all" => visit_all = true,
+            "--solver" => solver = Some(parse_solver(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--block" => {
+                let text = value(&mut args, arg, INVALID_INPUT)?;
+
+                blocked.push(parse_position(text).ok_or_else(|| usage_error!(INVALID_INPUT; "--block takes X,Y, got '{}'", text))?);
+            },
+            "--path-style" => path_style = parse_path_style(value(&mut args, arg, INVALID_INPUT)?)?,
+            "--no-color" => no_color = true,
+            "--wide" => wide = Some(true),
+            "--no-wide" => wide = Some(false),
+            "-o" | "--output" => output = Some(Path::new(value(&mut args, arg, INVALID_INPUT)?)),
+            _ if input.is_none() => input = Some(Path::new(arg)),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let mut maze = load_maze(input.or_usage(INVALID_INPUT)?, input_format)?;
+
+    for (name, pos) in waypoints {
+        let pos = parse_position(pos).ok_or_else(|| usage_error!(INVALID_INPUT; "Waypoint '{}' takes X,Y, got '{}'", name, pos))?;
+
+        maze.set_waypoint(name, pos)
+            .map_err(|_| usage_error!(INVALID_INPUT; "Waypoint '{}' at ({}, {}) is outside the maze", name, pos.0, pos.1))?;
+    }
+
+    if visit_all && !via.is_empty() {
+        return Err(usage_error!(INVALID_INPUT; "--visit-all picks its own order and can't be combined with --via"));
+    }
+
+    if solver.is_some() && (visit_all || !via.is_empty()) {
+        return Err(usage_error!(INVALID_INPUT; "--solver goes straight to the goal and can't be combined with --via or --visit-all"));
+    }
+
+    if !blocked.is_empty() && (solver.is_some() || visit_all || !via.is_empty()) {
+        return Err(usage_error!(INVALID_INPUT; "--block can't be combined with --solver, --via or --visit-all"));
+    }
+
+    let result = match (visit_all, solver) {
+        _ if !blocked.is_empty() => {
+            let (from, to) = (Position::new(), maze.size.get_max_pos());
+
+            maze.solve_avoiding(from, to, &blocked)
+                .ok_or(RouteError::Unreachable { from, to })
+        },
+        (_, Some(solver)) => {
+            let (from, to) = (Position::new(), maze.size.get_max_pos());
+
+            maze.solve_with(solver.solver(), from, to)
+                .map_err(|_| RouteError::Unreachable { from, to })
+        },
+        (true, None) => {
+            let targets: Vec<Position> = maze.waypoints.iter().map(|(_, pos)| *pos).collect();
+
+            maze.solve_visiting_all(Position::new(), maze.size.get_max_pos(), &targets)
+                .map(|(path, order)| {
+                    eprintln!(
+                        "Order: {}",
+                        order.iter()
+                            .map(|i| maze.waypoints[*i].0.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", ")
+                    );
+
+                    path
+                })
+        },
+        (false, None) => maze.solve_via(Position::new(), maze.size.get_max_pos(), &via),
+    };
+
+    let path = result.map_err(|error| failure!("Can't solve the maze: {}", error))?;
+
+    let mut display = Display::new_from_maze(&maze);
+    display.color = !no_color;
+    display.wide = wide.unwrap_or(output.is_none() && io::stdout().is_terminal());
+    check_drawn(display.draw_maze(&maze))?;
+    check_drawn(display.draw_solution_styled(&path, path_style))?;
+    display.draw_blocked(&blocked);
+    display.draw_waypoints(&maze);
+
+    match output {
+        Some(output) => check_written(output, display.save(output)),
+        None => print_display(display),
+    }
+}
@@ -0,0 +1,159 @@
+use crate::cli::args::{OrUsage, parse_format, value};
+use crate::cli::error::{CliError, CliResult, check_stdout};
+use crate::cli::load::{find_maze_files, try_load_maze};
+use my_project::*;
+use my_project::json::json_string;
+use my_project::metrics::{MazeMetrics, Summary};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+// Prints aggregate metrics over every maze matched by a directory or a file name pattern.
+// Files that can't be loaded are reported and left out instead of stopping the run.
+pub(crate) fn stats(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: stats <dir-or-glob> [--each] [--json] [--input-format FORMAT]";
+
+    let mut pattern = None;
+    let mut input_format = None;
+    let mut each = false;
+    let mut json = false;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--each" => each = true,
+            "--json" => json = true,
+            "--input-format" => input_format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            _ if pattern.is_none() => pattern = Some(arg.as_str()),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let mut rows: Vec<(PathBuf, Size, MazeMetrics)> = vec![];
+    let mut skipped: Vec<PathBuf> = vec![];
+
+    for path in find_maze_files(pattern.or_usage(INVALID_INPUT)?)? {
+        match try_load_maze(&path, input_format) {
+            Ok(maze) => rows.push((path, maze.size, maze.metrics())),
+            Err(error) => {
+                eprintln!("Skipping: {}", error);
+                skipped.push(path);
+            },
+        }
+    }
+
+    let solution_lengths: Vec<f64> = rows.iter()
+        .filter_map(|(_, _, metrics)| metrics.solution_length)
+        .map(|x| x as f64)
+        .collect();
+
+    let dead_ends: Vec<f64> = rows.iter()
+        .map(|(_, _, metrics)| metrics.dead_ends as f64)
+        .collect();
+
+    let scores: Vec<f64> = rows.iter()
+        .filter_map(|(_, _, metrics)| metrics.difficulty_score())
+        .collect();
+
+    let summaries = [
+        ("solution_length", "Solution length", Summary::of(&solution_lengths)),
+        ("dead_ends", "Dead ends", Summary::of(&dead_ends)),
+        ("difficulty_score", "Difficulty score", Summary::of(&scores)),
+    ];
+
+    let mut stdout = io::stdout().lock();
+
+    let written = match json {
+        true => write_stats_json(&mut stdout, &rows, &skipped, &summaries, each),
+        false => write_stats_table(&mut stdout, &rows, &skipped, &summaries, each),
+    };
+
+    check_stdout(written.and_then(|_| stdout.flush()))
+}
+
+fn write_stats_table<W: Write>(
+    w: &mut W,
+    rows: &[(PathBuf, Size, MazeMetrics)],
+    skipped: &[PathBuf],
+    summaries: &[(&str, &str, Option<Summary>)],
+    each: bool
+) -> io::Result<()> {
+    if each {
+        writeln!(w, "{:<9} {:>8} {:>9} {:>6}  File", "Size", "Solution", "Dead ends", "Score")?;
+
+        for (path, size, metrics) in rows {
+            writeln!(
+                w,
+                "{:<9} {:>8} {:>9} {:>6}  {}",
+                format!("{}x{}", size.0, size.1),
+                metrics.solution_length.map_or("-".to_string(), |x| x.to_string()),
+                metrics.dead_ends,
+                metrics.difficulty_score().map_or("-".to_string(), |x| format!("{:.3}", x)),
+                path.display()
+            )?;
+        }
+
+        writeln!(w)?;
+    }
+
+    writeln!(w, "Mazes: {}, skipped: {}", rows.len(), skipped.len())?;
+    writeln!(w, "{:<17} {:>9} {:>9} {:>9} {:>9}", "", "min", "median", "mean", "max")?;
+
+    for (_, label, summary) in summaries {
+        match summary {
+            Some(x) => writeln!(
+                w,
+                "{:<17} {:>9.3} {:>9.3} {:>9.3} {:>9.3}",
+                label, x.min, x.median, x.mean, x.max
+            )?,
+            None => writeln!(w, "{:<17} {:>9}", label, "-")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn write_stats_json<W: Write>(
+    w: &mut W,
+    rows: &[(PathBuf, Size, MazeMetrics)],
+    skipped: &[PathBuf],
+    summaries: &[(&str, &str, Option<Summary>)],
+    each: bool
+) -> io::Result<()> {
+    let path_list = |paths: Vec<&PathBuf>| paths.iter()
+        .map(|path| json_string(&path.display().to_string()))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    write!(w, "{{\"count\":{},\"skipped\":[{}]", rows.len(), path_list(skipped.iter().collect()))?;
+
+    for (key, _, summary) in summaries {
+        match summary {
+            Some(x) => write!(
+                w,
+                ",\"{}\":{{\"min\":{},\"median\":{},\"mean\":{},\"max\":{}}}",
+                key, x.min, x.median, x.mean, x.max
+            )?,
+            None => write!(w, ",\"{}\":null", key)?,
+        }
+    }
+
+    if each {
+        let files: Vec<String> = rows.iter()
+            .map(|(path, size, metrics)| format!(
+                "{{\"path\":{},\"width\":{},\"height\":{},\"solution_length\":{},\"dead_ends\":{},\"difficulty_score\":{}}}",
+                json_string(&path.display().to_string()),
+                size.0,
+                size.1,
+                metrics.solution_length.map_or("null".to_string(), |x| x.to_string()),
+                metrics.dead_ends,
+                metrics.difficulty_score().map_or("null".to_string(), |x| x.to_string())
+            ))
+            .collect();
+
+        write!(w, ",\"files\":[{}]", files.join(","))?;
+    }
+
+    writeln!(w, "}}")
+}
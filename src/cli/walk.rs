@@ -0,0 +1,115 @@
+use crate::cli::args::{OrUsage, number, parse_format, value};
+use crate::cli::error::{CliError, CliResult, check_drawn, check_stdout, check_written, print_display};
+use crate::cli::load::load_maze;
+use my_project::*;
+use my_project::animation;
+use my_project::mouse::MOUSE_STEPS_PER_CELL;
+use my_project::rng::Pcg32;
+use my_project::solution;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+// Exits with 1 if the walk is invalid and with 3 if it is valid but doesn't get from the
+// entrance to the exit, after drawing it.
+pub(crate) fn walk(args: &[String]) -> CliResult {
+    const INVALID_INPUT: &str = "Usage: walk <maze-file> (--moves MOVES | --path-file FILE | --random-mouse [--seed SEED]) [--input-format FORMAT] [--animate] [--delay MS] [-o OUTPUT]";
+
+    let mut input = None;
+    let mut input_format = None;
+    let mut moves = None;
+    let mut path_file = None;
+    let mut random_mouse = false;
+    let mut seed = None;
+    let mut animate = false;
+    let mut delay = Duration::from_millis(50);
+    let mut output = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help(INVALID_INPUT.to_string())),
+            "--moves" => moves = Some(value(&mut args, arg, INVALID_INPUT)?),
+            "--path-file" => path_file = Some(Path::new(value(&mut args, arg, INVALID_INPUT)?)),
+            "--random-mouse" => random_mouse = true,
+            "--seed" => seed = Some(number(&mut args, arg, INVALID_INPUT)?),
+            "--input-format" => input_format = Some(parse_format(value(&mut args, arg, INVALID_INPUT)?)?),
+            "--animate" => animate = true,
+            "--delay" => delay = Duration::from_millis(number(&mut args, arg, INVALID_INPUT)?),
+            "-o" | "--output" => output = Some(Path::new(value(&mut args, arg, INVALID_INPUT)?)),
+            _ if input.is_none() => input = Some(Path::new(arg)),
+            _ => return Err(usage_error!(INVALID_INPUT; "Unknown argument '{}'", arg)),
+        }
+    }
+
+    let maze = load_maze(input.or_usage(INVALID_INPUT)?, input_format)?;
+
+    if seed.is_some() && !random_mouse {
+        return Err(usage_error!(INVALID_INPUT; "--seed only applies to --random-mouse"));
+    }
+
+    let path = match (moves, path_file) {
+        (None, None) if random_mouse => {
+            let max_steps = maze.tiles.len() * MOUSE_STEPS_PER_CELL;
+
+            let trace = match seed {
+                Some(seed) => maze.solve_random_mouse(&mut Pcg32::new(seed), max_steps),
+                None => maze.solve_random_mouse(&mut rand::rng(), max_steps),
+            };
+
+            let trace = trace
+                .ok_or_else(|| CliError::Incomplete(format!("The random mouse didn't find the exit in {} moves", max_steps)))?;
+
+            eprintln!("Random mouse: {} steps, shortest {}", trace.len() - 1, maze.solve_bfs().len().saturating_sub(1));
+
+            Ok(trace)
+        },
+        (Some(moves), None) if !random_mouse => {
+            let moves = solution::parse_moves(moves)
+                .map_err(|_| usage_error!(INVALID_INPUT; "Moves must only contain the letters N, E, S and W"))?;
+
+            maze.walk_moves(Position::new(), &moves)
+        },
+        (None, Some(path_file)) if !random_mouse => {
+            let text = fs::read_to_string(path_file)
+                .map_err(|error| failure!("Couldn't read '{}': {}", path_file.display(), error))?;
+            let path = solution::parse_coords(&text)
+                .map_err(|_| failure!("The path file must be a path-json file or a JSON array of [x, y] pairs"))?;
+
+            maze.verify_path(&path).map(|_| path)
+        },
+        _ => return Err(CliError::Usage(None, INVALID_INPUT)),
+    };
+
+    let path = path.map_err(|error| failure!("Invalid walk: {}", error))?;
+
+    let complete = path.first() == Some(&Position::new()) && path.last() == Some(&maze.size.get_max_pos());
+
+    if animate {
+        check_stdout(animation::play(
+            &mut io::stdout().lock(),
+            &animation::walk_frames(&maze, &path, complete, delay)
+        ))?;
+    }
+
+    // An animated walk ends on the same drawing, so only the file still needs it
+    if !animate || output.is_some() {
+        let mut display = Display::new_from_maze(&maze);
+        check_drawn(display.draw_maze(&maze))?;
+        check_drawn(display.draw_walk(&path, complete, POINT_CHAR))?;
+
+        match output {
+            Some(output) => check_written(output, display.save(output))?,
+            None => print_display(display)?,
+        }
+    }
+
+    match path.last() {
+        Some(last) if !complete => Err(CliError::Incomplete(format!(
+            "The walk stops at ({}, {}) without getting from the entrance to the exit", last.0, last.1
+        ))),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,107 @@
+use std::io;
+
+use ndarray::s;
+
+use crate::{Display, Maze, Position, Size, POINT_CHAR};
+
+impl Display {
+    /// Copies the pixels and colors of `other` onto this display with its top left corner
+    /// at `offset`. Nothing is drawn if `other` would go past the edge.
+    pub fn blit(&mut self, other: &Display, offset: Position) -> Result<(), io::ErrorKind> {
+        if offset.0 + other.size.0 > self.size.0 || offset.1 + other.size.1 > self.size.1 {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let window = s![offset.1..offset.1 + other.size.1, offset.0..offset.0 + other.size.0];
+
+        self.pixels.slice_mut(window).assign(&other.pixels);
+        self.colors.slice_mut(window).assign(&other.colors);
+
+        Ok(())
+    }
+
+    /// Draws the mazes, and the solutions of those that have one, next to each other in
+    /// rows of `columns` with `gap` empty pixels between them. Every maze gets a slot as
+    /// big as the biggest one, smaller ones sit in its top left corner.
+    pub fn grid_of_mazes(mazes: &[(Maze, Option<Vec<Position>>)], columns: usize, gap: usize) -> Result<Display, io::ErrorKind> {
+        if columns == 0 {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let tiles = mazes.iter()
+            .map(|(maze, path)| {
+                let mut tile = Display::new_from_maze(maze);
+                tile.draw_maze(maze)?;
+
+                if let Some(path) = path {
+                    tile.draw_solution(path, POINT_CHAR)?;
+                }
+
+                Ok(tile)
+            })
+            .collect::<Result<Vec<Display>, io::ErrorKind>>()?;
+
+        let slot = Size(
+            tiles.iter().map(|x| x.size.0).max().unwrap_or(0),
+            tiles.iter().map(|x| x.size.1).max().unwrap_or(0)
+        );
+
+        let (across, down) = (columns.min(tiles.len()), tiles.len().div_ceil(columns));
+
+        let mut display = Display::new(Size(
+            across * slot.0 + across.saturating_sub(1) * gap,
+            down * slot.1 + down.saturating_sub(1) * gap
+        ));
+
+        for (i, tile) in tiles.iter().enumerate() {
+            let offset = Position((i % columns) * (slot.0 + gap), (i / columns) * (slot.1 + gap));
+
+            display.blit(tile, offset)?;
+        }
+
+        Ok(display)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Maze, Position, Size};
+    use std::io;
+
+    #[test]
+    fn mazes_sit_in_equal_slots_with_gaps() {
+        let wide = opened(Size(2, 1), &[(Position(0, 0), Direction::East)]);
+        let small = Maze::new(Size(1, 1), true);
+
+        let mut display = Display::grid_of_mazes(&[
+            (wide.clone(), None),
+            (small.clone(), None),
+            (wide, Some(vec![Position(0, 0), Position(1, 0)])),
+        ], 2, 1).unwrap();
+        display.margins = Margins::none();
+
+        // The small maze keeps to the top left of its slot, the third wraps onto a new row
+        let expected = [
+            "█████ ███",
+            "█   █ █ █",
+            "█████ ███",
+            "",
+            "█•███",
+            "█•••█",
+            "███•█",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn blits_past_the_edge_are_refused() {
+        let mut display = Display::new(Size(5, 5));
+        let tile = Display::new_from_maze(&Maze::new(Size(1, 1), true));
+
+        assert_eq!(display.blit(&tile, Position(2, 2)), Ok(()));
+        assert_eq!(display.blit(&tile, Position(3, 0)), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(Display::grid_of_mazes(&[], 0, 1).err(), Some(io::ErrorKind::InvalidInput));
+    }
+}
@@ -0,0 +1,151 @@
+use std::io;
+
+use crate::{Direction, Maze, Position};
+
+/// How `repair_inconsistencies` settles a wall whose two sides disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairPolicy {
+    Open,
+    Close,
+}
+
+impl Maze {
+    /// Every shared wall whose two sides disagree, reported from the cell west or north of it.
+    pub fn find_inconsistencies(&self) -> Vec<(Position, Direction)> {
+        let mut out = vec![];
+
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            let pos = Position(x, y);
+
+            for direction in [Direction::East, Direction::South] {
+                let Some(neighbor) = self.get_neighbor(pos, direction) else {
+                    continue;
+                };
+
+                let mirrored = self.get_tile(neighbor)
+                    .unwrap()
+                    .get_side(direction.get_opposite());
+
+                if tile.get_side(direction) != mirrored {
+                    out.push((pos, direction));
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn assert_consistent(&self) {
+        let inconsistencies = self.find_inconsistencies();
+
+        assert!(
+            inconsistencies.is_empty(),
+            "Mirrored walls disagree at {:?}",
+            inconsistencies
+        );
+    }
+
+    /// Makes both sides of every disagreeing wall match according to `policy` and returns
+    /// how many walls were changed.
+    pub fn repair_inconsistencies(&mut self, policy: RepairPolicy) -> usize {
+        let inconsistencies = self.find_inconsistencies();
+
+        for (pos, direction) in &inconsistencies {
+            self.set_wall(*pos, *direction, policy == RepairPolicy::Close)
+                .unwrap();
+        }
+
+        inconsistencies.len()
+    }
+
+    pub fn open_wall(&mut self, pos: Position, direction: Direction) -> Result<(), io::ErrorKind> {
+        self.set_wall(pos, direction, false)?;
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    pub fn close_wall(&mut self, pos: Position, direction: Direction) -> Result<(), io::ErrorKind> {
+        self.set_wall(pos, direction, true)?;
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    /// Sets both sides of a shared wall without checking the rest of the grid, for
+    /// operations that assert consistency once they are done.
+    pub(crate) fn set_wall(&mut self, pos: Position, direction: Direction, closed: bool) -> Result<(), io::ErrorKind> {
+        let neighbor = self
+            .get_neighbor(pos, direction)
+            .ok_or(io::ErrorKind::InvalidInput)?;
+
+        self.get_mut_tile(pos)
+            .unwrap()
+            .set_side(direction, closed);
+
+        self.get_mut_tile(neighbor)
+            .unwrap()
+            .set_side(direction.get_opposite(), closed);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RepairPolicy;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+
+    // A generated maze with the east side of its first cell flipped in the raw array, leaving
+    // the west side of the cell next to it as it was
+    fn desynced_maze() -> Maze {
+        let mut maze = Maze::new(Size(6, 6), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(4));
+
+        let tile = &mut maze.tiles[[0, 0]];
+        tile.set_side(Direction::East, !tile.get_side(Direction::East));
+
+        maze
+    }
+
+    #[test]
+    fn generated_mazes_are_consistent() {
+        let mut maze = Maze::new(Size(6, 6), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(4));
+
+        assert!(maze.find_inconsistencies().is_empty());
+        assert_eq!(desynced_maze().find_inconsistencies(), vec![(Position(0, 0), Direction::East)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mirrored walls disagree")]
+    fn desynced_walls_fail_the_assertion() {
+        desynced_maze().assert_consistent();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Mirrored walls disagree")]
+    fn mutations_assert_in_debug_builds() {
+        desynced_maze().close_wall(Position(3, 3), Direction::South).unwrap();
+    }
+
+    #[test]
+    fn repairs_settle_both_sides_by_the_policy() {
+        for (policy, closed) in [(RepairPolicy::Open, false), (RepairPolicy::Close, true)] {
+            let mut maze = desynced_maze();
+
+            assert_eq!(maze.repair_inconsistencies(policy), 1);
+            assert!(maze.find_inconsistencies().is_empty());
+            assert_eq!(maze.tiles[[0, 0]].get_side(Direction::East), closed);
+            assert_eq!(maze.tiles[[1, 0]].get_side(Direction::West), closed);
+        }
+    }
+}
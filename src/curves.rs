@@ -0,0 +1,163 @@
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::{Direction, Maze, Position, Size};
+
+/// Cells of an `n`x`n` Hilbert curve in curve order, from `(0, 0)` to `(n - 1, 0)`. `n` has
+/// to be a power of two.
+pub fn hilbert_order(n: usize) -> Result<Vec<Position>, io::ErrorKind> {
+    if !n.is_power_of_two() {
+        return Err(io::ErrorKind::InvalidInput);
+    }
+
+    Ok((0..n * n).map(|d| hilbert_position(n, d)).collect())
+}
+
+/// Cells of a boustrophedon through the whole grid starting at `(0, 0)`. It sweeps rows,
+/// or columns if that makes it end in the bottom right corner and rows wouldn't.
+pub fn serpentine_order(size: Size) -> Vec<Position> {
+    let by_columns = size.1.is_multiple_of(2) && !size.0.is_multiple_of(2);
+
+    let (lines, length) = match by_columns {
+        true => (size.0, size.1),
+        false => (size.1, size.0),
+    };
+
+    let mut out = vec![];
+
+    for line in 0..lines {
+        for i in 0..length {
+            let along = if line % 2 == 0 { i } else { length - 1 - i };
+
+            out.push(match by_columns {
+                true => Position(line, along),
+                false => Position(along, line),
+            });
+        }
+    }
+
+    out
+}
+
+// Maps index `d` along the curve to its cell by undoing one quadrant per level
+fn hilbert_position(n: usize, d: usize) -> Position {
+    let (mut x, mut y) = (0, 0);
+    let mut t = d;
+    let mut s = 1;
+
+    while s < n {
+        let rx = 1 & (t / 2);
+        let ry = 1 & (t ^ rx);
+
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - x;
+                y = s - 1 - y;
+            }
+
+            (x, y) = (y, x);
+        }
+
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s *= 2;
+    }
+
+    Position(x, y)
+}
+
+impl Maze {
+    /// Opens the wall between every pair of consecutive cells in `route`, which has to step
+    /// between neighbours only.
+    pub fn carve_route(&mut self, route: &[Position]) -> Result<(), io::ErrorKind> {
+        for pair in route.windows(2) {
+            let direction = Direction::iter()
+                .find(|direction| self.get_neighbor(pair[0], *direction) == Some(pair[1]))
+                .ok_or(io::ErrorKind::InvalidInput)?;
+
+            self.set_wall(pair[0], direction, false)?;
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    /// Carves a single corridor along a Hilbert curve. Only square mazes with a power of two
+    /// side are supported. The corridor ends in the top right corner, so the usual corner to
+    /// corner solution stops partway along it.
+    pub fn generate_hilbert(&mut self) -> Result<(), io::ErrorKind> {
+        if self.size.0 != self.size.1 {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        self.carve_route(&hilbert_order(self.size.0)?)
+    }
+
+    /// Carves a single corridor sweeping back and forth, see `serpentine_order`. Whenever a
+    /// grid allows it the corridor ends in the bottom right corner and is the whole solution.
+    pub fn generate_serpentine(&mut self) {
+        self.carve_route(&serpentine_order(self.size))
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hilbert_order, serpentine_order};
+    use crate::{Maze, Position, Size};
+
+    // Every cell has two open sides except the two ends of `route`, which have one
+    fn assert_single_corridor(maze: &Maze, route: &[Position]) {
+        let ends = [route[0], route[route.len() - 1]];
+
+        for ((x, y), tile) in maze.tiles.indexed_iter() {
+            let open = tile.get_sides().iter().filter(|(_, closed)| !*closed).count();
+            let expected = if ends.contains(&Position(x, y)) { 1 } else { 2 };
+
+            assert_eq!(open, expected, "{:?} at {:?}", maze.size, Position(x, y));
+        }
+    }
+
+    #[test]
+    fn hilbert_curves_are_one_corridor() {
+        for n in [2, 4, 8, 16] {
+            let mut maze = Maze::new(Size(n, n), true);
+            maze.generate_hilbert().unwrap();
+
+            let route = hilbert_order(n).unwrap();
+            assert_eq!(route.len(), n * n);
+            assert_eq!((route[0], route[n * n - 1]), (Position(0, 0), Position(n - 1, 0)));
+            assert_single_corridor(&maze, &route);
+        }
+
+        assert!(Maze::new(Size(6, 6), true).generate_hilbert().is_err());
+        assert!(Maze::new(Size(8, 4), true).generate_hilbert().is_err());
+    }
+
+    #[test]
+    fn serpentines_are_one_corridor() {
+        for size in [Size(5, 4), Size(4, 5), Size(4, 4), Size(5, 5), Size(1, 6), Size(7, 1)] {
+            let mut maze = Maze::new(size, true);
+            maze.generate_serpentine();
+
+            let route = serpentine_order(size);
+            assert_eq!(route.len(), size.0 * size.1);
+            assert_single_corridor(&maze, &route);
+        }
+    }
+
+    #[test]
+    fn the_solution_is_the_whole_corridor() {
+        // Every shape but even by even can end the sweep in the bottom right corner
+        for size in [Size(5, 4), Size(4, 5), Size(5, 5)] {
+            let mut maze = Maze::new(size, true);
+            maze.generate_serpentine();
+
+            assert_eq!(maze.solve_bfs(), serpentine_order(size), "{size:?}");
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::{Direction, Display, Maze, Position};
+
+/// Marks a wall that is open in the second maze but closed in the first.
+pub const OPENED_CHAR: char = '░';
+/// Marks a wall that is closed in the second maze but open in the first.
+pub const CLOSED_CHAR: char = '▓';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WallChange {
+    Opened,
+    Closed,
+}
+
+/// A wall that differs between two mazes, from the first maze to the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WallDiff {
+    pub pos: Position,
+    pub direction: Direction,
+    pub change: WallChange,
+}
+
+impl Maze {
+    /// Every wall whose state differs in `other`, which must have the same size. Shared
+    /// walls are reported once, from the cell west or north of them; border walls from the
+    /// cell they belong to.
+    pub fn diff(&self, other: &Maze) -> Result<Vec<WallDiff>, io::ErrorKind> {
+        if self.size != other.size {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let mut out = vec![];
+
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            let pos = Position(x, y);
+            let other_tile = other.tiles[[x, y]];
+
+            for direction in Direction::iter() {
+                let shared = self.get_neighbor(pos, direction).is_some();
+
+                if shared && !matches!(direction, Direction::East | Direction::South) {
+                    continue;
+                }
+
+                let change = match (tile.get_side(direction), other_tile.get_side(direction)) {
+                    (true, false) => WallChange::Opened,
+                    (false, true) => WallChange::Closed,
+                    _ => continue,
+                };
+
+                out.push(WallDiff { pos, direction, change });
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl Display {
+    /// Overwrites the wall pixel of every change with `OPENED_CHAR` or `CLOSED_CHAR`.
+    pub fn draw_diff(&mut self, diff: &[WallDiff]) {
+        for wall in diff {
+            let symbol = match wall.change {
+                WallChange::Opened => OPENED_CHAR,
+                WallChange::Closed => CLOSED_CHAR,
+            };
+
+            self.draw_point(Maze::to_display_pos(wall.pos).translate(wall.direction), symbol);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WallChange, CLOSED_CHAR};
+    use crate::rng::Pcg32;
+    use crate::{Display, Margins, Maze, Size};
+
+    fn braided_pair() -> (Maze, Maze, usize) {
+        let mut maze = Maze::new(Size(5, 4), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(6));
+
+        let mut braided = maze.clone();
+        let removed = braided.braid_with_rng(1.0, &mut Pcg32::new(6));
+
+        (maze, braided, removed)
+    }
+
+    #[test]
+    fn braided_openings_are_highlighted() {
+        let (maze, braided, removed) = braided_pair();
+        let diff = maze.diff(&braided).unwrap();
+
+        assert_eq!(diff.len(), removed);
+        assert!(diff.iter().all(|wall| wall.change == WallChange::Opened));
+
+        let mut display = Display::new_from_maze(&braided);
+        display.margins = Margins::none();
+        display.draw_maze(&braided).unwrap();
+        display.draw_diff(&diff);
+
+        // Braiding opened two walls, shown as light shade in the braided maze
+        let expected = [
+            "███████████",
+            "█ ░       █",
+            "█   █████ █",
+            "█   █   █ █",
+            "█████   █ █",
+            "█     ░ █ █",
+            "█ ███████ █",
+            "█         █",
+            "███████████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn going_back_closes_the_same_walls() {
+        let (maze, braided, _) = braided_pair();
+
+        let forward = maze.diff(&braided).unwrap();
+        let back = braided.diff(&maze).unwrap();
+
+        assert_eq!(back.len(), forward.len());
+
+        for (a, b) in forward.iter().zip(&back) {
+            assert_eq!((a.pos, a.direction, b.change), (b.pos, b.direction, WallChange::Closed));
+        }
+
+        let mut display = Display::new_from_maze(&maze);
+        display.draw_maze(&maze).unwrap();
+        display.draw_diff(&back);
+
+        assert_eq!(display.render().matches(CLOSED_CHAR).count(), back.len());
+    }
+
+    #[test]
+    fn sizes_have_to_match() {
+        assert!(Maze::new(Size(5, 4), true).diff(&Maze::new(Size(4, 5), true)).is_err());
+    }
+}
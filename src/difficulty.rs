@@ -0,0 +1,199 @@
+use rand::RngCore;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::endpoints::{EndpointPolicy, Endpoints};
+use crate::generator::GeneratorKind;
+use crate::Maze;
+
+const MAX_ATTEMPTS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Evil,
+}
+impl Difficulty {
+    pub fn get_preset(&self) -> &'static DifficultyPreset {
+        PRESETS.iter()
+            .find(|preset| preset.difficulty == *self)
+            .unwrap()
+    }
+}
+
+/// Loops added next to the solution once a maze is accepted, see `Maze::braid_near_path`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearPathBraid {
+    pub radius: usize,
+    pub fraction: f64,
+}
+
+/// A maze matches a preset when its `endpoint_difficulty_score` between the preset's
+/// endpoints lies in `min_score..max_score`. The bands are tuned on 31x31 mazes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyPreset {
+    pub difficulty: Difficulty,
+    pub min_score: f64,
+    pub max_score: f64,
+    pub generator: GeneratorKind,
+    pub endpoints: EndpointPolicy,
+    pub braid: Option<NearPathBraid>,
+}
+impl DifficultyPreset {
+    pub fn contains(&self, score: f64) -> bool {
+        score >= self.min_score && score < self.max_score
+    }
+
+    fn distance(&self, score: f64) -> f64 {
+        (self.min_score - score).max(score - self.max_score).max(0.0)
+    }
+}
+
+pub const PRESETS: [DifficultyPreset; 4] = [
+    DifficultyPreset {
+        difficulty: Difficulty::Easy,
+        min_score: 0.0,
+        max_score: 0.2,
+        generator: GeneratorKind::Kruskal,
+        endpoints: EndpointPolicy::Corners,
+        braid: None,
+    },
+    DifficultyPreset {
+        difficulty: Difficulty::Medium,
+        min_score: 0.2,
+        max_score: 0.35,
+        generator: GeneratorKind::Backtracker,
+        endpoints: EndpointPolicy::Corners,
+        braid: None,
+    },
+    DifficultyPreset {
+        difficulty: Difficulty::Hard,
+        min_score: 0.35,
+        max_score: 0.5,
+        generator: GeneratorKind::Backtracker,
+        endpoints: EndpointPolicy::Hardest,
+        braid: None,
+    },
+    DifficultyPreset {
+        difficulty: Difficulty::Evil,
+        min_score: 0.5,
+        max_score: f64::INFINITY,
+        generator: GeneratorKind::Backtracker,
+        endpoints: EndpointPolicy::Hardest,
+        braid: Some(NearPathBraid {
+            radius: 2,
+            fraction: 0.5,
+        }),
+    },
+];
+
+impl Maze {
+    /// Share of all cells that lie on the shortest corner to corner solution.
+    pub fn difficulty_score(&self) -> f64 {
+        self.endpoint_difficulty_score(&Endpoints::corners(self.size))
+    }
+
+    /// Share of all cells that lie on the shortest solution between `endpoints`, 0 if they
+    /// aren't connected.
+    pub fn endpoint_difficulty_score(&self, endpoints: &Endpoints) -> f64 {
+        self.solve_endpoints(endpoints)
+            .map_or(0.0, |path| path.len() as f64 / self.tiles.len() as f64)
+    }
+
+    /// Regenerates with the preset's generator until the score of the finished maze, braid
+    /// included, falls in the preset's band. Gives up after a fixed number of attempts and
+    /// keeps the closest maze, since small sizes can't reach every band. Endpoints other
+    /// than the corners get their border walls opened. Returns the endpoints and the score.
+    pub fn generate_with_difficulty<R: RngCore>(&mut self, difficulty: Difficulty, rng: &mut R) -> (Endpoints, f64) {
+        let preset = difficulty.get_preset();
+
+        let mut best: Option<(Maze, Endpoints, f64)> = None;
+
+        for _ in 0..MAX_ATTEMPTS {
+            let mut candidate = Maze::new(self.size, true);
+            candidate.generate(preset.generator, rng);
+
+            let endpoints = candidate.choose_endpoints(preset.endpoints, rng);
+
+            if let Some(braid) = preset.braid {
+                if let Some(path) = candidate.solve_endpoints(&endpoints) {
+                    candidate.braid_near_path(&path, braid.radius, braid.fraction, rng);
+                }
+            }
+
+            let score = candidate.endpoint_difficulty_score(&endpoints);
+
+            if preset.contains(score) {
+                best = Some((candidate, endpoints, score));
+                break;
+            }
+
+            if best.as_ref().is_none_or(|(.., x)| preset.distance(score) < preset.distance(*x)) {
+                best = Some((candidate, endpoints, score));
+            }
+        }
+
+        let (maze, endpoints, score) = best.unwrap();
+        *self = maze;
+
+        if preset.endpoints != EndpointPolicy::Corners {
+            for (pos, direction) in [endpoints.entrance, endpoints.exit] {
+                self.open_border(pos, direction).unwrap();
+            }
+        }
+
+        (endpoints, score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Difficulty, PRESETS};
+    use crate::rng::Pcg32;
+    use crate::{Maze, Size};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_preset_lands_in_its_band() {
+        for difficulty in Difficulty::iter() {
+            let preset = difficulty.get_preset();
+            let mut maze = Maze::new(Size(31, 31), true);
+
+            let (endpoints, score) = maze.generate_with_difficulty(difficulty, &mut Pcg32::new(31));
+
+            assert!(preset.contains(score), "{} scored {}", difficulty, score);
+            assert_eq!(maze.endpoint_difficulty_score(&endpoints), score, "{}", difficulty);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_maze() {
+        for difficulty in Difficulty::iter() {
+            let mut first = Maze::new(Size(15, 15), true);
+            let mut second = Maze::new(Size(15, 15), true);
+
+            let ends = first.generate_with_difficulty(difficulty, &mut Pcg32::new(8));
+
+            assert_eq!(second.generate_with_difficulty(difficulty, &mut Pcg32::new(8)), ends, "{}", difficulty);
+            assert_eq!(first.tiles, second.tiles, "{}", difficulty);
+        }
+    }
+
+    #[test]
+    fn unconnected_ends_score_zero() {
+        let maze = Maze::new(Size(4, 4), true);
+
+        assert_eq!(maze.difficulty_score(), 0.0);
+    }
+
+    #[test]
+    fn the_bands_follow_each_other() {
+        assert_eq!(PRESETS[0].min_score, 0.0);
+
+        for pair in PRESETS.windows(2) {
+            assert_eq!(pair[0].max_score, pair[1].min_score, "{} and {}", pair[0].difficulty, pair[1].difficulty);
+        }
+    }
+}
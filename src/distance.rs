@@ -0,0 +1,684 @@
+use ndarray::Array2;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::exits::manhattan;
+use crate::{Direction, Maze, Position};
+
+/// A path together with what it costs to walk, see `Maze::solve_dijkstra`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedPath {
+    pub path: Vec<Position>,
+    pub cost: u64,
+}
+
+impl Maze {
+    /// Steps from `origin` to every cell through open walls, or `None` for cells that can't
+    /// be reached. Moves are the ones of `solve_bfs`, so a crossing is only passed straight
+    /// through and gets the distance of the nearest pass.
+    pub fn distances_from(&self, origin: Position) -> Array2<Option<u32>> {
+        self.distances_from_cells(&[origin])
+    }
+
+    /// Steps from the nearest of `origins` to every cell through open walls, or `None` for
+    /// cells that can't be reached.
+    pub fn distances_from_cells(&self, origins: &[Position]) -> Array2<Option<u32>> {
+        let mut distances = Array2::from_elem(self.size.as_array(), None);
+        // A move over crossings is longer than one step, so there is one queue per distance
+        // like in `BfsSearch`
+        let mut layers = vec![vec![]];
+
+        for origin in origins {
+            if let Some(distance) = distances.get_mut(origin.as_array()) {
+                if distance.is_none() {
+                    *distance = Some(0);
+                    layers[0].push(*origin);
+                }
+            }
+        }
+
+        let mut distance = 0;
+
+        while distance < layers.len() {
+            for pos in std::mem::take(&mut layers[distance]) {
+                if distances[pos.as_array()] != Some(distance as u32) {
+                    continue;
+                }
+
+                for (direction, neighbor) in self.get_moves(pos) {
+                    let mut between = pos.translate(direction);
+                    let mut next = distance + 1;
+
+                    while between != neighbor {
+                        if distances[between.as_array()].is_none_or(|x| (next as u32) < x) {
+                            distances[between.as_array()] = Some(next as u32);
+                        }
+
+                        between = between.translate(direction);
+                        next += 1;
+                    }
+
+                    if distances[neighbor.as_array()].is_none_or(|x| (next as u32) < x) {
+                        distances[neighbor.as_array()] = Some(next as u32);
+
+                        if layers.len() <= next {
+                            layers.resize(next + 1, vec![]);
+                        }
+
+                        layers[next].push(neighbor);
+                    }
+                }
+            }
+
+            distance += 1;
+        }
+
+        distances
+    }
+
+    /// One of the shortest paths from `from` to `to`, both included, or `None` if they
+    /// aren't connected. Crossings on the way are in the path like in `solve_bfs`.
+    pub fn shortest_path(&self, from: Position, to: Position) -> Option<Vec<Position>> {
+        let distances = self.distances_from(to);
+
+        let mut path = vec![from];
+        let mut pos = from;
+        let mut distance = (*distances.get(from.as_array())?)?;
+
+        while distance > 0 {
+            let (direction, next) = self.get_moves(pos)
+                .into_iter()
+                .find(|(_, neighbor)| {
+                    let steps = manhattan(pos, *neighbor) as u32;
+
+                    steps <= distance && distances[neighbor.as_array()] == Some(distance - steps)
+                })
+                .unwrap();
+
+            while pos != next {
+                pos = pos.translate(direction);
+                path.push(pos);
+            }
+
+            distance = distances[pos.as_array()].unwrap();
+        }
+
+        Some(path)
+    }
+
+    /// A path between two cells as far apart as any, found by flooding from `(0, 0)` and
+    /// again from the furthest cell that reached. That is only guaranteed to be the longest
+    /// path on a perfect maze, on one with loops it is a long one but may not be the
+    /// longest. Cells `(0, 0)` can't reach are left out.
+    pub fn longest_path(&self) -> Vec<Position> {
+        let from = farthest(&self.distances_from(Position::new()));
+        let to = farthest(&self.distances_from(from));
+
+        self.shortest_path(from, to).unwrap()
+    }
+
+    /// The shortest path from `(0, 0)` to the bottom right corner by breadth-first search.
+    /// Unlike `solve_maze` it is the same every time and stays shortest on mazes with loops.
+    /// Crossings are passed straight through like in `solve_maze` and count as the cells
+    /// they are. Empty if the corners aren't connected.
+    pub fn solve_bfs(&self) -> Vec<Position> {
+        self.solve_bfs_between(Position::new(), self.size.get_max_pos(), &mut 0)
+    }
+
+    // `solve_bfs` between any two cells in the maze, adding the steps it took to `expanded`
+    pub(crate) fn solve_bfs_between(&self, start: Position, goal: Position, expanded: &mut usize) -> Vec<Position> {
+        let mut search = BfsSearch::new(self, start, goal);
+
+        while search.step(self).is_some() {
+            *expanded += 1;
+        }
+
+        search.path(self).unwrap_or_default()
+    }
+
+    /// The nearest of `goals` to `start` and the shortest path there, from a single search
+    /// like `solve_bfs` that stops at the first goal it settles. Goals at the same distance
+    /// go to the one listed first, so a goal on the start wins with a path of just the
+    /// start. Repeated goals count once and goals outside the maze are left out. `None` if
+    /// the start is outside the maze, or if no goal is left or none can be reached.
+    pub fn solve_to_nearest(&self, start: Position, goals: &[Position]) -> Option<(Position, Vec<Position>)> {
+        self.get_tile(start)?;
+
+        let goals: Vec<Position> = goals.iter()
+            .filter(|goal| self.get_tile(**goal).is_some())
+            .copied()
+            .collect();
+
+        if goals.is_empty() {
+            return None;
+        }
+
+        let mut search = BfsSearch::towards_any(self, start, &goals);
+
+        while search.step(self).is_some() {}
+
+        Some((search.reached()?, search.path(self)?))
+    }
+
+    /// The cheapest path from `(0, 0)` to the bottom right corner, where entering a cell
+    /// costs its entry in `weights` and the start is free. Ties go to the cell that comes
+    /// first in reading order, so the result doesn't change between runs. Crossings are
+    /// passed straight through and cost as much as any other cell. Fails if `weights`
+    /// doesn't have the maze's shape, and is `None` if the corners aren't connected.
+    pub fn solve_dijkstra(&self, weights: &Array2<u32>) -> Result<Option<WeightedPath>, io::ErrorKind> {
+        if weights.dim() != self.tiles.dim() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        Ok(self.solve_dijkstra_between(Position::new(), self.size.get_max_pos(), weights.clone(), &mut 0))
+    }
+
+    // `solve_dijkstra` between any two cells in the maze, adding the steps it took to
+    // `expanded`. `weights` has to have the maze's shape.
+    pub(crate) fn solve_dijkstra_between(&self, start: Position, goal: Position, weights: Array2<u32>, expanded: &mut usize) -> Option<WeightedPath> {
+        let mut search = DijkstraSearch::new(start, goal, weights);
+
+        while search.step(self).is_some() {
+            *expanded += 1;
+        }
+
+        Some(WeightedPath {
+            path: search.path(self)?,
+            cost: search.cost?,
+        })
+    }
+
+    /// A path from `start` to `goal` by greedy best first search, which always goes on from
+    /// the cell seen so far that is closest to the goal as the crow flies (Manhattan
+    /// distance), ties going to the cell first in reading order. It is quick but often
+    /// takes a long way round on twisty mazes. Each cell is gone on from at most once.
+    /// `None` if either end is outside the maze or they aren't connected.
+    pub fn solve_greedy(&self, start: Position, goal: Position) -> Option<Vec<Position>> {
+        self.get_tile(start)?;
+        self.get_tile(goal)?;
+
+        self.solve_greedy_between(start, goal, &mut 0)
+    }
+
+    // `solve_greedy` adding the steps it took to `expanded`, with both ends in the maze
+    pub(crate) fn solve_greedy_between(&self, start: Position, goal: Position, expanded: &mut usize) -> Option<Vec<Position>> {
+        let mut search = GreedySearch::new(self, start, goal);
+
+        while search.step(self).is_some() {
+            *expanded += 1;
+        }
+
+        search.path(self)
+    }
+
+    // Every cell one move away from `pos` through an open wall with the direction of the
+    // move, skipping over crossings
+    pub(crate) fn get_moves(&self, pos: Position) -> Vec<(Direction, Position)> {
+        let tile = self.get_tile(pos).unwrap();
+
+        Direction::iter()
+            .filter(|direction| !tile.get_side(*direction))
+            .filter_map(|direction| Some((direction, self.beyond_crossings(pos, direction)?)))
+            .collect()
+    }
+
+    // Follows the direction each cell was entered in back from `goal` to a cell without one
+    fn walk_back(&self, parents: &Array2<Option<Direction>>, goal: Position) -> Vec<Position> {
+        let mut path = vec![goal];
+        let mut pos = goal;
+
+        while let Some(direction) = parents[pos.as_array()] {
+            // Walks back over any crossings to the cell the move was made from
+            loop {
+                pos = self.get_neighbor(pos, direction.get_opposite()).unwrap();
+                path.push(pos);
+
+                if !self.is_crossing(pos) {
+                    break;
+                }
+            }
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+// Where `Maze::solve_bfs` is between steps, see `steps::SolveSteps`
+pub(crate) struct BfsSearch {
+    // The search ends at whichever of these it reaches first
+    goals: Vec<Position>,
+    // The direction each cell was entered in on the way there
+    parents: Array2<Option<Direction>>,
+    distances: Array2<Option<usize>>,
+    // A move over crossings is longer than one step, so there is one queue per distance
+    // rather than a single one, and a cell reached again sooner is queued again
+    layers: Vec<Vec<Position>>,
+    distance: usize,
+    // How far into the layer at `distance` the search is
+    index: usize,
+    // Cells the search treats as walled in
+    blocked: Array2<bool>,
+}
+impl BfsSearch {
+    pub(crate) fn new(maze: &Maze, start: Position, goal: Position) -> Self {
+        Self::towards_any(maze, start, &[goal])
+    }
+
+    // A search for the nearest of `goals`, which all have to be in the maze
+    pub(crate) fn towards_any(maze: &Maze, start: Position, goals: &[Position]) -> Self {
+        let mut distances = Array2::from_elem(maze.size.as_array(), None);
+        distances[start.as_array()] = Some(0);
+
+        Self {
+            goals: goals.to_vec(),
+            parents: Array2::from_elem(maze.size.as_array(), None),
+            distances,
+            layers: vec![vec![start]],
+            distance: 0,
+            index: 0,
+            blocked: Array2::from_elem(maze.size.as_array(), false),
+        }
+    }
+
+    // Keeps the search off `blocked`, ignoring cells outside the maze
+    pub(crate) fn avoiding(mut self, blocked: &[Position]) -> Self {
+        for pos in blocked {
+            if let Some(cell) = self.blocked.get_mut(pos.as_array()) {
+                *cell = true;
+            }
+        }
+
+        self
+    }
+
+    // Takes the next cell off the queue and reaches out from it. Returns that cell and the
+    // cells it reached for the first time or sooner, `None` once the nearest goal's distance
+    // is settled or the queue has run dry.
+    pub(crate) fn step(&mut self, maze: &Maze) -> Option<(Position, Vec<Position>)> {
+        loop {
+            if self.distance >= self.layers.len() || self.reached().is_some_and(|goal| self.distances[goal.as_array()].unwrap() <= self.distance) {
+                return None;
+            }
+
+            let Some(&pos) = self.layers[self.distance].get(self.index) else {
+                self.layers[self.distance] = vec![];
+                self.distance += 1;
+                self.index = 0;
+
+                continue;
+            };
+
+            self.index += 1;
+
+            if self.distances[pos.as_array()] != Some(self.distance) {
+                continue;
+            }
+
+            let mut entered = vec![];
+
+            for (direction, neighbor) in maze.get_moves(pos) {
+                let mut cells = vec![];
+                let mut between = pos;
+
+                while between != neighbor {
+                    between = between.translate(direction);
+                    cells.push(between);
+                }
+
+                if cells.iter().any(|cell| self.blocked[cell.as_array()]) {
+                    continue;
+                }
+
+                let next = self.distance + manhattan(pos, neighbor);
+
+                if self.distances[neighbor.as_array()].is_none_or(|x| next < x) {
+                    self.distances[neighbor.as_array()] = Some(next);
+                    self.parents[neighbor.as_array()] = Some(direction);
+
+                    if self.layers.len() <= next {
+                        self.layers.resize(next + 1, vec![]);
+                    }
+
+                    self.layers[next].push(neighbor);
+                    entered.extend(cells);
+                }
+            }
+
+            return Some((pos, entered));
+        }
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        let Some(layer) = self.layers.get(self.distance) else {
+            return vec![];
+        };
+
+        layer[self.index..].iter()
+            .chain(self.layers[self.distance + 1..].iter().flatten())
+            .copied()
+            .collect()
+    }
+
+    pub(crate) fn path(&self, maze: &Maze) -> Option<Vec<Position>> {
+        Some(maze.walk_back(&self.parents, self.reached()?))
+    }
+
+    // The goal nearest to the start of those reached so far, the first one on a tie
+    pub(crate) fn reached(&self) -> Option<Position> {
+        self.goals.iter()
+            .filter(|goal| self.distances[goal.as_array()].is_some())
+            .min_by_key(|goal| self.distances[goal.as_array()])
+            .copied()
+    }
+}
+
+// Where `Maze::solve_dijkstra` is between steps, see `steps::SolveSteps`
+pub(crate) struct DijkstraSearch {
+    start: Position,
+    goal: Position,
+    weights: Array2<u32>,
+    costs: Array2<Option<u64>>,
+    // The direction each cell was entered in on the cheapest way there
+    parents: Array2<Option<Direction>>,
+    // Cells by cost and then reading order
+    queue: BinaryHeap<Reverse<(u64, usize, usize)>>,
+    // What the goal cost once it came off the queue
+    cost: Option<u64>,
+}
+impl DijkstraSearch {
+    pub(crate) fn new(start: Position, goal: Position, weights: Array2<u32>) -> Self {
+        let mut costs = Array2::from_elem(weights.dim(), None);
+        costs[start.as_array()] = Some(0);
+
+        Self {
+            start,
+            goal,
+            parents: Array2::from_elem(weights.dim(), None),
+            weights,
+            costs,
+            queue: BinaryHeap::from([Reverse((0, start.1, start.0))]),
+            cost: None,
+        }
+    }
+
+    // Goes on from the cheapest queued cell. Returns that cell and the cells that got
+    // cheaper, with the crossings on the way there, `None` once the goal comes off the
+    // queue or the queue is empty.
+    pub(crate) fn step(&mut self, maze: &Maze) -> Option<(Position, Vec<Position>)> {
+        if self.cost.is_some() {
+            return None;
+        }
+
+        loop {
+            let Reverse((cost, y, x)) = self.queue.pop()?;
+            let pos = Position(x, y);
+
+            // A cell is queued again whenever it gets cheaper, and only the cheapest entry counts
+            if self.costs[pos.as_array()] != Some(cost) {
+                continue;
+            }
+
+            if pos == self.goal {
+                self.cost = Some(cost);
+                return None;
+            }
+
+            let mut entered = vec![];
+
+            for (direction, neighbor) in maze.get_moves(pos) {
+                let mut next_cost = cost + self.weights[neighbor.as_array()] as u64;
+                let mut cells = vec![];
+                let mut between = pos.translate(direction);
+
+                while between != neighbor {
+                    next_cost += self.weights[between.as_array()] as u64;
+                    cells.push(between);
+                    between = between.translate(direction);
+                }
+
+                if neighbor != self.start && self.costs[neighbor.as_array()].is_none_or(|x| next_cost < x) {
+                    self.costs[neighbor.as_array()] = Some(next_cost);
+                    self.parents[neighbor.as_array()] = Some(direction);
+                    self.queue.push(Reverse((next_cost, neighbor.1, neighbor.0)));
+
+                    cells.push(neighbor);
+                    entered.extend(cells);
+                }
+            }
+
+            return Some((pos, entered));
+        }
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        self.queue.iter()
+            .filter(|Reverse((cost, y, x))| self.costs[[*x, *y]] == Some(*cost))
+            .map(|Reverse((_, y, x))| Position(*x, *y))
+            .collect()
+    }
+
+    pub(crate) fn path(&self, maze: &Maze) -> Option<Vec<Position>> {
+        self.cost.map(|_| maze.walk_back(&self.parents, self.goal))
+    }
+}
+
+// Where `Maze::solve_greedy` is between steps, see `steps::SolveSteps`
+pub(crate) struct GreedySearch {
+    goal: Position,
+    seen: Array2<bool>,
+    // The direction each cell was entered in on the way there
+    parents: Array2<Option<Direction>>,
+    // Cells by distance to the goal and then reading order
+    queue: BinaryHeap<Reverse<(usize, usize, usize)>>,
+    reached: bool,
+}
+impl GreedySearch {
+    pub(crate) fn new(maze: &Maze, start: Position, goal: Position) -> Self {
+        let mut seen = Array2::from_elem(maze.size.as_array(), false);
+        seen[start.as_array()] = true;
+
+        Self {
+            goal,
+            seen,
+            parents: Array2::from_elem(maze.size.as_array(), None),
+            queue: BinaryHeap::from([Reverse((manhattan(start, goal), start.1, start.0))]),
+            reached: false,
+        }
+    }
+
+    // Goes on from the queued cell closest to the goal. Returns that cell and the cells it
+    // reached for the first time, `None` once the goal comes off the queue or the queue is
+    // empty.
+    pub(crate) fn step(&mut self, maze: &Maze) -> Option<(Position, Vec<Position>)> {
+        if self.reached {
+            return None;
+        }
+
+        let Reverse((_, y, x)) = self.queue.pop()?;
+        let pos = Position(x, y);
+
+        if pos == self.goal {
+            self.reached = true;
+            return None;
+        }
+
+        let mut entered = vec![];
+
+        for (direction, neighbor) in maze.get_moves(pos) {
+            if self.seen[neighbor.as_array()] {
+                continue;
+            }
+
+            let mut between = pos;
+
+            while between != neighbor {
+                between = between.translate(direction);
+                entered.push(between);
+            }
+
+            self.seen[neighbor.as_array()] = true;
+            self.parents[neighbor.as_array()] = Some(direction);
+            self.queue.push(Reverse((manhattan(neighbor, self.goal), neighbor.1, neighbor.0)));
+        }
+
+        Some((pos, entered))
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        self.queue.iter().map(|Reverse((_, y, x))| Position(*x, *y)).collect()
+    }
+
+    pub(crate) fn path(&self, maze: &Maze) -> Option<Vec<Position>> {
+        self.reached.then(|| maze.walk_back(&self.parents, self.goal))
+    }
+}
+
+// The cell with the highest distance, unreachable ones counting as the lowest
+fn farthest(distances: &Array2<Option<u32>>) -> Position {
+    let ((x, y), _) = distances.indexed_iter()
+        .max_by_key(|(_, distance)| **distance)
+        .unwrap();
+
+    Position(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Direction, Display, Maze, Position, Size, POINT_CHAR};
+
+    // A 3x3 ring with a short way along the top and right and a longer one around the left,
+    // the bottom and through the middle
+    fn two_ways() -> Maze {
+        let mut maze = Maze::new(Size(3, 3), true);
+
+        for (pos, direction) in [
+            (Position(0, 0), Direction::East),
+            (Position(1, 0), Direction::East),
+            (Position(2, 0), Direction::South),
+            (Position(2, 1), Direction::South),
+            (Position(0, 0), Direction::South),
+            (Position(0, 1), Direction::South),
+            (Position(0, 2), Direction::East),
+            (Position(1, 2), Direction::North),
+            (Position(1, 1), Direction::East),
+        ] {
+            maze.open_wall(pos, direction).unwrap();
+        }
+
+        maze
+    }
+
+    #[test]
+    fn the_short_way_wins() {
+        let maze = two_ways();
+
+        assert_eq!(maze.solve_bfs(), vec![Position(0, 0), Position(1, 0), Position(2, 0), Position(2, 1), Position(2, 2)]);
+        assert_eq!(maze.solve_bfs_between(Position(2, 2), Position(0, 0), &mut 0).len(), 5);
+    }
+
+    #[test]
+    fn braided_paths_are_shortest_and_drawable() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(13, 9), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+            maze.braid_with_rng(0.5, &mut Pcg32::new(seed));
+
+            let path = maze.solve_bfs();
+            let distance = maze.distances_from(Position(0, 0))[[12, 8]].unwrap();
+
+            assert_eq!(path.len() as u32 - 1, distance, "seed {seed}");
+            assert!(path.len() <= maze.solve_maze_with_rng(&mut Pcg32::new(seed)).len(), "seed {seed}");
+            assert!(path.windows(2).all(|step| step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1) == 1));
+
+            let mut display = Display::new_from_maze(&maze);
+            display.draw_maze(&maze).unwrap();
+            assert!(display.draw_path(path.into_iter().map(Maze::to_display_pos).collect(), POINT_CHAR).is_ok(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn the_far_corner_is_as_far_as_the_path() {
+        for (size, seed) in [(Size(13, 9), 1), (Size(1, 9), 2), (Size(20, 20), 3), (Size(1, 1), 4)] {
+            let mut maze = Maze::new(size, true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+            let distances = maze.distances_from(Position(0, 0));
+
+            assert_eq!(distances[[0, 0]], Some(0));
+            assert_eq!(distances[size.get_max_pos().as_array()], Some(maze.solve_bfs().len() as u32 - 1), "{size:?}");
+
+            // Every open wall joins cells one step apart
+            for ((x, y), distance) in distances.indexed_iter() {
+                for (_, next) in maze.get_moves(Position(x, y)) {
+                    assert_eq!(distance.unwrap().abs_diff(distances[next.as_array()].unwrap()), 1, "{size:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cut_off_cells_have_no_distance() {
+        let mut maze = two_ways();
+        maze.close_wall(Position(1, 1), Direction::East).unwrap();
+        maze.close_wall(Position(1, 2), Direction::North).unwrap();
+
+        let distances = maze.distances_from(Position(0, 0));
+
+        assert_eq!(distances[[1, 1]], None);
+        assert_eq!(distances.iter().filter(|x| x.is_some()).count(), 8);
+    }
+
+    #[test]
+    fn walled_off_corners_give_no_path() {
+        assert!(Maze::new(Size(3, 3), true).solve_bfs().is_empty());
+    }
+
+    #[test]
+    fn the_nearer_goal_wins() {
+        let maze = two_ways();
+
+        // (1, 1) is listed first but four steps away round the left, (2, 1) only three
+        let (goal, path) = maze.solve_to_nearest(Position(0, 0), &[Position(1, 1), Position(2, 1)]).unwrap();
+
+        assert_eq!(goal, Position(2, 1));
+        assert_eq!(path, vec![Position(0, 0), Position(1, 0), Position(2, 0), Position(2, 1)]);
+
+        // Two steps each way, so the one listed first
+        assert_eq!(maze.solve_to_nearest(Position(0, 0), &[Position(0, 2), Position(2, 0)]).unwrap().0, Position(0, 2));
+        assert_eq!(maze.solve_to_nearest(Position(0, 0), &[Position(2, 0), Position(0, 2)]).unwrap().0, Position(2, 0));
+    }
+
+    #[test]
+    fn repeated_goals_count_once() {
+        let maze = two_ways();
+
+        assert_eq!(
+            maze.solve_to_nearest(Position(0, 0), &[Position(1, 1), Position(1, 1), Position(1, 1)]),
+            maze.solve_to_nearest(Position(0, 0), &[Position(1, 1)]),
+        );
+        assert_eq!(maze.solve_to_nearest(Position(0, 0), &[Position(1, 1), Position(1, 1)]).unwrap().1.len(), 5);
+    }
+
+    #[test]
+    fn a_goal_on_the_start_is_reached_at_once() {
+        let maze = two_ways();
+
+        assert_eq!(
+            maze.solve_to_nearest(Position(1, 1), &[Position(2, 2), Position(1, 1)]),
+            Some((Position(1, 1), vec![Position(1, 1)])),
+        );
+    }
+
+    #[test]
+    fn no_goals_give_nothing() {
+        let maze = two_ways();
+
+        assert_eq!(maze.solve_to_nearest(Position(0, 0), &[]), None);
+        assert_eq!(maze.solve_to_nearest(Position(0, 0), &[Position(3, 0)]), None);
+        assert_eq!(Maze::new(Size(3, 3), true).solve_to_nearest(Position(0, 0), &[Position(2, 2)]), None);
+    }
+}
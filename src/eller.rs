@@ -0,0 +1,185 @@
+use rand::RngCore;
+use std::collections::BTreeMap;
+
+use crate::rng::{chance, random_index};
+use crate::{Direction, Maze, Position};
+
+/// The walls one row of Eller's algorithm opens, indexed by column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EllerRow {
+    /// Whether the wall to the east of each cell is open. The last entry is always false.
+    pub east: Vec<bool>,
+    /// Whether the wall below each cell is open. All false on the last row.
+    pub south: Vec<bool>,
+}
+
+/// Carves one row given the set label of each of its cells and returns the labels of the
+/// row below. Cells in different sets are joined at random, or always on the `last` row so
+/// the whole maze ends up connected, and every set then continues down through at least
+/// one of its cells. Cells nothing reaches from above get fresh labels from `next_label`.
+///
+/// Only the labels are carried from one row to the next, so a caller can write rows out as
+/// they are made instead of keeping the whole maze.
+pub fn eller_row<R: RngCore>(sets: &[usize], next_label: &mut usize, last: bool, rng: &mut R) -> (EllerRow, Vec<usize>) {
+    let mut sets = sets.to_vec();
+    let mut row = EllerRow {
+        east: vec![false; sets.len()],
+        south: vec![false; sets.len()],
+    };
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+
+    for (x, label) in sets.iter().enumerate() {
+        members.entry(*label).or_default().push(x);
+    }
+
+    for x in 1..sets.len() {
+        let (left, right) = (sets[x - 1], sets[x]);
+
+        if left == right || !(last || chance(rng, 0.5)) {
+            continue;
+        }
+
+        row.east[x - 1] = true;
+
+        // Relabels the smaller set so joining stays cheap on wide rows
+        let (keep, merge) = match members[&left].len() >= members[&right].len() {
+            true => (left, right),
+            false => (right, left),
+        };
+
+        let moved = members.remove(&merge).unwrap();
+
+        for cell in &moved {
+            sets[*cell] = keep;
+        }
+
+        members.get_mut(&keep).unwrap().extend(moved);
+    }
+
+    if last {
+        return (row, sets);
+    }
+
+    let mut next = vec![usize::MAX; sets.len()];
+
+    for (label, cells) in &members {
+        let mut down: Vec<usize> = cells.iter()
+            .copied()
+            .filter(|_| chance(rng, 0.5))
+            .collect();
+
+        if down.is_empty() {
+            down.push(cells[random_index(rng, cells.len())]);
+        }
+
+        for x in down {
+            row.south[x] = true;
+            next[x] = *label;
+        }
+    }
+
+    for label in next.iter_mut().filter(|label| **label == usize::MAX) {
+        *label = *next_label;
+        *next_label += 1;
+    }
+
+    (row, next)
+}
+
+impl Maze {
+    pub fn generate_eller(&mut self) {
+        self.generate_eller_with_rng(&mut rand::rng());
+    }
+
+    /// Generates a perfect maze one row at a time with `eller_row`, top to bottom.
+    pub fn generate_eller_with_rng<R: RngCore>(&mut self, rng: &mut R) {
+        let mut sets: Vec<usize> = (0..self.size.0).collect();
+        let mut next_label = self.size.0;
+
+        for y in 0..self.size.1 {
+            let (row, next) = eller_row(&sets, &mut next_label, y + 1 == self.size.1, rng);
+
+            for x in 0..self.size.0 {
+                if row.east[x] {
+                    self.set_wall(Position(x, y), Direction::East, false).unwrap();
+                }
+
+                if row.south[x] {
+                    self.set_wall(Position(x, y), Direction::South, false).unwrap();
+                }
+            }
+
+            sets = next;
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::eller_row;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+
+    #[test]
+    fn the_last_row_joins_every_set_once() {
+        let mut rng = Pcg32::new(1);
+        let mut next_label = 10;
+
+        let (row, sets) = eller_row(&[0, 0, 1, 2, 2, 3], &mut next_label, true, &mut rng);
+
+        assert_eq!(row.east, vec![false, true, true, false, true, false]);
+        assert_eq!(row.south, vec![false; 6]);
+        assert!(sets.iter().all(|label| *label == sets[0]));
+        assert_eq!(next_label, 10);
+
+        // The two ends are already joined through the middle once it is merged with one of
+        // them, so opening the second wall would make a loop
+        let (row, _) = eller_row(&[4, 7, 4], &mut next_label, true, &mut rng);
+        assert_eq!(row.east, vec![true, false, false]);
+    }
+
+    #[test]
+    fn every_set_continues_down() {
+        for seed in 0..20 {
+            let mut next_label = 6;
+            let (row, next) = eller_row(&[0, 0, 1, 2, 2, 3], &mut next_label, false, &mut Pcg32::new(seed));
+
+            // Each opening east merges two of the four sets, and what is left all goes on
+            let mut continued: Vec<usize> = next.iter().copied().filter(|label| *label < 6).collect();
+            continued.sort();
+            continued.dedup();
+
+            assert_eq!(continued.len(), 4 - row.east.iter().filter(|open| **open).count(), "seed {seed}");
+
+            for (x, label) in next.iter().enumerate() {
+                // Cells not reached from above start sets of their own
+                assert_eq!(row.south[x], *label < 6, "seed {seed}");
+            }
+
+            assert_eq!(next_label, 6 + row.south.iter().filter(|down| !**down).count(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn every_size_is_perfect() {
+        for size in [Size(1, 1), Size(1, 6), Size(6, 1), Size(2, 2), Size(11, 7)] {
+            for seed in 0..5 {
+                let mut maze = Maze::new(size, true);
+                maze.generate_eller_with_rng(&mut Pcg32::new(seed));
+
+                let cells = size.0 * size.1;
+                let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+                let open: usize = maze.tiles.iter()
+                    .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+                    .sum();
+
+                assert_eq!((reached, open / 2), (cells, cells - 1), "{size:?} seed {seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use rand::RngCore;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::exits::{manhattan, ENTRANCE};
+use crate::rng::{chance, random_index};
+use crate::{Direction, Maze, Position, Size};
+
+/// How `Maze::place_endpoints` picks the border cells the solution runs between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum EndpointPolicy {
+    /// The top left and bottom right corners, where the solution has always been.
+    Corners,
+    /// Random cells on two opposite sides of the border.
+    OppositeEdges,
+    /// Random cells anywhere on the border.
+    RandomBorder,
+    /// Random cells on the same side of the border.
+    SameEdge,
+    /// The two border cells with the longest path between them.
+    Hardest,
+}
+
+/// The entrance and the exit as a border cell and its side facing out of the maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoints {
+    pub entrance: (Position, Direction),
+    pub exit: (Position, Direction),
+}
+impl Endpoints {
+    /// The ends `Display::draw_solution` marks when the border is closed.
+    pub fn corners(size: Size) -> Self {
+        Self {
+            entrance: ENTRANCE,
+            exit: (size.get_max_pos(), Direction::South),
+        }
+    }
+}
+
+impl Maze {
+    /// Picks an entrance and an exit by `policy` without touching the maze. The random
+    /// policies keep the two cells at least half a side (`SameEdge`) or half the width plus
+    /// height apart, and only get closer on grids too small for that.
+    pub fn choose_endpoints<R: RngCore>(&self, policy: EndpointPolicy, rng: &mut R) -> Endpoints {
+        let sides = self.get_border_sides();
+        let on_edge = |direction: Direction| -> Vec<(Position, Direction)> {
+            sides.iter()
+                .copied()
+                .filter(|side| side.1 == direction)
+                .collect()
+        };
+
+        let spacing = (self.size.0 + self.size.1) / 2;
+
+        match policy {
+            EndpointPolicy::Corners => Endpoints::corners(self.size),
+            EndpointPolicy::OppositeEdges => {
+                let mut edge = match chance(rng, 0.5) {
+                    true => Direction::North,
+                    false => Direction::West,
+                };
+
+                if chance(rng, 0.5) {
+                    edge = edge.get_opposite();
+                }
+
+                pick_apart(&on_edge(edge), &on_edge(edge.get_opposite()), spacing, rng)
+            },
+            EndpointPolicy::RandomBorder => pick_apart(&sides, &sides, spacing, rng),
+            EndpointPolicy::SameEdge => {
+                let edges: Vec<Vec<(Position, Direction)>> = Direction::iter()
+                    .map(on_edge)
+                    .filter(|edge| edge.len() > 1)
+                    .collect();
+
+                if edges.is_empty() {
+                    return pick_apart(&sides, &sides, 0, rng);
+                }
+
+                let edge = &edges[random_index(rng, edges.len())];
+
+                pick_apart(edge, edge, edge.len() / 2, rng)
+            },
+            EndpointPolicy::Hardest => self.hardest_endpoints(),
+        }
+    }
+
+    /// Picks the entrance and exit by `policy` and opens their border walls.
+    pub fn place_endpoints<R: RngCore>(&mut self, policy: EndpointPolicy, rng: &mut R) -> Endpoints {
+        let endpoints = self.choose_endpoints(policy, rng);
+
+        for (pos, direction) in [endpoints.entrance, endpoints.exit] {
+            self.open_border(pos, direction).unwrap();
+        }
+
+        endpoints
+    }
+
+    /// The shortest path from the entrance to the exit, `None` if they aren't connected.
+    pub fn solve_endpoints(&self, endpoints: &Endpoints) -> Option<Vec<Position>> {
+        self.shortest_path(endpoints.entrance.0, endpoints.exit.0)
+    }
+
+    // One distance map per border cell, keeping the pair furthest apart. A 1x1 maze gets two
+    // sides of its only cell.
+    fn hardest_endpoints(&self) -> Endpoints {
+        let sides = self.get_border_sides();
+
+        let mut cells: Vec<Position> = vec![];
+
+        for (pos, _) in &sides {
+            if !cells.contains(pos) {
+                cells.push(*pos);
+            }
+        }
+
+        let mut best = (cells[0], cells[0], 0);
+
+        for (i, from) in cells.iter().enumerate() {
+            let distances = self.distances_from(*from);
+
+            for to in &cells[i + 1..] {
+                if let Some(distance) = distances[to.as_array()] {
+                    if distance > best.2 {
+                        best = (*from, *to, distance);
+                    }
+                }
+            }
+        }
+
+        let sides_of = |pos: Position| sides.iter().filter(move |side| side.0 == pos).copied();
+
+        let entrance = sides_of(best.0).next().unwrap();
+        let exit = sides_of(best.1)
+            .find(|side| *side != entrance)
+            .unwrap();
+
+        Endpoints { entrance, exit }
+    }
+}
+
+// Random sides from `from` and `to` at least `spacing` apart, lowering the spacing until a
+// pair fits. The two sides are always different.
+fn pick_apart<R: RngCore>(
+    from: &[(Position, Direction)],
+    to: &[(Position, Direction)],
+    spacing: usize,
+    rng: &mut R
+) -> Endpoints {
+    let mut from = from.to_vec();
+    let mut to = to.to_vec();
+
+    for sides in [&mut from, &mut to] {
+        for i in 0..sides.len() {
+            let other = i + random_index(rng, sides.len() - i);
+            sides.swap(i, other);
+        }
+    }
+
+    for spacing in (0..=spacing).rev() {
+        for entrance in &from {
+            let exit = to.iter().find(|exit| {
+                *exit != entrance && manhattan(entrance.0, exit.0) >= spacing
+            });
+
+            if let Some(exit) = exit {
+                return Endpoints {
+                    entrance: *entrance,
+                    exit: *exit,
+                };
+            }
+        }
+    }
+
+    unreachable!("every maze has at least four border sides");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EndpointPolicy, Endpoints};
+    use crate::exits::manhattan;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Size};
+
+    fn placed(size: Size, policy: EndpointPolicy, seed: u64) -> (Maze, Endpoints) {
+        let mut maze = Maze::new(size, true);
+        let mut rng = Pcg32::new(seed);
+        maze.generate_maze_with_rng(&mut rng);
+
+        let endpoints = maze.place_endpoints(policy, &mut rng);
+
+        (maze, endpoints)
+    }
+
+    #[test]
+    fn every_policy_opens_two_border_walls() {
+        for policy in [
+            EndpointPolicy::Corners,
+            EndpointPolicy::OppositeEdges,
+            EndpointPolicy::RandomBorder,
+            EndpointPolicy::SameEdge,
+            EndpointPolicy::Hardest,
+        ] {
+            for seed in 0..10 {
+                let (maze, endpoints) = placed(Size(12, 9), policy, seed);
+
+                let openings = maze.get_border_openings();
+
+                assert_eq!(openings.len(), 2, "{policy} seed {seed}");
+                assert!(openings.contains(&endpoints.entrance) && openings.contains(&endpoints.exit), "{policy} seed {seed}");
+                assert!(maze.solve_endpoints(&endpoints).is_some(), "{policy} seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn corners_keep_the_old_ends() {
+        let (_, endpoints) = placed(Size(12, 9), EndpointPolicy::Corners, 0);
+
+        assert_eq!(endpoints, Endpoints::corners(Size(12, 9)));
+    }
+
+    #[test]
+    fn random_policies_keep_their_shape_and_spacing() {
+        for seed in 0..30 {
+            let (_, opposite) = placed(Size(12, 9), EndpointPolicy::OppositeEdges, seed);
+            assert_eq!(opposite.entrance.1, opposite.exit.1.get_opposite(), "seed {seed}");
+
+            let (_, border) = placed(Size(12, 9), EndpointPolicy::RandomBorder, seed);
+            assert!(manhattan(border.entrance.0, border.exit.0) >= (12 + 9) / 2, "seed {seed}");
+
+            // Half of the shorter side at least
+            let (_, same) = placed(Size(12, 9), EndpointPolicy::SameEdge, seed);
+            assert_eq!(same.entrance.1, same.exit.1, "seed {seed}");
+            assert!(manhattan(same.entrance.0, same.exit.0) >= 9 / 2, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn hardest_ends_are_furthest_apart() {
+        for seed in 0..5 {
+            let (maze, endpoints) = placed(Size(6, 5), EndpointPolicy::Hardest, seed);
+            let length = maze.solve_endpoints(&endpoints).unwrap().len();
+
+            let sides = maze.get_border_sides();
+
+            for (from, _) in &sides {
+                for (to, _) in &sides {
+                    assert!(maze.shortest_path(*from, *to).unwrap().len() <= length, "seed {seed}");
+                }
+            }
+        }
+    }
+}
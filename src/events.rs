@@ -0,0 +1,293 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use strum_macros::EnumString;
+
+use crate::generator::GeneratorKind;
+use crate::{Direction, Maze, Position, Size};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, strum_macros::Display)]
+pub enum CarveAction {
+    Carve,
+    Backtrack,
+}
+
+/// One step of a generator: `Carve` opens the wall of `position` facing `direction`,
+/// `Backtrack` moves the generator head back to `position` without touching walls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CarveEvent {
+    pub step: usize,
+    pub position: Position,
+    pub direction: Option<Direction>,
+    pub action: CarveAction,
+}
+impl CarveEvent {
+    pub fn new(step: usize, position: Position, direction: Option<Direction>, action: CarveAction) -> Self {
+        Self {
+            step,
+            position,
+            direction,
+            action,
+        }
+    }
+}
+
+impl fmt::Display for CarveEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.direction {
+            Some(direction) => direction.to_string(),
+            None => String::from("-"),
+        };
+
+        write!(f, "{} {} {} {} {}", self.step, self.position.0, self.position.1, direction, self.action)
+    }
+}
+
+impl FromStr for CarveEvent {
+    type Err = io::ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+
+        if fields.len() != 5 {
+            return Err(io::ErrorKind::InvalidData);
+        }
+
+        let parse_usize = |field: &str| field.parse::<usize>().map_err(|_| io::ErrorKind::InvalidData);
+
+        let direction = match fields[3] {
+            "-" => None,
+            name => Some(Direction::from_str(name).map_err(|_| io::ErrorKind::InvalidData)?),
+        };
+
+        Ok(Self::new(
+            parse_usize(fields[0])?,
+            Position(parse_usize(fields[1])?, parse_usize(fields[2])?),
+            direction,
+            CarveAction::from_str(fields[4]).map_err(|_| io::ErrorKind::InvalidData)?,
+        ))
+    }
+}
+
+/// Everything needed to rebuild a maze without the RNG: the size on the first line,
+/// followed by one event per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventLog {
+    pub size: Size,
+    pub events: Vec<CarveEvent>,
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}x{}", self.size.0, self.size.1)?;
+
+        for event in &self.events {
+            writeln!(f, "{}", event)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for EventLog {
+    type Err = io::ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().filter(|line| !line.trim().is_empty());
+
+        let size = lines
+            .next()
+            .and_then(|line| line.trim().split_once("x"))
+            .ok_or(io::ErrorKind::InvalidData)?;
+
+        let size = Size(
+            size.0.parse().map_err(|_| io::ErrorKind::InvalidData)?,
+            size.1.parse().map_err(|_| io::ErrorKind::InvalidData)?,
+        );
+
+        let events = lines
+            .map(CarveEvent::from_str)
+            .collect::<Result<Vec<CarveEvent>, io::ErrorKind>>()?;
+
+        Ok(Self { size, events })
+    }
+}
+
+impl Maze {
+    pub fn generate_maze_logged(&mut self) -> EventLog {
+        self.generate_maze_logged_with_rng(&mut rand::rng())
+    }
+
+    pub fn generate_maze_logged_with_rng<R: RngCore>(&mut self, rng: &mut R) -> EventLog {
+        let mut events = vec![];
+
+        self.generate_backtracker(rng, Some(&mut events));
+
+        EventLog {
+            size: self.size,
+            events,
+        }
+    }
+
+    /// Carves a fully walled maze with `kind` like `generate`, logging it as it goes. Only
+    /// the backtracker logs its own steps, the log of any other generator is the
+    /// `to_event_log` of the finished maze.
+    pub fn generate_logged<R: RngCore>(&mut self, kind: GeneratorKind, rng: &mut R) -> EventLog {
+        match kind {
+            GeneratorKind::Backtracker => self.generate_maze_logged_with_rng(rng),
+            _ => {
+                self.generate(kind, rng);
+                self.to_event_log()
+            },
+        }
+    }
+
+    /// A log that `Maze::replay` turns back into this maze, whichever generator made it. It
+    /// walks the passages depth first from `(0, 0)`, so it shows the shape of the maze being
+    /// uncovered rather than the order the generator carved it in. Parts that can't be reached
+    /// from the start follow, and passages that close a loop are carved last.
+    pub fn to_event_log(&self) -> EventLog {
+        let mut events = vec![];
+        let mut explored = Array2::from_elem(self.size.as_array(), false);
+        let mut rebuilt = Maze::new(self.size, true);
+
+        for origin in self.tiles.indexed_iter().map(|(index, _)| Position::from_array(index.into())) {
+            if explored[origin.as_array()] {
+                continue;
+            }
+
+            explored[origin.as_array()] = true;
+            let mut stack = vec![origin];
+
+            while let Some(pos) = stack.last().copied() {
+                let next = Direction::iter().find(|direction| {
+                    !self.get_tile(pos).unwrap().get_side(*direction)
+                        && self.get_neighbor(pos, *direction).is_some_and(|x| !explored[x.as_array()])
+                });
+
+                let Some(direction) = next else {
+                    stack.pop();
+
+                    if let Some(back) = stack.last() {
+                        events.push(CarveEvent::new(events.len(), *back, None, CarveAction::Backtrack));
+                    }
+
+                    continue;
+                };
+
+                let neighbor = pos.translate(direction);
+                explored[neighbor.as_array()] = true;
+                rebuilt.set_wall(pos, direction, false).unwrap();
+
+                events.push(CarveEvent::new(events.len(), pos, Some(direction), CarveAction::Carve));
+                stack.push(neighbor);
+            }
+        }
+
+        for (index, tile) in self.tiles.indexed_iter() {
+            let pos = Position::from_array(index.into());
+
+            for direction in [Direction::East, Direction::South] {
+                let missing = !tile.get_side(direction)
+                    && self.get_neighbor(pos, direction).is_some()
+                    && rebuilt.get_tile(pos).unwrap().get_side(direction);
+
+                if missing {
+                    events.push(CarveEvent::new(events.len(), pos, Some(direction), CarveAction::Carve));
+                }
+            }
+        }
+
+        EventLog {
+            size: self.size,
+            events,
+        }
+    }
+
+    /// Rebuilds the maze described by `log` starting from a fully walled grid. Events must
+    /// be numbered in order, stay inside the grid and only carve walls that are still closed.
+    pub fn replay(log: &EventLog) -> Result<Maze, io::ErrorKind> {
+        let mut maze = Maze::new(log.size, true);
+
+        for (index, event) in log.events.iter().enumerate() {
+            if event.step != index || maze.get_tile(event.position).is_none() {
+                return Err(io::ErrorKind::InvalidInput);
+            }
+
+            if event.action == CarveAction::Backtrack {
+                continue;
+            }
+
+            let direction = event.direction.ok_or(io::ErrorKind::InvalidInput)?;
+
+            if !maze.get_tile(event.position).unwrap().get_side(direction) {
+                return Err(io::ErrorKind::InvalidInput);
+            }
+
+            maze.set_wall(event.position, direction, false)?;
+        }
+
+        if cfg!(debug_assertions) {
+            maze.assert_consistent();
+        }
+
+        Ok(maze)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CarveAction, CarveEvent, EventLog};
+    use crate::generator::GeneratorKind;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+    use std::io;
+
+    #[test]
+    fn replaying_a_log_rebuilds_the_maze() {
+        let mut maze = Maze::new(Size(9, 7), true);
+        let log = maze.generate_maze_logged_with_rng(&mut Pcg32::new(11));
+
+        assert_eq!(Maze::replay(&log), Ok(maze.clone()));
+
+        // The text form reads back into the same log
+        let read: EventLog = log.to_string().parse().unwrap();
+        assert_eq!(read, log);
+        assert_eq!(Maze::replay(&read), Ok(maze));
+    }
+
+    #[test]
+    fn finished_mazes_turn_into_logs_of_their_own() {
+        for kind in [GeneratorKind::Kruskal, GeneratorKind::Wilson, GeneratorKind::Eller, GeneratorKind::Cave] {
+            let mut maze = Maze::new(Size(9, 7), true);
+            let log = maze.generate_logged(kind, &mut Pcg32::new(11));
+
+            assert_eq!(Maze::replay(&log), Ok(maze), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn illegal_events_are_refused() {
+        let carve = |step, pos, direction| CarveEvent::new(step, pos, Some(direction), CarveAction::Carve);
+        let size = Size(3, 3);
+
+        for events in [
+            // Out of order
+            vec![carve(1, Position(0, 0), Direction::East)],
+            // Outside the grid, or through its border
+            vec![carve(0, Position(5, 0), Direction::East)],
+            vec![carve(0, Position(0, 0), Direction::North)],
+            // The same wall twice
+            vec![carve(0, Position(0, 0), Direction::East), carve(1, Position(1, 0), Direction::West)],
+            // A carve without a direction
+            vec![CarveEvent::new(0, Position(0, 0), None, CarveAction::Carve)],
+        ] {
+            assert_eq!(Maze::replay(&EventLog { size, events: events.clone() }), Err(io::ErrorKind::InvalidInput), "{:?}", events);
+        }
+
+        assert!("3x3\n0 0 0 Up Carve".parse::<EventLog>().is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use rand::RngCore;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::rng::random_index;
+use crate::{Direction, Display, Maze, Position, EMPTY_CHAR, GOAL_MARKER_SIDES, POINT_CHAR, START_MARKER_SIDES};
+
+/// Where `place_exits` puts the entrance, matching the marker drawn by `draw_solution`.
+pub const ENTRANCE: (Position, Direction) = (Position(0, 0), Direction::North);
+
+/// The last rank `Display::draw_exit_ranks` has a label for, 'z'.
+pub const MAX_RANK_LABEL: u32 = 35;
+
+impl Maze {
+    /// Opens the outer wall of a border cell. `direction` has to point out of the maze.
+    pub fn open_border(&mut self, pos: Position, direction: Direction) -> Result<(), io::ErrorKind> {
+        if self.get_tile(pos).is_none() || self.get_neighbor(pos, direction).is_some() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        self.get_mut_tile(pos)
+            .unwrap()
+            .set_side(direction, false);
+
+        Ok(())
+    }
+
+    /// Every side of a cell that faces out of the maze, open or not.
+    pub fn get_border_sides(&self) -> Vec<(Position, Direction)> {
+        self.tiles
+            .indexed_iter()
+            .flat_map(|((x, y), _)| Direction::iter().map(move |direction| (Position(x, y), direction)))
+            .filter(|(pos, direction)| self.get_neighbor(*pos, *direction).is_none())
+            .collect()
+    }
+
+    pub fn get_border_openings(&self) -> Vec<(Position, Direction)> {
+        self.get_border_sides()
+            .into_iter()
+            .filter(|(pos, direction)| !self.get_tile(*pos).unwrap().get_side(*direction))
+            .collect()
+    }
+
+    /// Opens the entrance at `ENTRANCE` and `count` exits elsewhere on the border. Exits are
+    /// kept at least a Manhattan distance apart from each other and the entrance; the spacing
+    /// starts at an even share of the perimeter and shrinks until all of them fit.
+    pub fn place_exits<R: RngCore>(&mut self, count: usize, rng: &mut R) -> Result<Vec<Position>, io::ErrorKind> {
+        let mut sides = self.get_border_sides();
+
+        for i in 0..sides.len() {
+            let pick = i + random_index(rng, sides.len() - i);
+            sides.swap(i, pick);
+        }
+
+        let mut spacing = (sides.len() / (2 * (count + 1))).max(1);
+
+        let exits = loop {
+            let mut taken = vec![ENTRANCE];
+
+            for side in &sides {
+                if taken.len() > count {
+                    break;
+                }
+
+                if taken.iter().all(|(pos, _)| manhattan(*pos, side.0) >= spacing) {
+                    taken.push(*side);
+                }
+            }
+
+            if taken.len() > count {
+                break taken;
+            }
+
+            if spacing == 1 {
+                return Err(io::ErrorKind::InvalidInput);
+            }
+
+            spacing -= 1;
+        };
+
+        for (pos, direction) in &exits {
+            self.open_border(*pos, *direction)?;
+        }
+
+        Ok(exits[1..]
+            .iter()
+            .map(|(pos, _)| *pos)
+            .collect())
+    }
+
+    /// Every border opening other than `from` with its shortest path length from `from`,
+    /// nearest first. Unreachable exits are left out.
+    pub fn rank_exits(&self, from: Position) -> Vec<(Position, u32)> {
+        let distances = self.distances_from(from);
+
+        let mut exits: Vec<Position> = vec![];
+
+        for (pos, _) in self.get_border_openings() {
+            if pos != from && !exits.contains(&pos) {
+                exits.push(pos);
+            }
+        }
+
+        let mut ranking: Vec<(Position, u32)> = exits
+            .into_iter()
+            .filter_map(|pos| Some((pos, distances[pos.as_array()]?)))
+            .collect();
+
+        ranking.sort_by_key(|(_, distance)| *distance);
+
+        ranking
+    }
+}
+
+impl Display {
+    /// Writes the rank of each exit, counting from 1, into its border opening. Ranks 1 to 9
+    /// are digits and 10 to `MAX_RANK_LABEL` the letters 'a' to 'z'. Exits ranked further
+    /// down only get a `POINT_CHAR`.
+    pub fn draw_exit_ranks(&mut self, maze: &Maze, ranking: &[(Position, u32)]) {
+        let openings = maze.get_border_openings();
+
+        for (rank, (exit, _)) in ranking.iter().enumerate() {
+            let label = match char::from_digit(rank as u32 + 1, MAX_RANK_LABEL + 1) {
+                Some(label) => label,
+                None => POINT_CHAR,
+            };
+
+            for (pos, direction) in &openings {
+                if pos == exit {
+                    self.draw_point(Maze::to_display_pos(*pos).translate(*direction), label);
+                }
+            }
+        }
+    }
+
+    /// Erases the outer wall pixel on the `side` of the cell `at`, so the maze can be
+    /// entered there from outside. Fails without erasing anything if that side of the cell
+    /// isn't on the border.
+    pub fn open_border(&mut self, at: Position, side: Direction) -> Result<(), io::ErrorKind> {
+        let pixel = self.get_border_wall(at, side).ok_or(io::ErrorKind::InvalidInput)?;
+
+        self.draw_point(pixel, EMPTY_CHAR);
+
+        Ok(())
+    }
+
+    /// Opens the border next to the `start` and `goal` cells, on the same sides
+    /// `draw_solution` puts its markers on, which then go in the openings. Fails without
+    /// erasing anything if either cell is away from the border.
+    pub fn open_ends(&mut self, start: Position, goal: Position) -> Result<(), io::ErrorKind> {
+        let side = |pos: Position, sides: [Direction; 4]| sides.into_iter()
+            .find(|side| self.get_border_wall(pos, *side).is_some())
+            .ok_or(io::ErrorKind::InvalidInput);
+
+        let (start_side, goal_side) = (side(start, START_MARKER_SIDES)?, side(goal, GOAL_MARKER_SIDES)?);
+
+        self.open_border(start, start_side)?;
+        self.open_border(goal, goal_side)
+    }
+
+    // The pixel of the outer wall on the `side` of the cell `at`, if there is one
+    fn get_border_wall(&self, at: Position, side: Direction) -> Option<Position> {
+        let max = self.size.get_max_pos();
+        let display_pos = Maze::to_display_pos(at);
+
+        if display_pos.0 >= max.0 || display_pos.1 >= max.1 {
+            return None;
+        }
+
+        Some(display_pos.translate(side))
+            .filter(|pixel| pixel.0 == 0 || pixel.1 == 0 || pixel.0 == max.0 || pixel.1 == max.1)
+    }
+}
+
+pub(crate) fn manhattan(a: Position, b: Position) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ENTRANCE;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Display, Maze, Position, Size, POINT_CHAR};
+
+    #[test]
+    fn ranking_matches_separate_searches() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(12, 9), true);
+            let mut rng = Pcg32::new(seed);
+            maze.generate_maze_with_rng(&mut rng);
+
+            let exits = maze.place_exits(4, &mut rng).unwrap();
+            let ranking = maze.rank_exits(ENTRANCE.0);
+
+            assert_eq!(ranking.len(), 4, "seed {seed}");
+
+            for (exit, distance) in &ranking {
+                assert!(exits.contains(exit), "seed {seed}");
+
+                let path = maze.solve_bfs_between(ENTRANCE.0, *exit, &mut 0);
+                assert_eq!(path.len() as u32 - 1, *distance, "seed {seed} to {exit:?}");
+            }
+
+            assert!(ranking.windows(2).all(|pair| pair[0].1 <= pair[1].1), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn exits_open_the_border_apart_from_each_other() {
+        let mut maze = Maze::new(Size(12, 9), true);
+        let mut rng = Pcg32::new(3);
+        maze.generate_maze_with_rng(&mut rng);
+
+        let exits = maze.place_exits(3, &mut rng).unwrap();
+
+        // The entrance and one opening per exit
+        assert_eq!(maze.get_border_openings().len(), 4);
+        assert!(exits.iter().all(|exit| *exit != ENTRANCE.0));
+
+        // Too many exits for the border to hold
+        let mut small = Maze::new(Size(2, 2), true);
+        small.generate_maze_with_rng(&mut rng);
+        assert!(small.place_exits(8, &mut rng).is_err());
+    }
+
+    #[test]
+    fn ranks_past_z_get_a_point() {
+        let mut maze = Maze::new(Size(40, 1), true);
+        let mut ranking = vec![];
+
+        for x in 0..40 {
+            maze.open_border(Position(x, 0), Direction::North).unwrap();
+            ranking.push((Position(x, 0), x as u32));
+        }
+
+        let mut display = Display::new_from_maze(&maze);
+        display.draw_maze(&maze).unwrap();
+        display.draw_exit_ranks(&maze, &ranking);
+
+        let labels: String = (0..40)
+            .map(|x| {
+                let opening = Maze::to_display_pos(Position(x, 0)).translate(Direction::North);
+                display.pixels[[opening.1, opening.0]]
+            })
+            .collect();
+
+        assert_eq!(labels, format!("123456789abcdefghijklmnopqrstuvwxyz{}", POINT_CHAR.to_string().repeat(5)));
+    }
+}
@@ -0,0 +1,190 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::io;
+
+use crate::rng::random_index;
+use crate::{Direction, Maze, Position, Size, Tile};
+
+impl Maze {
+    /// Appends `count` rows or columns on `side`, carves a maze into the new strip and joins
+    /// it to the existing maze through one opening. Existing passages are kept as they are
+    /// and waypoints and crossings move along with their cells.
+    ///
+    /// Returns the offset added to every existing position: extending North or West moves
+    /// the old cells by `count` along that axis, extending South or East moves nothing.
+    pub fn extend<R: RngCore>(&mut self, side: Direction, count: usize, rng: &mut R) -> Result<Position, io::ErrorKind> {
+        self.extend_with_openings(side, count, 1, rng)
+    }
+
+    pub fn extend_with_openings<R: RngCore>(
+        &mut self,
+        side: Direction,
+        count: usize,
+        openings: usize,
+        rng: &mut R
+    ) -> Result<Position, io::ErrorKind> {
+        let seam_length = match side {
+            Direction::North | Direction::South => self.size.0,
+            Direction::East | Direction::West => self.size.1,
+        };
+
+        if count == 0 || openings == 0 || openings > seam_length {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let (strip_size, new_size, old_offset, strip_offset) = match side {
+            Direction::North => (
+                Size(self.size.0, count),
+                Size(self.size.0, self.size.1 + count),
+                Position(0, count),
+                Position(0, 0),
+            ),
+            Direction::South => (
+                Size(self.size.0, count),
+                Size(self.size.0, self.size.1 + count),
+                Position(0, 0),
+                Position(0, self.size.1),
+            ),
+            Direction::West => (
+                Size(count, self.size.1),
+                Size(self.size.0 + count, self.size.1),
+                Position(count, 0),
+                Position(0, 0),
+            ),
+            Direction::East => (
+                Size(count, self.size.1),
+                Size(self.size.0 + count, self.size.1),
+                Position(0, 0),
+                Position(self.size.0, 0),
+            ),
+        };
+
+        let mut strip = Maze::new(strip_size, true);
+
+        if strip.tiles.len() > 1 {
+            strip.generate_backtracker(rng, None);
+        }
+
+
+        let mut tiles = Array2::from_elem(new_size.as_array(), Tile::new(true));
+
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            tiles[[x + old_offset.0, y + old_offset.1]] = *tile;
+        }
+
+        for ((x, y), tile) in strip.tiles.indexed_iter() {
+            tiles[[x + strip_offset.0, y + strip_offset.1]] = *tile;
+        }
+
+        self.size = new_size;
+        self.tiles = tiles;
+
+        for (_, pos) in &mut self.waypoints {
+            *pos = Position(pos.0 + old_offset.0, pos.1 + old_offset.1);
+        }
+
+        for (pos, _) in &mut self.crossings {
+            *pos = Position(pos.0 + old_offset.0, pos.1 + old_offset.1);
+        }
+
+
+        let mut seam: Vec<usize> = (0..seam_length).collect();
+
+        for i in 0..openings {
+            let pick = i + random_index(rng, seam.len() - i);
+            seam.swap(i, pick);
+
+            // The old cell on the seam, in the new coordinates
+            let old_cell = match side {
+                Direction::North => Position(seam[i], count),
+                Direction::South => Position(seam[i], new_size.1 - count - 1),
+                Direction::West => Position(count, seam[i]),
+                Direction::East => Position(new_size.0 - count - 1, seam[i]),
+            };
+
+            self.set_wall(old_cell, side, false)?;
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(old_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+    use std::io;
+    use strum::IntoEnumIterator;
+
+    fn maze() -> Maze {
+        let mut maze = Maze::new(Size(6, 5), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(29));
+        maze.set_waypoint("key", Position(2, 3)).unwrap();
+        maze
+    }
+
+    fn weave() -> Maze {
+        let mut maze = Maze::new(Size(6, 5), true);
+        maze.generate_weave_with_rng(0.5, &mut Pcg32::new(29));
+        maze.set_waypoint("key", Position(2, 3)).unwrap();
+        assert!(!maze.crossings.is_empty());
+        maze
+    }
+
+    #[test]
+    fn old_walls_only_move_by_the_offset() {
+        for side in Direction::iter() {
+            for (openings, old) in [(1, maze()), (3, maze()), (1, weave())] {
+                let mut grown = old.clone();
+
+                let offset = grown.extend_with_openings(side, 2, openings, &mut Pcg32::new(3)).unwrap();
+                let mut opened = 0;
+
+                for ((x, y), tile) in old.tiles.indexed_iter() {
+                    let moved = grown.tiles[[x + offset.0, y + offset.1]];
+
+                    for direction in Direction::iter() {
+                        match direction == side && tile.get_side(direction) != moved.get_side(direction) {
+                            true => opened += 1,
+                            false => assert_eq!(tile.get_side(direction), moved.get_side(direction), "{} {},{}", side, x, y),
+                        }
+                    }
+                }
+
+                assert_eq!(opened, openings, "{}", side);
+                assert_eq!(grown.waypoints, vec![("key".to_string(), Position(2 + offset.0, 3 + offset.1))]);
+
+                let crossings: Vec<_> = old.crossings.iter()
+                    .map(|(pos, axis)| (Position(pos.0 + offset.0, pos.1 + offset.1), *axis))
+                    .collect();
+                assert_eq!(grown.crossings, crossings, "{}", side);
+
+                // The strip is reachable from every old cell
+                let distances = grown.distances_from(offset);
+                assert!(distances.iter().all(Option::is_some), "{}", side);
+            }
+        }
+    }
+
+    #[test]
+    fn offsets_follow_the_side() {
+        let mut grown = maze();
+
+        assert_eq!(grown.extend(Direction::North, 2, &mut Pcg32::new(1)), Ok(Position(0, 2)));
+        assert_eq!(grown.extend(Direction::East, 3, &mut Pcg32::new(1)), Ok(Position(0, 0)));
+        assert_eq!(grown.size, Size(9, 7));
+    }
+
+    #[test]
+    fn bad_counts_are_refused() {
+        let mut grown = maze();
+
+        assert_eq!(grown.extend(Direction::West, 0, &mut Pcg32::new(1)), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(grown.extend_with_openings(Direction::South, 1, 7, &mut Pcg32::new(1)), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(grown, maze());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::Maze;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+impl Maze {
+    /// A short hex id of the walls, equal for equal mazes on every platform and version.
+    ///
+    /// It is the 64 bit FNV-1a hash of `canonical_bytes`, written as 16 lowercase hex digits.
+    pub fn fingerprint(&self) -> String {
+        let hash = self.canonical_bytes()
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME));
+
+        format!("{:016x}", hash)
+    }
+
+    /// Width and height as little endian u64s, then one bit per wall packed from the lowest
+    /// bit up: the north and west side of every cell row by row, then the south side of the
+    /// bottom row and the east side of the right column. Shared walls are only read from
+    /// the cell south or east of them, so every wall is stored exactly once. Weave mazes
+    /// go on with their crossings in reading order, each as its x and y as little endian
+    /// u64s and a byte for the axis of the passage below.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bits = vec![];
+
+        for y in 0..self.size.1 {
+            for x in 0..self.size.0 {
+                let tile = self.tiles[[x, y]];
+
+                bits.push(tile.up);
+                bits.push(tile.left);
+            }
+        }
+
+        for x in 0..self.size.0 {
+            bits.push(self.tiles[[x, self.size.1 - 1]].down);
+        }
+
+        for y in 0..self.size.1 {
+            bits.push(self.tiles[[self.size.0 - 1, y]].right);
+        }
+
+        let mut out = vec![];
+        out.extend((self.size.0 as u64).to_le_bytes());
+        out.extend((self.size.1 as u64).to_le_bytes());
+
+        for chunk in bits.chunks(8) {
+            out.push(
+                chunk.iter()
+                    .enumerate()
+                    .fold(0, |byte, (i, bit)| byte | ((*bit as u8) << i))
+            );
+        }
+
+        let mut crossings = self.crossings.clone();
+        crossings.sort_by_key(|(pos, _)| (pos.1, pos.0));
+
+        for (pos, axis) in crossings {
+            out.extend((pos.0 as u64).to_le_bytes());
+            out.extend((pos.1 as u64).to_le_bytes());
+            out.push(axis.index() as u8);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::symmetry::Symmetry;
+    use crate::test_support::reflected;
+    use crate::{Axis, Maze, Position, Size};
+
+    fn seeded(size: Size, seed: u64) -> Maze {
+        let mut maze = Maze::new(size, true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+        maze
+    }
+
+    #[test]
+    fn fingerprints_are_pinned() {
+        for (size, seed, fingerprint) in [
+            (Size(7, 5), 7, "c0d2c19a34319749"),
+            (Size(16, 16), 99, "e68a5c9b0be4113f"),
+            // Just the size and the four outer walls
+            (Size(1, 1), 0, "928d6364f9ba5fbe"),
+        ] {
+            assert_eq!(seeded(size, seed).fingerprint(), fingerprint, "{size:?} seed {seed}");
+        }
+    }
+
+    #[test]
+    fn turning_or_mirroring_changes_the_fingerprint() {
+        let maze = seeded(Size(9, 9), 5);
+
+        for symmetry in [Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Rotational] {
+            let image = reflected(&maze, symmetry);
+
+            assert_ne!(image.fingerprint(), maze.fingerprint(), "{symmetry}");
+            // Mirroring twice gives the maze back
+            assert_eq!(reflected(&image, symmetry).fingerprint(), maze.fingerprint(), "{symmetry}");
+        }
+    }
+
+    #[test]
+    fn crossings_change_the_fingerprint() {
+        let mut maze = Maze::new(Size(3, 3), false);
+        let open = maze.fingerprint();
+
+        maze.crossings.push((Position(1, 1), Axis(0)));
+        let under_east_west = maze.fingerprint();
+
+        maze.crossings[0].1 = Axis(1);
+        let under_north_south = maze.fingerprint();
+
+        assert_ne!(open, under_east_west);
+        assert_ne!(open, under_north_south);
+        assert_ne!(under_east_west, under_north_south);
+
+        // The order they were added in doesn't matter
+        let mut first = maze.clone();
+        first.crossings.push((Position(0, 1), Axis(0)));
+        let mut second = maze.clone();
+        second.crossings.insert(0, (Position(0, 1), Axis(0)));
+
+        assert_eq!(first.fingerprint(), second.fingerprint());
+    }
+}
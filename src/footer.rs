@@ -0,0 +1,146 @@
+use crate::{Display, Maze, Position, Size};
+
+/// The numbers `--stats-footer` prints under a maze, see `MazeStats::footer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeStats {
+    pub size: Size,
+    /// Name of whatever generated the maze.
+    pub algorithm: String,
+    pub seed: Option<u64>,
+    /// Cells on the drawn solution, both ends included.
+    pub solution_length: usize,
+    pub dead_ends: usize,
+}
+impl MazeStats {
+    pub fn new(maze: &Maze, algorithm: &str, seed: Option<u64>, solution: &[Position]) -> Self {
+        Self {
+            size: maze.size,
+            algorithm: algorithm.to_string(),
+            seed,
+            solution_length: solution.len(),
+            dead_ends: maze.get_dead_ends().len(),
+        }
+    }
+
+    /// How much of the maze the solution runs through, in percent.
+    pub fn solution_share(&self) -> f64 {
+        match self.size.0 * self.size.1 {
+            0 => 0.0,
+            cells => self.solution_length as f64 * 100.0 / cells as f64,
+        }
+    }
+
+    /// One line per number, leaving out the seed if there wasn't one.
+    pub fn footer(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Size: {}x{}", self.size.0, self.size.1),
+            format!("Algorithm: {}", self.algorithm),
+        ];
+
+        if let Some(seed) = self.seed {
+            lines.push(format!("Seed: {}", seed));
+        }
+
+        lines.push(format!("Solution: {} cells, {:.1}% of the maze", self.solution_length, self.solution_share()));
+        lines.push(format!("Dead ends: {}", self.dead_ends));
+
+        lines
+    }
+}
+
+impl Display {
+    /// Adds `lines` under the pixels. They're written after the last row, wrapped at spaces
+    /// to the printed width of the display, and words too long for it are cut, so the
+    /// block stays as wide as the maze. The charset and colors don't apply to them.
+    pub fn draw_footer(&mut self, lines: &[String]) {
+        self.footer.extend_from_slice(lines);
+    }
+
+    // The footer wrapped to `width` characters
+    pub(crate) fn wrap_footer(&self, width: usize) -> Vec<String> {
+        let mut out = vec![];
+
+        if width == 0 {
+            return out;
+        }
+
+        for line in &self.footer {
+            let mut current = String::new();
+
+            for word in line.split(' ').filter(|x| !x.is_empty()) {
+                let word: Vec<char> = word.chars().collect();
+
+                for piece in word.chunks(width) {
+                    let piece: String = piece.iter().collect();
+                    let length = current.chars().count();
+
+                    if length > 0 && length + 1 + piece.chars().count() > width {
+                        out.push(std::mem::take(&mut current));
+                    }
+
+                    if !current.is_empty() {
+                        current.push(' ');
+                    }
+
+                    current.push_str(&piece);
+                }
+            }
+
+            out.push(current);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MazeStats;
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Maze, Position, Size};
+
+    #[test]
+    fn the_footer_lists_the_numbers() {
+        let maze = opened(Size(2, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+        let path = [Position(0, 0), Position(1, 0), Position(1, 1)];
+
+        assert_eq!(MazeStats::new(&maze, "backtracker", Some(7), &path).footer(), [
+            "Size: 2x2",
+            "Algorithm: backtracker",
+            "Seed: 7",
+            "Solution: 3 cells, 75.0% of the maze",
+            "Dead ends: 2",
+        ]);
+
+        assert!(!MazeStats::new(&maze, "backtracker", None, &path).footer().iter().any(|line| line.starts_with("Seed")));
+    }
+
+    #[test]
+    fn lines_wrap_at_spaces_and_long_words_are_cut() {
+        let mut display = Display::new(Size(7, 1));
+        display.draw_footer(&["a bb ccc".to_string(), "abcdefghij".to_string()]);
+
+        assert_eq!(display.wrap_footer(5), ["a bb", "ccc", "abcde", "fghij"]);
+        assert!(display.wrap_footer(0).is_empty());
+    }
+
+    #[test]
+    fn the_footer_keeps_to_the_width_of_the_maze() {
+        let maze = Maze::new(Size(3, 1), true);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_footer(&["Dead ends: 3".to_string()]);
+
+        let expected = [
+            "███████",
+            "█ █ █ █",
+            "███████",
+            "Dead",
+            "ends: 3",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
@@ -0,0 +1,243 @@
+use std::io;
+use std::path::Path;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::html::HtmlOptions;
+use crate::solution::Solution;
+use crate::svg::SvgOptions;
+use crate::{Display, Maze, Position, Size, Tile, BLOCK_CHAR};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Format {
+    Txt,
+    /// The solution only, see `Solution::to_json`. It has no extension of its own so it
+    /// has to be picked by name.
+    #[strum(to_string = "path-json")]
+    PathJson,
+    /// Export only, see `Maze::to_svg`.
+    Svg,
+    /// Export only, see `Maze::to_html`.
+    Html,
+    /// See `Maze::to_json` and `Maze::from_json`.
+    Json,
+    /// Lines of `#` and `.`, see `Maze::to_wall_grid_text`. The solution isn't written.
+    Grid,
+    /// Export only, see `Maze::to_image`.
+    #[cfg(feature = "png")]
+    Png,
+}
+impl Format {
+    pub fn get_extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Txt => &["txt"],
+            Self::PathJson => &[],
+            Self::Svg => &["svg"],
+            Self::Html => &["html", "htm"],
+            Self::Json => &["json"],
+            Self::Grid => &["grid"],
+            #[cfg(feature = "png")]
+            Self::Png => &["png"],
+        }
+    }
+
+    /// Whether the format is bytes rather than text, which a terminal can't show.
+    pub fn is_binary(&self) -> bool {
+        match self {
+            #[cfg(feature = "png")]
+            Self::Png => true,
+            _ => false,
+        }
+    }
+
+    pub fn can_import(&self) -> bool {
+        match self {
+            Self::Txt => true,
+            Self::PathJson => false,
+            Self::Svg => false,
+            Self::Html => false,
+            Self::Json => true,
+            Self::Grid => true,
+            #[cfg(feature = "png")]
+            Self::Png => false,
+        }
+    }
+
+    /// Every format claiming the extension of `path`. More than one candidate means the
+    /// extension is ambiguous and the caller has to name the format explicitly.
+    pub fn candidates_for(path: &Path) -> Vec<Format> {
+        let extension = path
+            .extension()
+            .and_then(|x| x.to_str())
+            .map(|x| x.to_lowercase());
+
+        let Some(extension) = extension else {
+            return vec![];
+        };
+
+        Self::iter()
+            .filter(|format| format.get_extensions().contains(&extension.as_str()))
+            .collect()
+    }
+
+    pub fn detect(path: &Path) -> Result<Format, io::ErrorKind> {
+        match Self::candidates_for(path).as_slice() {
+            [format] => Ok(*format),
+            [] => Err(io::ErrorKind::Unsupported),
+            _ => Err(io::ErrorKind::InvalidInput),
+        }
+    }
+
+    pub fn import(&self, data: &[u8]) -> Result<Maze, io::ErrorKind> {
+        match self {
+            Self::Txt => import_txt(
+                std::str::from_utf8(data).map_err(|_| io::ErrorKind::InvalidData)?
+            ),
+            Self::Json => Maze::from_json(
+                std::str::from_utf8(data).map_err(|_| io::ErrorKind::InvalidData)?
+            ).map_err(|_| io::ErrorKind::InvalidData),
+            Self::Grid => Maze::from_wall_grid_text(
+                std::str::from_utf8(data).map_err(|_| io::ErrorKind::InvalidData)?
+            ).map_err(|_| io::ErrorKind::InvalidData),
+            Self::PathJson | Self::Svg | Self::Html => Err(io::ErrorKind::Unsupported),
+            #[cfg(feature = "png")]
+            Self::Png => Err(io::ErrorKind::Unsupported),
+        }
+    }
+
+    pub fn export<W: io::Write>(&self, maze: &Maze, solution: Option<&[Position]>, w: &mut W) -> io::Result<()> {
+        match self {
+            Self::Txt => export_txt(maze, solution, w),
+            Self::PathJson => {
+                let path = solution.ok_or(io::ErrorKind::InvalidInput)?;
+
+                writeln!(w, "{}", Solution::new(path.to_vec()).to_json(None)?)
+            },
+            Self::Svg => write!(w, "{}", maze.to_svg(solution, &SvgOptions::default())),
+            Self::Html => write!(w, "{}", maze.to_html(solution, &HtmlOptions::default())),
+            Self::Json => writeln!(w, "{}", maze.to_json_with(solution, None)),
+            Self::Grid => write!(w, "{}", maze.to_wall_grid_text()),
+            #[cfg(feature = "png")]
+            Self::Png => maze.write_png_to(solution, &crate::png::ImageOptions::default(), w),
+        }
+    }
+}
+
+pub fn export_txt<W: io::Write>(maze: &Maze, solution: Option<&[Position]>, w: &mut W) -> io::Result<()> {
+    let mut display = Display::new_from_maze(maze);
+    display.draw_maze(maze)?;
+
+    if let Some(path) = solution {
+        display.draw_solution(path, crate::POINT_CHAR)?;
+    }
+
+    display.write_to(w)
+}
+
+/// Reads back the block rendering printed by the binary. The left margin is whatever
+/// indentation all rows share, `BLOCK_CHAR` is a wall and anything else is open. The outer
+/// border is always treated as closed so entrance markers drawn on it are ignored.
+pub fn import_txt(text: &str) -> Result<Maze, io::ErrorKind> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim_end())
+        .skip_while(|line| line.is_empty())
+        .collect();
+
+    let lines = &lines[..lines.iter().rposition(|line| !line.is_empty()).map_or(0, |x| x + 1)];
+
+    let margin = lines.iter()
+        .map(|line| line.chars().take_while(|c| *c == ' ').count())
+        .min()
+        .ok_or(io::ErrorKind::InvalidData)?;
+
+    let rows: Vec<Vec<char>> = lines.iter()
+        .map(|line| line.chars().skip(margin).collect())
+        .collect();
+
+    let height = rows.len();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    if width < 3 || height < 3 || width.is_multiple_of(2) || height.is_multiple_of(2) {
+        return Err(io::ErrorKind::InvalidData);
+    }
+
+    let is_wall = |x: usize, y: usize| rows[y].get(x) == Some(&BLOCK_CHAR);
+
+    let size = Size((width - 1) / 2, (height - 1) / 2);
+    let mut maze = Maze::new(size, true);
+
+    for ((x, y), tile) in maze.tiles.indexed_iter_mut() {
+        let center = Maze::to_display_pos(Position(x, y));
+
+        if is_wall(center.0, center.1) {
+            return Err(io::ErrorKind::InvalidData);
+        }
+
+        *tile = Tile {
+            up: y == 0 || is_wall(center.0, center.1 - 1),
+            right: x == size.0 - 1 || is_wall(center.0 + 1, center.1),
+            down: y == size.1 - 1 || is_wall(center.0, center.1 + 1),
+            left: x == 0 || is_wall(center.0 - 1, center.1),
+        };
+    }
+
+    Ok(maze)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Size};
+    use std::io;
+    use std::path::Path;
+    use strum::IntoEnumIterator;
+
+    fn fixture() -> Maze {
+        let mut maze = Maze::new(Size(5, 4), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(6));
+        maze
+    }
+
+    #[test]
+    fn every_importer_feeds_every_exporter() {
+        let maze = fixture();
+        let solution = maze.solve_bfs();
+
+        for importer in Format::iter().filter(Format::can_import) {
+            let mut data = vec![];
+            importer.export(&maze, None, &mut data).unwrap();
+
+            let imported = importer.import(&data).unwrap_or_else(|error| panic!("{} didn't read back: {:?}", importer, error));
+            assert_eq!(imported, maze, "{}", importer);
+
+            for exporter in Format::iter() {
+                let mut out = vec![];
+                exporter.export(&imported, Some(&solution), &mut out).unwrap();
+                assert!(!out.is_empty(), "{} to {}", importer, exporter);
+
+                if exporter.can_import() {
+                    assert_eq!(exporter.import(&out), Ok(maze.clone()), "{} to {}", importer, exporter);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn export_only_formats_refuse_to_import() {
+        for format in Format::iter().filter(|format| !format.can_import()) {
+            assert_eq!(format.import(b"").err(), Some(io::ErrorKind::Unsupported), "{}", format);
+        }
+    }
+
+    #[test]
+    fn extensions_pick_the_format() {
+        assert_eq!(Format::detect(Path::new("maze.TXT")), Ok(Format::Txt));
+        assert_eq!(Format::detect(Path::new("maze.htm")), Ok(Format::Html));
+        assert_eq!(Format::detect(Path::new("maze.grid")), Ok(Format::Grid));
+        assert_eq!(Format::detect(Path::new("maze.xyz")), Err(io::ErrorKind::Unsupported));
+        assert_eq!(Format::detect(Path::new("maze")), Err(io::ErrorKind::Unsupported));
+    }
+}
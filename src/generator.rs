@@ -0,0 +1,127 @@
+use rand::RngCore;
+use std::io;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::growing_tree::GrowStrategy;
+use crate::rng::Pcg32;
+use crate::symmetry::Symmetry;
+use crate::{Maze, Position, Size};
+
+/// Every generation algorithm the crate ships, for code that picks one by name or wants to
+/// run all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum GeneratorKind {
+    Backtracker,
+    Hilbert,
+    Serpentine,
+    Kruskal,
+    Wilson,
+    Eller,
+    Sidewinder,
+    #[strum(to_string = "growing-tree")]
+    GrowingTree,
+    Cave,
+    Sparse,
+    Weave,
+    Symmetric,
+    Unicursal,
+}
+impl GeneratorKind {
+    /// Whether every maze from this generator has exactly one path between any two cells,
+    /// counting the walls it opens. Caves and symmetric mazes have loops, sparse ones leave
+    /// cells out and a crossing of a weave maze opens more walls than a tree would.
+    pub fn is_perfect(&self) -> bool {
+        match self {
+            Self::Backtracker
+            | Self::Hilbert
+            | Self::Serpentine
+            | Self::Kruskal
+            | Self::Wilson
+            | Self::Eller
+            | Self::Sidewinder
+            | Self::GrowingTree
+            | Self::Unicursal => true,
+            Self::Cave | Self::Sparse | Self::Weave | Self::Symmetric => false,
+        }
+    }
+
+    /// Whether every cell of a maze from this generator can be reached. Caves and sparse
+    /// mazes leave solid cells that can't.
+    pub fn reaches_every_cell(&self) -> bool {
+        !matches!(self, Self::Cave | Self::Sparse)
+    }
+}
+
+/// Settings for `Maze::generate_maze_with`. The defaults generate the same mazes as
+/// `generate_maze` and `generate_maze_seeded`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationOptions {
+    pub start: Position,
+    /// Between 0 and 1, how strongly the backtracker prefers carving on in the direction it
+    /// just went. Values near 1 give long winding corridors.
+    pub straightness: f64,
+    pub seed: Option<u64>,
+}
+impl Default for GenerationOptions {
+    fn default() -> Self {
+        Self {
+            start: Position::new(),
+            straightness: 0.0,
+            seed: None,
+        }
+    }
+}
+
+impl Maze {
+    /// Runs the backtracker with `opts`. Fails if the start is outside the maze or the
+    /// straightness isn't between 0 and 1.
+    pub fn generate_maze_with(&mut self, opts: &GenerationOptions) -> Result<(), io::ErrorKind> {
+        if !(0.0..=1.0).contains(&opts.straightness) {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        match opts.seed {
+            Some(seed) => self.generate_backtracker_from(opts.start, opts.straightness, &mut Pcg32::new(seed), None),
+            None => self.generate_backtracker_from(opts.start, opts.straightness, &mut rand::rng(), None),
+        }
+    }
+
+    /// Carves a fully walled maze with the chosen algorithm. The curve generators don't use
+    /// `rng`, and Hilbert falls back to a serpentine on sizes it doesn't support. The growing
+    /// tree picks between the newest and a random cell evenly. Caves start 45% solid and are
+    /// smoothed 4 times, sparse mazes fill 60% of the cells, weave mazes tunnel with a
+    /// chance of 0.4 and symmetric ones are rotational. The unicursal corridor is grown from
+    /// a backtracker maze half the size, and is a serpentine if a side is odd.
+    pub fn generate<R: RngCore>(&mut self, kind: GeneratorKind, rng: &mut R) {
+        match kind {
+            GeneratorKind::Backtracker => self.generate_backtracker(rng, None),
+            GeneratorKind::Hilbert => {
+                if self.generate_hilbert().is_err() {
+                    self.generate_serpentine();
+                }
+            },
+            GeneratorKind::Serpentine => self.generate_serpentine(),
+            GeneratorKind::Kruskal => self.generate_kruskal_with_rng(rng),
+            GeneratorKind::Wilson => self.generate_wilson_with_rng(rng),
+            GeneratorKind::Eller => self.generate_eller_with_rng(rng),
+            GeneratorKind::Sidewinder => self.generate_sidewinder_with_rng(rng),
+            GeneratorKind::GrowingTree => self.generate_growing_tree_with_rng(GrowStrategy::Mixed(0.5), rng),
+            GeneratorKind::Cave => self.generate_cave_with_rng(0.45, 4, rng).expect("The fill is between 0 and 1"),
+            GeneratorKind::Sparse => self.generate_sparse_with_rng(0.6, rng).expect("The fill is between 0 and 1"),
+            GeneratorKind::Weave => self.generate_weave_with_rng(0.4, rng),
+            GeneratorKind::Symmetric => self.generate_symmetric_with_rng(Symmetry::Rotational, rng),
+            GeneratorKind::Unicursal => {
+                if !self.size.0.is_multiple_of(2) || !self.size.1.is_multiple_of(2) {
+                    self.generate_serpentine();
+                    return;
+                }
+
+                let mut half = Maze::new(Size(self.size.0 / 2, self.size.1 / 2), true);
+                half.generate_backtracker(rng, None);
+
+                self.tiles = half.to_unicursal().tiles;
+            },
+        }
+    }
+}
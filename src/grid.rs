@@ -0,0 +1,134 @@
+use ndarray::Array2;
+
+use crate::json::MazeError;
+use crate::{Display, Maze, Position, Size, Tile, BLOCK_CHAR};
+
+/// The characters `Maze::to_wall_grid_text` writes for walls and open pixels.
+pub const GRID_WALL: char = '#';
+pub const GRID_OPEN: char = '.';
+
+impl Maze {
+    /// The walls on the (2w+1)x(2h+1) grid `Display` draws, true where `draw_maze` puts a
+    /// `BLOCK_CHAR`. It's indexed `[y, x]` like the pixels of a display, and crossings are
+    /// drawn as open cells.
+    pub fn to_wall_grid(&self) -> Array2<bool> {
+        let mut display = Display::new_from_maze(self);
+        display.draw_maze(self).expect("The display is made for the maze");
+
+        display.pixels.map(|pixel| *pixel == BLOCK_CHAR)
+    }
+
+    /// Reads the walls back from a grid like `to_wall_grid` gives. Both sides have to be odd
+    /// and at least 3, and the cell centers, on odd rows and columns, open. The corners
+    /// between walls don't matter.
+    pub fn from_wall_grid(grid: &Array2<bool>) -> Result<Maze, MazeError> {
+        let (height, width) = grid.dim();
+
+        if width < 3 || height < 3 || width.is_multiple_of(2) || height.is_multiple_of(2) {
+            return Err(MazeError::GridSize(Size(width, height)));
+        }
+
+        let mut maze = Maze::new(Size(width / 2, height / 2), true);
+
+        for ((x, y), tile) in maze.tiles.indexed_iter_mut() {
+            let center = Maze::to_display_pos(Position(x, y));
+
+            if grid[[center.1, center.0]] {
+                return Err(MazeError::WalledCell(Position(x, y)));
+            }
+
+            *tile = Tile {
+                up: grid[[center.1 - 1, center.0]],
+                right: grid[[center.1, center.0 + 1]],
+                down: grid[[center.1 + 1, center.0]],
+                left: grid[[center.1, center.0 - 1]],
+            };
+        }
+
+        Ok(maze)
+    }
+
+    /// `to_wall_grid` as lines of `GRID_WALL` and `GRID_OPEN`.
+    pub fn to_wall_grid_text(&self) -> String {
+        self.to_wall_grid()
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().map(|wall| if *wall { GRID_WALL } else { GRID_OPEN }).collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// Reads a grid written as lines of `#` and `.` or of `1` and `0`, where `#` and `1` are
+    /// walls, see `from_wall_grid`. Blank lines around the grid and whitespace at the ends of
+    /// lines are skipped.
+    pub fn from_wall_grid_text(text: &str) -> Result<Maze, MazeError> {
+        let lines: Vec<&str> = text.trim().lines().map(str::trim).collect();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+
+        let mut grid = Array2::from_elem((lines.len(), width), false);
+
+        for (y, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(MazeError::GridSize(Size(line.chars().count(), lines.len())));
+            }
+
+            for (x, symbol) in line.chars().enumerate() {
+                grid[[y, x]] = match symbol {
+                    '#' | '1' => true,
+                    '.' | '0' => false,
+                    _ => return Err(MazeError::GridChar { pos: Position(x, y), symbol }),
+                };
+            }
+        }
+
+        Self::from_wall_grid(&grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Array2;
+
+    use crate::json::MazeError;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+
+    #[test]
+    fn generated_mazes_round_trip() {
+        for (size, seed) in [(Size(1, 1), 0), (Size(1, 5), 1), (Size(9, 6), 2), (Size(16, 3), 3)] {
+            let mut maze = Maze::new(size, true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+            maze.braid_with_rng(0.5, &mut Pcg32::new(seed));
+
+            let grid = maze.to_wall_grid();
+            assert_eq!(grid.dim(), (size.1 * 2 + 1, size.0 * 2 + 1));
+            assert_eq!(Maze::from_wall_grid(&grid).unwrap().tiles, maze.tiles, "{size:?}");
+
+            let text = maze.to_wall_grid_text();
+            assert_eq!(Maze::from_wall_grid_text(&text).unwrap().tiles, maze.tiles, "{size:?}");
+
+            let digits = text.replace('#', "1").replace('.', "0");
+            assert_eq!(Maze::from_wall_grid_text(&digits).unwrap().tiles, maze.tiles, "{size:?}");
+        }
+    }
+
+    #[test]
+    fn the_text_is_the_display_grid() {
+        let mut maze = Maze::new(Size(2, 1), true);
+        maze.open_wall(Position(0, 0), Direction::East).unwrap();
+
+        assert_eq!(maze.to_wall_grid_text(), "#####\n#...#\n#####\n");
+        assert_eq!(Maze::from_wall_grid_text("\n  #####\n  #...#  \n  #####\n\n"), Ok(maze));
+    }
+
+    #[test]
+    fn broken_grids_are_refused() {
+        assert_eq!(Maze::from_wall_grid(&Array2::from_elem((3, 4), false)), Err(MazeError::GridSize(Size(4, 3))));
+        assert_eq!(Maze::from_wall_grid(&Array2::from_elem((1, 1), false)), Err(MazeError::GridSize(Size(1, 1))));
+        assert_eq!(Maze::from_wall_grid_text("###\n#.#\n##"), Err(MazeError::GridSize(Size(2, 3))));
+        assert_eq!(Maze::from_wall_grid_text("#####\n#.###\n#####"), Err(MazeError::WalledCell(Position(1, 0))));
+        assert_eq!(
+            Maze::from_wall_grid_text("###\n#x#\n###"),
+            Err(MazeError::GridChar { pos: Position(1, 1), symbol: 'x' })
+        );
+    }
+}
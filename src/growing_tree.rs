@@ -0,0 +1,180 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+use crate::rng::{chance, pick, random_index};
+use crate::{Direction, Maze, Position};
+
+/// Which active cell the growing tree generator carves from next.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GrowStrategy {
+    /// The most recently added cell, giving the long corridors of the backtracker.
+    Newest,
+    /// The cell waiting the longest, spreading out from the start in rings.
+    Oldest,
+    /// Any cell, giving the short branchy dead ends of Prim's algorithm.
+    Random,
+    /// A random cell with the given probability and the newest one otherwise.
+    Mixed(f64),
+}
+impl fmt::Display for GrowStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Newest => write!(f, "newest"),
+            Self::Oldest => write!(f, "oldest"),
+            Self::Random => write!(f, "random"),
+            Self::Mixed(probability) => write!(f, "mixed:{}", probability),
+        }
+    }
+}
+impl FromStr for GrowStrategy {
+    type Err = io::ErrorKind;
+
+    /// Reads the names printed by `Display`, with the probability of `mixed:P` between 0 and 1.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "newest" => Ok(Self::Newest),
+            "oldest" => Ok(Self::Oldest),
+            "random" => Ok(Self::Random),
+            other => {
+                let probability: f64 = other
+                    .strip_prefix("mixed:")
+                    .and_then(|x| x.parse().ok())
+                    .ok_or(io::ErrorKind::InvalidInput)?;
+
+                match (0.0..=1.0).contains(&probability) {
+                    true => Ok(Self::Mixed(probability)),
+                    false => Err(io::ErrorKind::InvalidInput),
+                }
+            },
+        }
+    }
+}
+
+impl Maze {
+    pub fn generate_growing_tree(&mut self, strategy: GrowStrategy) {
+        self.generate_growing_tree_with_rng(strategy, &mut rand::rng());
+    }
+
+    /// Keeps a list of active cells starting with `(0, 0)`. Each step takes one by
+    /// `strategy` and carves into a random unvisited neighbour, which becomes active, or
+    /// drops the cell from the list if it has none left.
+    pub fn generate_growing_tree_with_rng<R: RngCore>(&mut self, strategy: GrowStrategy, rng: &mut R) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        let mut explored = Array2::from_elem(self.size.as_array(), false);
+        let mut active = VecDeque::from([Position::new()]);
+
+        explored[[0, 0]] = true;
+
+        while !active.is_empty() {
+            let newest = active.len() - 1;
+
+            let index = match strategy {
+                GrowStrategy::Newest => newest,
+                GrowStrategy::Oldest => 0,
+                GrowStrategy::Random => random_index(rng, active.len()),
+                GrowStrategy::Mixed(probability) => match chance(rng, probability) {
+                    true => random_index(rng, active.len()),
+                    false => newest,
+                },
+            };
+
+            let pos = active[index];
+
+            let directions: Vec<Direction> = Direction::iter()
+                .filter(|direction| {
+                    self.get_neighbor(pos, *direction)
+                        .is_some_and(|neighbor| !explored[neighbor.as_array()])
+                })
+                .collect();
+
+            let Some(direction) = pick(rng, &directions) else {
+                // Both ends come off in O(1) without disturbing the order, anything in
+                // between is swapped with the newest cell first
+                match index {
+                    0 => active.pop_front(),
+                    _ => active.swap_remove_back(index),
+                };
+
+                continue;
+            };
+
+            let next = pos.translate(direction);
+
+            self.set_wall(pos, direction, false).unwrap();
+            explored[next.as_array()] = true;
+
+            active.push_back(next);
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GrowStrategy;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+
+    const STRATEGIES: [GrowStrategy; 5] = [
+        GrowStrategy::Newest,
+        GrowStrategy::Oldest,
+        GrowStrategy::Random,
+        GrowStrategy::Mixed(0.0),
+        GrowStrategy::Mixed(0.3),
+    ];
+
+    #[test]
+    fn every_strategy_is_perfect() {
+        for strategy in STRATEGIES {
+            for size in [Size(1, 1), Size(1, 6), Size(6, 1), Size(2, 2), Size(11, 7)] {
+                let mut maze = Maze::new(size, true);
+                maze.generate_growing_tree_with_rng(strategy, &mut Pcg32::new(4));
+
+                let cells = size.0 * size.1;
+                let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+                let open: usize = maze.tiles.iter()
+                    .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+                    .sum();
+
+                assert_eq!(maze.size, size);
+                assert_eq!((reached, open / 2), (cells, cells - 1), "{strategy} at {size:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn newest_is_the_backtracker() {
+        for seed in 0..5 {
+            let mut grown = Maze::new(Size(9, 7), true);
+            grown.generate_growing_tree_with_rng(GrowStrategy::Newest, &mut Pcg32::new(seed));
+
+            let mut backtracked = Maze::new(Size(9, 7), true);
+            backtracked.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+            assert_eq!(grown, backtracked, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn strategies_read_back_what_they_print() {
+        for strategy in STRATEGIES {
+            assert_eq!(strategy.to_string().parse(), Ok(strategy));
+        }
+
+        assert_eq!("Mixed:1".parse(), Ok(GrowStrategy::Mixed(1.0)));
+        assert!("mixed:1.5".parse::<GrowStrategy>().is_err());
+        assert!("mixed".parse::<GrowStrategy>().is_err());
+        assert!("prim".parse::<GrowStrategy>().is_err());
+    }
+}
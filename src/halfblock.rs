@@ -0,0 +1,144 @@
+use std::io::{self, IsTerminal};
+
+use crate::{Color, Display, EMPTY_CHAR, RESET_COLOR};
+
+// What a single pixel turns into, colored pixels being the path and its markers
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Half {
+    Empty,
+    Filled,
+    Colored(Color),
+}
+
+impl Display {
+    /// Prints the display with two rows of pixels to a line, see `write_halfblock_to`.
+    /// Colors are only printed if `color` is set and stdout is a terminal.
+    pub fn print_halfblock(&self) {
+        let mut stdout = io::stdout().lock();
+        let color = self.color && stdout.is_terminal();
+
+        self.write_halfblock_with(&mut stdout, color)
+            .unwrap();
+    }
+
+    /// Writes the display with two rows of pixels packed into each line using `▀`, `▄`,
+    /// `█` and spaces, which is half as high and about as wide as it is tall on most
+    /// terminals. Every pixel that isn't empty counts as filled, only the colored ones
+    /// are drawn in their color if `color` is set and left out otherwise so the path
+    /// doesn't run into the walls. A display with an odd height gets an empty row below
+    /// its last one. Margins are kept as they are.
+    pub fn write_halfblock_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_halfblock_with(w, self.color)
+    }
+
+    fn write_halfblock_with<W: io::Write>(&self, w: &mut W, color: bool) -> io::Result<()> {
+        let half = |y: usize, x: usize| match (self.pixels.get([y, x]), self.colors.get([y, x])) {
+            (Some(_), Some(Some(pixel_color))) if color => Half::Colored(*pixel_color),
+            (Some(_), Some(Some(_))) | (Some(&EMPTY_CHAR), _) | (None, _) => Half::Empty,
+            (Some(_), _) => Half::Filled,
+        };
+
+        write!(w, "{}", "\n".repeat(self.margins.top))?;
+
+        for y in (0..self.size.1).step_by(2) {
+            let mut rowstring = String::new();
+            let mut current = (None, None);
+
+            for x in 0..self.size.0 {
+                let (symbol, foreground, background) = halfblock_char(half(y, x), half(y + 1, x));
+
+                if (foreground, background) != current {
+                    rowstring.push_str(RESET_COLOR);
+                    rowstring.extend(foreground.map(|x| x.ansi()));
+                    rowstring.extend(background.map(|x| x.ansi_background()));
+                    current = (foreground, background);
+                }
+
+                rowstring.push(symbol);
+            }
+
+            if current != (None, None) {
+                rowstring.push_str(RESET_COLOR);
+            }
+
+            write!(w, "{}", " ".repeat(self.margins.left))?;
+            write!(w, "{}", rowstring)?;
+            writeln!(w, "{}", " ".repeat(self.margins.right))?;
+        }
+
+        write!(w, "{}", "\n".repeat(self.margins.bottom))?;
+
+        Ok(())
+    }
+}
+
+// The character for a top and a bottom pixel with the foreground and background it needs.
+// Filled pixels are always the foreground so they stay in the terminal's own color.
+fn halfblock_char(top: Half, bottom: Half) -> (char, Option<Color>, Option<Color>) {
+    match (top, bottom) {
+        (Half::Empty, Half::Empty) => (' ', None, None),
+        (Half::Filled, Half::Filled) => ('█', None, None),
+        (Half::Filled, Half::Empty) => ('▀', None, None),
+        (Half::Empty, Half::Filled) => ('▄', None, None),
+        (Half::Colored(top), Half::Empty) => ('▀', Some(top), None),
+        (Half::Empty, Half::Colored(bottom)) => ('▄', Some(bottom), None),
+        (Half::Filled, Half::Colored(bottom)) => ('▀', None, Some(bottom)),
+        (Half::Colored(top), Half::Filled) => ('▄', None, Some(top)),
+        (Half::Colored(top), Half::Colored(bottom)) if top == bottom => ('█', Some(top), None),
+        (Half::Colored(top), Half::Colored(bottom)) => ('▀', Some(top), Some(bottom)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{halfblock_char, Half};
+    use crate::test_support::opened;
+    use crate::{Color, Direction, Display, Margins, Position, Size};
+
+    fn halfblock(color: bool) -> String {
+        let maze = opened(Size(2, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.color = color;
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution(&[Position(0, 0), Position(1, 0), Position(1, 1)], '*').unwrap();
+
+        let mut out = vec![];
+        display.write_halfblock_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn two_rows_go_on_a_line() {
+        // Five rows of pixels make three lines, the last with an empty row below it, and
+        // the uncolored path is left out
+        let expected = [
+            "█ ▀▀█",
+            "█▀█ █",
+            "▀▀▀ ▀",
+        ];
+
+        assert_eq!(halfblock(false), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn the_path_is_drawn_in_its_color() {
+        // The markers in green, the path in yellow behind the walls and every run of color
+        // reset before the next
+        let expected = [
+            "█\x1b[0m\x1b[32m\x1b[43m▀\x1b[0m\x1b[43m▀▀\x1b[0m█",
+            "█▀█\x1b[0m\x1b[33m█\x1b[0m█",
+            "▀▀▀\x1b[0m\x1b[32m▀\x1b[0m▀",
+        ];
+
+        assert_eq!(halfblock(true), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn walls_stay_in_the_foreground() {
+        assert_eq!(halfblock_char(Half::Filled, Half::Colored(Color::Red)), ('▀', None, Some(Color::Red)));
+        assert_eq!(halfblock_char(Half::Colored(Color::Red), Half::Filled), ('▄', None, Some(Color::Red)));
+        assert_eq!(halfblock_char(Half::Colored(Color::Red), Half::Colored(Color::Red)), ('█', Some(Color::Red), None));
+    }
+}
@@ -0,0 +1,108 @@
+use ndarray::Array2;
+
+use crate::{Color, Direction, Display, Maze, Position};
+
+/// The shades `Display::draw_heatmap` fills cells with, from the nearest to the farthest.
+/// Full blocks are left to the walls.
+pub const HEAT_SHADES: [char; 3] = ['░', '▒', '▓'];
+
+/// And the colors, in the same order, that are printed when the display has color on.
+pub const HEAT_COLORS: [Color; 5] = [Color::Blue, Color::Cyan, Color::Green, Color::Yellow, Color::Red];
+
+/// What cells the distances don't reach are marked with.
+pub const UNREACHABLE_CHAR: char = '×';
+pub const UNREACHABLE_COLOR: Color = Color::Magenta;
+
+impl Display {
+    /// Shades every cell of `maze`, and the open passages between them, by how far it is
+    /// along `distances` compared to the farthest cell, like from `Maze::distances_from`.
+    /// A passage counts as far as the farther of its cells. Cells without a distance get
+    /// `UNREACHABLE_CHAR`, walls are left alone.
+    pub fn draw_heatmap(&mut self, maze: &Maze, distances: &Array2<Option<u32>>) {
+        let max = distances.iter().flatten().copied().max().unwrap_or(0);
+
+        let level = |distance: u32, levels: usize| match max {
+            0 => 0,
+            _ => distance as usize * (levels - 1) / max as usize,
+        };
+
+        for ((x, y), tile) in maze.tiles.indexed_iter() {
+            let pos = Position(x, y);
+            let display_pos = Maze::to_display_pos(pos);
+
+            let Some(distance) = distances.get([x, y]).copied().flatten() else {
+                self.draw_point_colored(display_pos, UNREACHABLE_CHAR, Some(UNREACHABLE_COLOR));
+                continue;
+            };
+
+            let mut cells = vec![(display_pos, distance)];
+
+            for direction in [Direction::East, Direction::South] {
+                let neighbor = maze.get_neighbor(pos, direction)
+                    .filter(|_| !tile.get_side(direction))
+                    .and_then(|neighbor| distances.get(neighbor.as_array()).copied().flatten());
+
+                if let Some(other) = neighbor {
+                    cells.push((display_pos.translate(direction), distance.max(other)));
+                }
+            }
+
+            for (pixel, distance) in cells {
+                self.draw_point_colored(
+                    pixel,
+                    HEAT_SHADES[level(distance, HEAT_SHADES.len())],
+                    Some(HEAT_COLORS[level(distance, HEAT_COLORS.len())])
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HEAT_COLORS, UNREACHABLE_COLOR};
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Maze, Position, Size};
+
+    fn heatmap(maze: &Maze) -> Display {
+        let mut display = Display::new_from_maze(maze);
+        display.margins = Margins::none();
+        display.draw_maze(maze).unwrap();
+        display.draw_heatmap(maze, &maze.distances_from(Position(0, 0)));
+
+        display
+    }
+
+    #[test]
+    fn cells_and_passages_darken_with_distance() {
+        let maze = opened(Size(3, 1), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::East)]);
+        let display = heatmap(&maze);
+
+        // Passages take the shade of their farther cell
+        let expected = [
+            "███████",
+            "█░▒▒▓▓█",
+            "███████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+        assert_eq!(display.colors[[1, 1]], Some(HEAT_COLORS[0]));
+        assert_eq!(display.colors[[1, 5]], Some(HEAT_COLORS[4]));
+    }
+
+    #[test]
+    fn unreachable_cells_are_crossed_out() {
+        let maze = opened(Size(3, 1), &[(Position(0, 0), Direction::East)]);
+        let display = heatmap(&maze);
+
+        // The farthest reached cell is the darkest shade
+        let expected = [
+            "███████",
+            "█░▓▓█×█",
+            "███████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+        assert_eq!(display.colors[[1, 5]], Some(UNREACHABLE_COLOR));
+    }
+}
@@ -0,0 +1,164 @@
+use ndarray::Array2;
+use std::fmt::Write;
+
+use crate::svg::escape;
+use crate::{Direction, Maze, Position};
+
+/// Sizes in CSS pixels and colors for `Maze::to_html`. Colors are anything CSS takes, like
+/// `black` or `#c03030`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlOptions {
+    pub cell_size: f64,
+    pub wall_width: f64,
+    pub wall_color: String,
+    pub path_color: String,
+    pub background: String,
+}
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 24.0,
+            wall_width: 2.0,
+            wall_color: "black".to_string(),
+            path_color: "#dc2828".to_string(),
+            background: "white".to_string(),
+        }
+    }
+}
+
+// The class a cell gets for each of its closed walls
+fn wall_class(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "n",
+        Direction::East => "e",
+        Direction::South => "s",
+        Direction::West => "w",
+    }
+}
+
+const STYLE: &str = "\
+body{font-family:sans-serif}\
+.maze{display:grid;grid-template-columns:repeat(var(--columns),var(--cell));grid-auto-rows:var(--cell);\
+background:var(--background);width:max-content}\
+.maze div{box-sizing:border-box;border:0 solid var(--wall-color)}\
+.maze .n{border-top-width:var(--wall)}.maze .e{border-right-width:var(--wall)}\
+.maze .s{border-bottom-width:var(--wall)}.maze .w{border-left-width:var(--wall)}\
+.solved .p{background:var(--path-color)}\
+@media print{button{display:none}}";
+
+impl Maze {
+    /// The maze as a standalone HTML page with nothing to load. Every cell is a `div` in a
+    /// CSS grid, in rows from the top left, with the classes `n`, `e`, `s` and `w` for its
+    /// closed walls. Walls between two cells are drawn half from each side. If `solution`
+    /// is given its cells also get the class `p` and a button shows or hides them.
+    pub fn to_html(&self, solution: Option<&[Position]>, opts: &HtmlOptions) -> String {
+        let mut html = String::new();
+
+        writeln!(html, "<!DOCTYPE html>").unwrap();
+        writeln!(html, "<html><head><meta charset=\"utf-8\"><title>Maze {}x{}</title>", self.size.0, self.size.1).unwrap();
+        writeln!(html, "<style>{}</style></head><body>", STYLE).unwrap();
+
+        let solution = solution.unwrap_or_default();
+        let mut on_path = Array2::from_elem(self.size.as_array(), false);
+
+        for pos in solution {
+            if let Some(cell) = on_path.get_mut(pos.as_array()) {
+                *cell = true;
+            }
+        }
+
+        if !solution.is_empty() {
+            writeln!(html, r#"<p><button onclick="document.body.classList.toggle('solved')">Show solution</button></p>"#).unwrap();
+        }
+
+        writeln!(
+            html,
+            r#"<div class="maze" style="--columns:{};--cell:{}px;--wall:{}px;--wall-color:{};--path-color:{};--background:{}">"#,
+            self.size.0,
+            opts.cell_size,
+            opts.wall_width / 2.0,
+            escape(&opts.wall_color),
+            escape(&opts.path_color),
+            escape(&opts.background)
+        ).unwrap();
+
+        for y in 0..self.size.1 {
+            for x in 0..self.size.0 {
+                let mut classes: Vec<&str> = self.tiles[[x, y]]
+                    .get_sides()
+                    .into_iter()
+                    .filter(|(_, closed)| *closed)
+                    .map(|(direction, _)| wall_class(direction))
+                    .collect();
+
+                if on_path[[x, y]] {
+                    classes.push("p");
+                }
+
+                writeln!(html, r#"<div class="{}"></div>"#, classes.join(" ")).unwrap();
+            }
+        }
+
+        writeln!(html, "</div>").unwrap();
+        writeln!(html, "</body></html>").unwrap();
+
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlOptions;
+    use crate::{Direction, Maze, Position, Size};
+
+    // A hook from the top left across to the top right and down to the bottom right, with
+    // the bottom left cell on its own
+    fn hook() -> Maze {
+        let mut maze = Maze::new(Size(2, 2), true);
+        maze.open_wall(Position(0, 0), Direction::East).unwrap();
+        maze.open_wall(Position(1, 0), Direction::South).unwrap();
+
+        maze
+    }
+
+    fn cell_classes(html: &str) -> Vec<&str> {
+        html.lines()
+            .filter_map(|line| line.strip_prefix(r#"<div class=""#)?.strip_suffix(r#""></div>"#))
+            .collect()
+    }
+
+    #[test]
+    fn cells_get_a_class_per_closed_wall() {
+        let html = hook().to_html(None, &HtmlOptions::default());
+
+        assert_eq!(cell_classes(&html), vec!["n s w", "n e", "n e s w", "e s w"]);
+        assert!(!html.contains("<button"));
+    }
+
+    #[test]
+    fn the_solution_is_marked_behind_a_button() {
+        let path = [Position(0, 0), Position(1, 0), Position(1, 1)];
+        let html = hook().to_html(Some(&path), &HtmlOptions::default());
+
+        assert_eq!(cell_classes(&html), vec!["n s w p", "n e p", "n e s w", "e s w p"]);
+        assert_eq!(html.matches("<button").count(), 1);
+    }
+
+    #[test]
+    fn options_go_into_the_page() {
+        let opts = HtmlOptions {
+            cell_size: 10.0,
+            wall_width: 3.0,
+            wall_color: "\"red\"".to_string(),
+            ..HtmlOptions::default()
+        };
+
+        let mut maze = Maze::new(Size(7, 4), true);
+        maze.generate_maze_seeded(1);
+        let html = maze.to_html(None, &opts);
+
+        assert_eq!(cell_classes(&html).len(), 28);
+        assert!(html.contains("--columns:7;--cell:10px;--wall:1.5px;--wall-color:&quot;red&quot;;"));
+        assert!(!html.contains("http"));
+    }
+}
@@ -0,0 +1,151 @@
+use std::ops::Range;
+
+use crate::rng::{mix_seed, Pcg32};
+use crate::{Maze, Position, Size};
+
+const CHUNK_SALT: u64 = 0;
+const VERTICAL_EDGE_SALT: u64 = 1;
+const HORIZONTAL_EDGE_SALT: u64 = 2;
+
+/// An endless maze made of independently generated chunks. Each chunk is a perfect maze
+/// seeded from `(seed, cx, cy)`, and every edge shared by two chunks gets one opening
+/// whose offset depends only on `(seed, edge)`. Neighbours therefore agree on their shared
+/// border without ever generating each other, in whatever order chunks are requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InfiniteMaze {
+    pub seed: u64,
+    pub chunk_size: Size,
+}
+impl InfiniteMaze {
+    pub fn new(seed: u64, chunk_size: Size) -> Self {
+        assert!(chunk_size.0 >= 2 && chunk_size.1 >= 2, "Chunks must be at least 2x2");
+
+        Self { seed, chunk_size }
+    }
+
+    /// The chunk at `(cx, cy)`. Border walls are closed except for the openings towards
+    /// the four neighbouring chunks.
+    pub fn get_chunk(&self, cx: i64, cy: i64) -> Maze {
+        let mut maze = Maze::new(self.chunk_size, true);
+
+        maze.generate_backtracker(
+            &mut Pcg32::new(mix_seed(self.seed, &[CHUNK_SALT, cx as u64, cy as u64])),
+            None
+        );
+
+        let max = self.chunk_size.get_max_pos();
+
+        let east = self.get_vertical_opening(cx, cy);
+        let west = self.get_vertical_opening(cx - 1, cy);
+        let south = self.get_horizontal_opening(cx, cy);
+        let north = self.get_horizontal_opening(cx, cy - 1);
+
+        maze.tiles[[max.0, east]].right = false;
+        maze.tiles[[0, west]].left = false;
+        maze.tiles[[south, max.1]].down = false;
+        maze.tiles[[north, 0]].up = false;
+
+        maze
+    }
+
+    /// Row of the opening in the edge between chunks `(cx, cy)` and `(cx + 1, cy)`.
+    pub fn get_vertical_opening(&self, cx: i64, cy: i64) -> usize {
+        let hash = mix_seed(self.seed, &[VERTICAL_EDGE_SALT, cx as u64, cy as u64]);
+
+        (hash % self.chunk_size.1 as u64) as usize
+    }
+
+    /// Column of the opening in the edge between chunks `(cx, cy)` and `(cx, cy + 1)`.
+    pub fn get_horizontal_opening(&self, cx: i64, cy: i64) -> usize {
+        let hash = mix_seed(self.seed, &[HORIZONTAL_EDGE_SALT, cx as u64, cy as u64]);
+
+        (hash % self.chunk_size.0 as u64) as usize
+    }
+
+    /// Stitches a block of chunks into one finite maze with a closed outer border.
+    pub fn get_region(&self, cxs: Range<i64>, cys: Range<i64>) -> Maze {
+        let width = cxs.clone().count();
+        let height = cys.clone().count();
+
+        let mut maze = Maze::new(
+            Size(width * self.chunk_size.0, height * self.chunk_size.1),
+            true
+        );
+
+        for (i, cx) in cxs.enumerate() {
+            for (j, cy) in cys.clone().enumerate() {
+                let chunk = self.get_chunk(cx, cy);
+
+                for ((x, y), tile) in chunk.tiles.indexed_iter() {
+                    let pos = Position(i * self.chunk_size.0 + x, j * self.chunk_size.1 + y);
+
+                    maze.tiles[pos.as_array()] = *tile;
+                }
+            }
+        }
+
+        let max = maze.size.get_max_pos();
+
+        for ((x, y), tile) in maze.tiles.indexed_iter_mut() {
+            tile.up |= y == 0;
+            tile.left |= x == 0;
+            tile.down |= y == max.1;
+            tile.right |= x == max.0;
+        }
+
+        if cfg!(debug_assertions) {
+            maze.assert_consistent();
+        }
+
+        maze
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InfiniteMaze;
+    use crate::{Maze, Position, Size};
+
+    // The rows of the east border of `left` and the west border of `right` that are open
+    fn shared_edge(left: &Maze, right: &Maze) -> (Vec<usize>, Vec<usize>) {
+        let max = left.size.get_max_pos();
+
+        (
+            (0..left.size.1).filter(|y| !left.tiles[[max.0, *y]].right).collect(),
+            (0..right.size.1).filter(|y| !right.tiles[[0, *y]].left).collect(),
+        )
+    }
+
+    #[test]
+    fn neighbours_agree_in_either_order() {
+        let infinite = InfiniteMaze::new(77, Size(6, 5));
+
+        let (first, second) = (infinite.get_chunk(0, 0), infinite.get_chunk(1, 0));
+        let (second_again, first_again) = (InfiniteMaze::new(77, Size(6, 5)).get_chunk(1, 0), infinite.get_chunk(0, 0));
+
+        assert_eq!(first, first_again);
+        assert_eq!(second, second_again);
+
+        let (east, west) = shared_edge(&first, &second);
+        assert_eq!(east, vec![infinite.get_vertical_opening(0, 0)]);
+        assert_eq!(east, west);
+
+        // The same holds across the origin and between rows of chunks
+        let (above, below) = (infinite.get_chunk(-3, -1), infinite.get_chunk(-3, 0));
+        let column = infinite.get_horizontal_opening(-3, -1);
+
+        assert!(!above.tiles[[column, 4]].down);
+        assert!(!below.tiles[[column, 0]].up);
+    }
+
+    #[test]
+    fn paths_cross_between_chunks() {
+        let infinite = InfiniteMaze::new(5, Size(6, 5));
+        let region = infinite.get_region(0..2, -1..1);
+
+        let path = region.shortest_path(Position(0, 0), region.size.get_max_pos()).unwrap();
+
+        assert!(path.iter().any(|pos| pos.0 < 6) && path.iter().any(|pos| pos.0 >= 6));
+        assert!(region.find_inconsistencies().is_empty());
+    }
+}
@@ -0,0 +1,548 @@
+use std::fmt;
+use std::io;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use ndarray::Axis;
+use strum::IntoEnumIterator;
+
+use crate::{Direction, Maze, Position, Size, Tile};
+
+/// What generated a maze, written by `Maze::to_json_with`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GenerationInfo {
+    pub algorithm: Option<String>,
+    pub seed: Option<u64>,
+}
+
+/// Why `Maze::from_json`, `Maze::read_binary` or `Maze::from_wall_grid` couldn't read a maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MazeError {
+    /// Reading failed, `UnexpectedEof` if the data stops early.
+    Io(io::ErrorKind),
+    /// The data doesn't start with `binary::MAGIC`.
+    BadMagic,
+    /// The text isn't JSON at all.
+    Syntax,
+    /// A field is missing or of the wrong type, named like `cells[3].up`.
+    Field(String),
+    UnsupportedVersion(usize),
+    EmptySize,
+    /// `cells` doesn't have `width` times `height` entries.
+    CellCount { expected: usize, found: usize },
+    /// Two neighbouring cells disagree about the wall between them.
+    MismatchedWall { cell: Position, neighbor: Position },
+    /// A waypoint or crossing outside the maze, named like `waypoints[0]`.
+    OutOfBounds { field: String, pos: Position },
+    /// A crossing on a cell that isn't open on every side.
+    WalledCrossing(Position),
+    /// A crossing on the border, where one of its passages would lead out of the maze.
+    BorderCrossing(Position),
+    /// A wall grid that isn't odd by odd and at least 3 by 3, or has a row of another width.
+    GridSize(Size),
+    /// A wall on the center of a cell of a wall grid.
+    WalledCell(Position),
+    /// A character in a wall grid that is neither a wall nor open, at its column and line.
+    GridChar { pos: Position, symbol: char },
+}
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(io::ErrorKind::UnexpectedEof) => write!(f, "It ends before the last cell"),
+            Self::Io(kind) => write!(f, "Couldn't read it: {}", kind),
+            Self::BadMagic => write!(f, "It isn't a binary maze file"),
+            Self::Syntax => write!(f, "It isn't valid JSON"),
+            Self::Field(field) => write!(f, "'{}' is missing or has the wrong type", field),
+            Self::UnsupportedVersion(version) => write!(f, "Version {} isn't supported, only version 1 is", version),
+            Self::EmptySize => write!(f, "The width and height have to be above 0"),
+            Self::CellCount { expected, found } => write!(
+                f, "There are {} cells instead of {}, the width times the height", found, expected
+            ),
+            Self::MismatchedWall { cell, neighbor } => write!(
+                f, "The cells ({}, {}) and ({}, {}) disagree about the wall between them",
+                cell.0, cell.1, neighbor.0, neighbor.1
+            ),
+            Self::OutOfBounds { field, pos } => write!(f, "{} at ({}, {}) is outside the maze", field, pos.0, pos.1),
+            Self::WalledCrossing(pos) => write!(
+                f, "The crossing at ({}, {}) has walls, crossings are open on every side", pos.0, pos.1
+            ),
+            Self::BorderCrossing(pos) => write!(
+                f, "The crossing at ({}, {}) is on the border, crossings need a cell on every side", pos.0, pos.1
+            ),
+            Self::GridSize(size) => write!(
+                f, "A {}x{} wall grid doesn't fit a maze, it needs odd sides of at least 3 and rows of the same width",
+                size.0, size.1
+            ),
+            Self::WalledCell(pos) => write!(f, "The center of the cell ({}, {}) is a wall", pos.0, pos.1),
+            Self::GridChar { pos, symbol } => write!(
+                f, "'{}' at column {} of line {} is neither a wall ('#' or '1') nor open ('.' or '0')",
+                symbol, pos.0 + 1, pos.1 + 1
+            ),
+        }
+    }
+}
+
+impl Maze {
+    /// Reads back the output of `to_json`, checking that the walls of neighbouring cells
+    /// agree and that waypoints and crossings are inside the maze. The solution and
+    /// generator are left out, and so are `waypoints` and `crossings` when missing.
+    pub fn from_json(text: &str) -> Result<Maze, MazeError> {
+        let value = JsonValue::parse(text).map_err(|_| MazeError::Syntax)?;
+
+        let field = |name: &str| value.get(name).ok_or_else(|| MazeError::Field(name.to_string()));
+        let number = |name: &str| field(name)?.as_usize().ok_or_else(|| MazeError::Field(name.to_string()));
+
+        match number("version")? {
+            1 => {},
+            version => return Err(MazeError::UnsupportedVersion(version)),
+        }
+
+        let size = Size(number("width")?, number("height")?);
+
+        if size.0 == 0 || size.1 == 0 {
+            return Err(MazeError::EmptySize);
+        }
+
+        let cells = field("cells")?.as_array().ok_or_else(|| MazeError::Field("cells".to_string()))?;
+
+        let expected = size.0.checked_mul(size.1).ok_or(MazeError::Io(io::ErrorKind::InvalidData))?;
+
+        if cells.len() != expected {
+            return Err(MazeError::CellCount { expected, found: cells.len() });
+        }
+
+        let mut maze = Maze::new(size, true);
+
+        for (i, cell) in cells.iter().enumerate() {
+            let wall = |side: &str| cell.get(side)
+                .and_then(JsonValue::as_bool)
+                .ok_or_else(|| MazeError::Field(format!("cells[{}].{}", i, side)));
+
+            maze.tiles[[i % size.0, i / size.0]] = Tile {
+                up: wall("up")?,
+                right: wall("right")?,
+                down: wall("down")?,
+                left: wall("left")?,
+            };
+        }
+
+        let list = |name: &str| match value.get(name) {
+            None => Ok(&[][..]),
+            Some(list) => list.as_array().ok_or_else(|| MazeError::Field(name.to_string())),
+        };
+
+        // Where the object at `field` is
+        let cell = |object: &JsonValue, field: String| {
+            let coordinate = |axis: &str| object.get(axis)
+                .and_then(JsonValue::as_usize)
+                .ok_or_else(|| MazeError::Field(format!("{}.{}", field, axis)));
+
+            Ok(Position(coordinate("x")?, coordinate("y")?))
+        };
+
+        for (i, waypoint) in list("waypoints")?.iter().enumerate() {
+            let name = waypoint.get("name")
+                .and_then(JsonValue::as_str)
+                .ok_or_else(|| MazeError::Field(format!("waypoints[{}].name", i)))?;
+
+            let pos = cell(waypoint, format!("waypoints[{}]", i))?;
+
+            maze.waypoints.push((name.to_string(), pos));
+        }
+
+        for (i, crossing) in list("crossings")?.iter().enumerate() {
+            let pos = cell(crossing, format!("crossings[{}]", i))?;
+
+            let under = match crossing.get("under").and_then(JsonValue::as_str) {
+                Some("horizontal") => Axis(0),
+                Some("vertical") => Axis(1),
+                _ => return Err(MazeError::Field(format!("crossings[{}].under", i))),
+            };
+
+            maze.crossings.push((pos, under));
+        }
+
+        maze.check_consistency()?;
+
+        Ok(maze)
+    }
+
+    // Whether a maze read from a file holds together: both sides of every wall agree, the
+    // waypoints and crossings are inside the maze and the crossings are open on every side
+    // with a cell on every side
+    pub(crate) fn check_consistency(&self) -> Result<(), MazeError> {
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            for direction in [Direction::East, Direction::South] {
+                let Some(neighbor) = self.get_neighbor(Position(x, y), direction) else {
+                    continue;
+                };
+
+                if tile.get_side(direction) != self.tiles[neighbor.as_array()].get_side(direction.get_opposite()) {
+                    return Err(MazeError::MismatchedWall { cell: Position(x, y), neighbor });
+                }
+            }
+        }
+
+        let inside = |pos: Position, field: String| match pos.0 < self.size.0 && pos.1 < self.size.1 {
+            true => Ok(()),
+            false => Err(MazeError::OutOfBounds { field, pos }),
+        };
+
+        for (i, (_, pos)) in self.waypoints.iter().enumerate() {
+            inside(*pos, format!("waypoints[{}]", i))?;
+        }
+
+        for (i, (pos, _)) in self.crossings.iter().enumerate() {
+            inside(*pos, format!("crossings[{}]", i))?;
+
+            if self.tiles[pos.as_array()].get_sides().iter().any(|(_, wall)| *wall) {
+                return Err(MazeError::WalledCrossing(*pos));
+            }
+
+            if Direction::iter().any(|direction| self.get_neighbor(*pos, direction).is_none()) {
+                return Err(MazeError::BorderCrossing(*pos));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The maze as JSON, without a solution or generator, see `to_json_with`.
+    pub fn to_json(&self) -> String {
+        self.to_json_with(None, None)
+    }
+
+    /// The maze as JSON on a single line, for example for a 2x1 maze
+    /// `{"version":1,"width":2,"height":1,"cells":[{"up":true,"right":false,"down":true,
+    /// "left":true},{"up":true,"right":true,"down":true,"left":false}],"waypoints":[],
+    /// "crossings":[],"solution":null,"generator":null}`.
+    ///
+    /// - `version` is 1 and only goes up for changes that would break existing readers.
+    ///   Fields may be added without it.
+    /// - `cells` has every cell row by row from the top left, `width` to a row. `up`,
+    ///   `right`, `down` and `left` are true where there is a wall, like the fields of `Tile`.
+    /// - `waypoints` are `{"name":..,"x":..,"y":..}` in the order they were added.
+    /// - `crossings` are `{"x":..,"y":..,"under":..}`, with `under` `"horizontal"` or
+    ///   `"vertical"` for the passage running underneath, see `Maze::crossings`.
+    /// - `solution` is a list of `{"x":..,"y":..}` cells or `null`.
+    /// - `generator` is `{"algorithm":..,"seed":..}` or `null`, each of its fields `null`
+    ///   when unknown.
+    ///
+    /// Coordinates are maze cells like in `Solution::to_json`, `x` counted from the left and
+    /// `y` from the top.
+    pub fn to_json_with(&self, solution: Option<&[Position]>, generator: Option<&GenerationInfo>) -> String {
+        let point = |pos: &Position| format!("{{\"x\":{},\"y\":{}}}", pos.0, pos.1);
+
+        let cells: Vec<String> = (0..self.size.1)
+            .flat_map(|y| (0..self.size.0).map(move |x| Position(x, y)))
+            .map(|pos| {
+                let tile = self.tiles[pos.as_array()];
+
+                format!(
+                    "{{\"up\":{},\"right\":{},\"down\":{},\"left\":{}}}",
+                    tile.up, tile.right, tile.down, tile.left
+                )
+            })
+            .collect();
+
+        let waypoints: Vec<String> = self.waypoints.iter()
+            .map(|(name, pos)| format!("{{\"name\":{},\"x\":{},\"y\":{}}}", json_string(name), pos.0, pos.1))
+            .collect();
+
+        let crossings: Vec<String> = self.crossings.iter()
+            .map(|(pos, axis)| format!(
+                "{{\"x\":{},\"y\":{},\"under\":\"{}\"}}",
+                pos.0,
+                pos.1,
+                if *axis == Axis(0) { "horizontal" } else { "vertical" }
+            ))
+            .collect();
+
+        let solution = solution.map_or("null".to_string(), |path| {
+            format!("[{}]", path.iter().map(point).collect::<Vec<String>>().join(","))
+        });
+
+        let generator = generator.map_or("null".to_string(), |info| format!(
+            "{{\"algorithm\":{},\"seed\":{}}}",
+            info.algorithm.as_deref().map_or("null".to_string(), json_string),
+            info.seed.map_or("null".to_string(), |x| x.to_string())
+        ));
+
+        format!(
+            "{{\"version\":1,\"width\":{},\"height\":{},\"cells\":[{}],\"waypoints\":[{}],\"crossings\":[{}],\"solution\":{},\"generator\":{}}}",
+            self.size.0,
+            self.size.1,
+            cells.join(","),
+            waypoints.join(","),
+            crossings.join(","),
+            solution,
+            generator
+        )
+    }
+}
+
+/// `s` as a quoted JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+/// A parsed JSON document. Objects keep their keys in order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+impl JsonValue {
+    pub fn parse(text: &str) -> Result<JsonValue, io::ErrorKind> {
+        let mut parser = Parser {
+            chars: text.chars().peekable(),
+            depth: 0,
+        };
+
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+
+        match parser.chars.next() {
+            Some(_) => Err(io::ErrorKind::InvalidData),
+            None => Ok(value),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            Self::Object(fields) => fields.iter()
+                .find(|(x, _)| x == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// The value if it is a whole, non-negative number.
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Self::Number(x) if *x >= 0.0 && x.fract() == 0.0 => Some(*x as usize),
+            _ => None,
+        }
+    }
+}
+
+// Arrays and objects nested deeper than this are turned down rather than parsed on an
+// ever deeper stack
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    // Arrays and objects the parser is inside of
+    depth: usize,
+}
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), io::ErrorKind> {
+        self.skip_whitespace();
+
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(io::ErrorKind::InvalidData),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, io::ErrorKind> {
+        self.skip_whitespace();
+
+        match self.chars.peek().ok_or(io::ErrorKind::InvalidData)? {
+            '{' | '[' if self.depth == MAX_DEPTH => Err(io::ErrorKind::InvalidData),
+            '{' => self.nested(Self::parse_object),
+            '[' => self.nested(Self::parse_array),
+            '"' => Ok(JsonValue::String(self.parse_string()?)),
+            't' => self.parse_literal("true", JsonValue::Bool(true)),
+            'f' => self.parse_literal("false", JsonValue::Bool(false)),
+            'n' => self.parse_literal("null", JsonValue::Null),
+            _ => self.parse_number(),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<JsonValue, io::ErrorKind>) -> Result<JsonValue, io::ErrorKind> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+
+        value
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, io::ErrorKind> {
+        for expected in literal.chars() {
+            if self.chars.next() != Some(expected) {
+                return Err(io::ErrorKind::InvalidData);
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, io::ErrorKind> {
+        let mut text = String::new();
+
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+            text.push(c);
+        }
+
+        text.parse()
+            .map(JsonValue::Number)
+            .map_err(|_| io::ErrorKind::InvalidData)
+    }
+
+    fn parse_string(&mut self) -> Result<String, io::ErrorKind> {
+        self.expect('"')?;
+
+        let mut out = String::new();
+
+        loop {
+            match self.chars.next().ok_or(io::ErrorKind::InvalidData)? {
+                '"' => return Ok(out),
+                '\\' => out.push(match self.chars.next().ok_or(io::ErrorKind::InvalidData)? {
+                    '"' => '"',
+                    '\\' => '\\',
+                    '/' => '/',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => {
+                        let code: String = self.chars.by_ref().take(4).collect();
+
+                        u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or(io::ErrorKind::InvalidData)?
+                    },
+                    _ => return Err(io::ErrorKind::InvalidData),
+                }),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, io::ErrorKind> {
+        self.expect('[')?;
+        self.skip_whitespace();
+
+        let mut values = vec![];
+
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(JsonValue::Array(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(JsonValue::Array(values)),
+                _ => return Err(io::ErrorKind::InvalidData),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, io::ErrorKind> {
+        self.expect('{')?;
+        self.skip_whitespace();
+
+        let mut fields = vec![];
+
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(JsonValue::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            let key = self.parse_string()?;
+            self.expect(':')?;
+
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(JsonValue::Object(fields)),
+                _ => return Err(io::ErrorKind::InvalidData),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossings_on_the_border_are_turned_down() {
+        let mut maze = Maze::new(Size(3, 3), false);
+        maze.crossings.push((Position(1, 1), Axis(0)));
+
+        assert!(Maze::from_json(&maze.to_json()).is_ok());
+
+        maze.crossings.push((Position(0, 1), Axis(0)));
+
+        assert_eq!(Maze::from_json(&maze.to_json()), Err(MazeError::BorderCrossing(Position(0, 1))));
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        let text = "[".repeat(100_000) + &"]".repeat(100_000);
+
+        assert_eq!(JsonValue::parse(&text), Err(io::ErrorKind::InvalidData));
+        assert!(JsonValue::parse(&("[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH))).is_ok());
+    }
+
+    #[test]
+    fn huge_sizes_are_an_error() {
+        let text = format!(r#"{{"version":1,"width":{},"height":{},"cells":[]}}"#, usize::MAX, 2);
+
+        assert_eq!(Maze::from_json(&text), Err(MazeError::Io(io::ErrorKind::InvalidData)));
+    }
+}
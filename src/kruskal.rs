@@ -0,0 +1,135 @@
+use ndarray::Array2;
+use rand::RngCore;
+
+use crate::rng::random_index;
+use crate::{Direction, Maze, Position, Size};
+
+/// Union-find over the cells of a grid with path halving and union by rank, so merging and
+/// looking up sets takes close to constant time.
+#[derive(Debug, Clone)]
+pub struct DisjointSets {
+    parents: Array2<Position>,
+    ranks: Array2<u8>,
+}
+impl DisjointSets {
+    /// Every cell in a set of its own.
+    pub fn new(size: Size) -> Self {
+        Self {
+            parents: Array2::from_shape_fn(size.as_array(), |(x, y)| Position(x, y)),
+            ranks: Array2::zeros(size.as_array()),
+        }
+    }
+
+    /// The cell representing the set `pos` is in.
+    pub fn find(&mut self, mut pos: Position) -> Position {
+        while self.parents[pos.as_array()] != pos {
+            let grandparent = self.parents[self.parents[pos.as_array()].as_array()];
+            self.parents[pos.as_array()] = grandparent;
+
+            pos = grandparent;
+        }
+
+        pos
+    }
+
+    /// Merges the sets of `a` and `b`, returning false if they already were one set.
+    pub fn union(&mut self, a: Position, b: Position) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        let (root, child) = match self.ranks[a.as_array()] < self.ranks[b.as_array()] {
+            true => (b, a),
+            false => (a, b),
+        };
+
+        self.parents[child.as_array()] = root;
+
+        if self.ranks[root.as_array()] == self.ranks[child.as_array()] {
+            self.ranks[root.as_array()] += 1;
+        }
+
+        true
+    }
+}
+
+impl Maze {
+    pub fn generate_kruskal(&mut self) {
+        self.generate_kruskal_with_rng(&mut rand::rng());
+    }
+
+    /// Opens the interior walls in random order, skipping every wall whose two cells are
+    /// already connected. The result is a perfect maze with many short dead ends instead of
+    /// the backtracker's long corridors.
+    pub fn generate_kruskal_with_rng<R: RngCore>(&mut self, rng: &mut R) {
+        let mut walls = vec![];
+
+        for ((x, y), _) in self.tiles.indexed_iter() {
+            for direction in [Direction::East, Direction::South] {
+                if self.get_neighbor(Position(x, y), direction).is_some() {
+                    walls.push((Position(x, y), direction));
+                }
+            }
+        }
+
+        for i in 0..walls.len() {
+            let other = i + random_index(rng, walls.len() - i);
+            walls.swap(i, other);
+        }
+
+        let mut sets = DisjointSets::new(self.size);
+
+        for (pos, direction) in walls {
+            if sets.union(pos, pos.translate(direction)) {
+                self.set_wall(pos, direction, false).unwrap();
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisjointSets;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+
+    #[test]
+    fn every_size_is_perfect_and_solvable() {
+        for size in [Size(1, 1), Size(2, 5), Size(5, 2), Size(2, 2), Size(1, 7), Size(7, 1), Size(13, 9)] {
+            for seed in 0..5 {
+                let mut maze = Maze::new(size, true);
+                maze.generate_kruskal_with_rng(&mut Pcg32::new(seed));
+
+                let path = maze.solve_maze_with_rng(&mut Pcg32::new(seed));
+                assert_eq!((path[0], path[path.len() - 1]), (Position(0, 0), size.get_max_pos()), "{size:?} seed {seed}");
+
+                // A tree: every cell reachable through one passage fewer than there are cells
+                let cells = size.0 * size.1;
+                let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+                let open: usize = maze.tiles.iter()
+                    .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+                    .sum();
+
+                assert_eq!((reached, open / 2), (cells, cells - 1), "{size:?} seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn sets_merge_once() {
+        let mut sets = DisjointSets::new(Size(3, 3));
+
+        assert!(sets.union(Position(0, 0), Position(1, 0)));
+        assert!(sets.union(Position(2, 2), Position(1, 0)));
+        assert!(!sets.union(Position(0, 0), Position(2, 2)));
+
+        assert_eq!(sets.find(Position(0, 0)), sets.find(Position(2, 2)));
+        assert_ne!(sets.find(Position(0, 0)), sets.find(Position(1, 1)));
+    }
+}
@@ -0,0 +1,1305 @@
+#![feature(iter_collect_into)]
+
+use ndarray::*;
+use rand::prelude::*;
+use rand::rng;
+use std::io::{self, IsTerminal, Write};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+pub mod animation;
+pub mod arrows;
+pub mod avoid;
+pub mod axes;
+pub mod batch;
+pub mod binary;
+pub mod box_drawing;
+pub mod braid;
+pub mod braille;
+pub mod cave;
+pub mod check;
+pub mod compose;
+pub mod consistency;
+pub mod curves;
+pub mod difficulty;
+pub mod diff;
+pub mod distance;
+pub mod eller;
+pub mod endpoints;
+pub mod events;
+pub mod exits;
+pub mod extend;
+pub mod fingerprint;
+pub mod footer;
+pub mod formats;
+pub mod generator;
+pub mod grid;
+pub mod growing_tree;
+pub mod halfblock;
+pub mod heatmap;
+pub mod html;
+pub mod infinite;
+pub mod json;
+pub mod kruskal;
+pub mod mask;
+pub mod metrics;
+pub mod mouse;
+pub mod overlay;
+pub mod pledge;
+#[cfg(feature = "png")]
+pub mod png;
+pub mod rng;
+pub mod rooms;
+pub mod route;
+pub mod save;
+pub mod scale;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod sidewinder;
+pub mod simple_paths;
+pub mod solution;
+pub mod solver;
+pub mod sparse;
+pub mod steps;
+pub mod svg;
+pub mod symmetry;
+pub mod terminal;
+#[cfg(test)]
+mod test_support;
+pub mod tremaux;
+pub mod unicursal;
+pub mod viewport;
+pub mod walk;
+pub mod waypoints;
+pub mod weave;
+pub mod wilson;
+
+use events::{CarveAction, CarveEvent};
+
+pub const BLOCK_CHAR: char = '█';
+pub const POINT_CHAR: char = '•';
+pub const EMPTY_CHAR: char = ' ';
+pub const INCOMPLETE_CHAR: char = '×';
+pub const CROSSING_CHAR: char = '┼';
+
+// Which side of an end cell on the border its marker goes on, in order of preference. These
+// put the markers of the usual corners above the start and below the goal.
+const START_MARKER_SIDES: [Direction; 4] = [Direction::North, Direction::West, Direction::South, Direction::East];
+const GOAL_MARKER_SIDES: [Direction; 4] = [Direction::South, Direction::East, Direction::North, Direction::West];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tile {
+    pub up: bool,
+    pub right: bool,
+    pub down: bool,
+    pub left: bool
+}
+impl Tile {
+    pub fn new(walled: bool) -> Self {
+        Self {
+            up: walled,
+            right: walled, 
+            down: walled,
+            left: walled,
+        }
+    }
+
+    pub fn set_side(&mut self, direction: Direction, closed: bool) {
+        match direction {
+            Direction::North => self.up = closed,
+            Direction::East => self.right = closed,
+            Direction::South => self.down = closed,
+            Direction::West => self.left = closed,
+        };
+    }
+
+    pub fn get_side(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::North => self.up,
+            Direction::East => self.right,
+            Direction::South => self.down,
+            Direction::West => self.left,
+        }
+    }
+
+    fn get_mut_sides(&mut self) -> [(Direction, bool); 4] {
+        [
+            (Direction::North, self.up),
+            (Direction::East, self.right),
+            (Direction::South, self.down),
+            (Direction::West, self.left),
+        ]
+    }
+
+    pub fn get_sides(&self) -> [(Direction, bool); 4] {
+        let mut mut_self: Self = Self::new(false);
+        self.clone_into(&mut mut_self);
+        mut_self.get_mut_sides()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "serialize::RawMaze", try_from = "serialize::RawMaze"))]
+pub struct Maze {
+    pub size: Size,
+    pub tiles: Array2<Tile>,
+    /// Named cells in the order they were added, see `Maze::set_waypoint`.
+    pub waypoints: Vec<(String, Position)>,
+    /// Cells where one straight passage runs under another, with the axis of the lower one.
+    /// All four walls of a crossing are open, but a walk through it can't turn.
+    pub crossings: Vec<(Position, Axis)>,
+}
+impl Maze {
+    pub fn new(size: Size, walled: bool) -> Self {
+        Self {
+            size,
+            tiles: Array2::from_elem(size.as_array(), Tile::new(walled)),
+            waypoints: vec![],
+            crossings: vec![],
+        }
+    }
+
+    pub fn generate_maze(&mut self) {
+        self.generate_maze_with_rng(&mut rng());
+    }
+
+    /// Like `generate_maze`, drawing every choice from `rng`, so the same generator state and
+    /// size always carve the same walls.
+    pub fn generate_maze_with_rng<R: RngCore>(&mut self, rng: &mut R) {
+        self.generate_backtracker(rng, None);
+    }
+
+    /// Like `generate_maze`, growing the spanning tree from `start` instead of the top left
+    /// corner. Fails if `start` is outside the maze.
+    pub fn generate_maze_from(&mut self, start: Position) -> Result<(), io::ErrorKind> {
+        self.generate_backtracker_from(start, 0.0, &mut rng(), None)
+    }
+
+    pub fn generate_maze_seeded(&mut self, seed: u64) {
+        self.generate_maze_with_rng(&mut rng::Pcg32::new(seed));
+    }
+
+    fn generate_backtracker<R: RngCore>(&mut self, rng: &mut R, log: Option<&mut Vec<CarveEvent>>) {
+        self.generate_backtracker_from(Position::new(), 0.0, rng, log)
+            .unwrap();
+    }
+
+    // `straightness` is the chance of carving on in the direction of the last carve when
+    // that's possible. Right after backtracking there is no such direction.
+    fn generate_backtracker_from<R: RngCore>(
+        &mut self,
+        start: Position,
+        straightness: f64,
+        rng: &mut R,
+        mut log: Option<&mut Vec<CarveEvent>>
+    ) -> Result<(), io::ErrorKind> {
+        if self.get_tile(start).is_none() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let mut explored = Array2::from_elem(self.size.as_array(), false);
+        explored[start.as_array()] = true;
+        
+        let mut stack = vec![start];
+
+        let mut heading = None;
+        
+        let mut currentpos = start;
+        
+        // A dead end carries on from the cell popped off the stack, which is the current one
+        // the first time, until popping the start empties the stack.
+        loop {
+            let dirs = self.get_valid_directions(currentpos, &explored);
+            
+            if dirs.is_empty() {
+                let previous = currentpos;
+                currentpos = stack.pop().unwrap();
+                heading = None;
+
+                if let Some(log) = log.as_deref_mut() {
+                    if currentpos != previous {
+                        log.push(CarveEvent::new(log.len(), currentpos, None, CarveAction::Backtrack));
+                    }
+                }
+
+                // Back at the start, which can still have neighbours left when it isn't a corner
+                if stack.is_empty() {
+                    if self.get_valid_directions(start, &explored).is_empty() {
+                        break;
+                    }
+
+                    stack.push(start);
+                }
+            } else {
+                // Skipping the roll at 0 keeps seeded mazes the same as without the option
+                let straight = heading.filter(|direction| {
+                    straightness > 0.0 && dirs.contains(direction) && rng::chance(rng, straightness)
+                });
+
+                let pick = match straight {
+                    Some(direction) => direction,
+                    None => rng::pick(rng, &dirs).unwrap(),
+                };
+
+                heading = Some(pick);
+
+                if let Some(log) = log.as_deref_mut() {
+                    log.push(CarveEvent::new(log.len(), currentpos, Some(pick), CarveAction::Carve));
+                }
+
+                self.get_mut_tile(currentpos)
+                    .unwrap()
+                    .set_side(pick, false);
+                
+                currentpos = currentpos.translate(pick);
+                
+                self.get_mut_tile(currentpos)
+                    .unwrap()
+                    .set_side(
+                        pick.get_opposite(),
+                        false
+                    );
+
+
+                stack.push(currentpos);
+                explored[currentpos.as_array()] = true;
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    fn get_valid_directions(&self, pos: Position, explored: &Array2<bool>) -> Vec<Direction> {
+        let mut invalid = vec![];
+        
+        if pos.0 == 0 {
+            invalid.push(Direction::West);
+        }
+
+        if pos.0 == self.size.get_max_pos().0 {
+            invalid.push(Direction::East);
+        }
+        
+        if pos.1 == 0 {
+            invalid.push(Direction::North);
+        }
+
+        if pos.1 == self.size.get_max_pos().1 {
+            invalid.push(Direction::South);
+        }
+
+        
+        let mut out = vec![];
+        
+        for direction in Direction::iter() {
+            if !(
+                invalid.contains(&direction) ||
+                explored[pos.translate(direction).as_array()]
+            ) {
+                out.push(direction);
+            }
+        }
+        
+        out
+    }
+
+    fn get_valid_moves(&self, pos: Position, explored: &Array2<bool>) -> Vec<Direction> {
+        let mut out = vec![];
+        
+        let invalid: Vec<Direction> = self
+            .get_tile(pos)
+            .unwrap()
+            .get_sides().iter()
+            .filter_map(
+                |(a,b)| if *b {
+                    Some(*a)
+                } else { None }
+            ).collect();
+
+        
+        for direction in Direction::iter() {
+            // Crossings are passed straight through, so what counts is the cell beyond
+            let Some(neighbor) = self.beyond_crossings(pos, direction) else {
+                continue;
+            };
+
+            if !(
+                invalid.contains(&direction) ||
+                explored[neighbor.as_array()]
+            ) {
+                out.push(direction);
+            }
+        }
+        
+        out
+    }
+
+    pub fn solve_maze(&self) -> Vec<Position> {
+        self.solve_maze_with_rng(&mut rng())
+    }
+
+    /// Like `solve_maze`, with the order moves are tried in drawn from `rng`.
+    pub fn solve_maze_with_rng<R: RngCore + ?Sized>(&self, rng: &mut R) -> Vec<Position> {
+        self.solve_dfs(Position::new(), self.size.get_max_pos(), rng, &mut 0)
+            .expect("The corners aren't connected")
+    }
+
+    /// Like `solve_maze`, from `start` to `goal` instead of between the corners. A path
+    /// from a cell to itself is just that cell. Fails if either end is outside the maze, or
+    /// with `NotFound` if they aren't connected.
+    pub fn solve_between(&self, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_between_with_rng(start, goal, &mut rng())
+    }
+
+    /// Like `solve_between`, with the order moves are tried in drawn from `rng`.
+    pub fn solve_between_with_rng<R: RngCore + ?Sized>(&self, start: Position, goal: Position, rng: &mut R) -> Result<Vec<Position>, io::ErrorKind> {
+        if self.get_tile(start).is_none() || self.get_tile(goal).is_none() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        self.solve_dfs(start, goal, rng, &mut 0)
+            .ok_or(io::ErrorKind::NotFound)
+    }
+
+    // Adds the steps the search took to `expanded`, see `Solver::solve_counted`
+    pub(crate) fn solve_dfs<R: RngCore + ?Sized>(&self, start: Position, goal: Position, rng: &mut R, expanded: &mut usize) -> Option<Vec<Position>> {
+        let mut search = DfsSearch::new(self, start, goal);
+
+        while search.step(self, rng).is_some() {
+            *expanded += 1;
+        }
+
+        search.path()
+    }
+
+    pub fn to_display_pos(pos: Position) -> Position {
+        Position::from_array(
+            pos.as_array()
+            .map( |x| x * 2 + 1 )
+        )
+    }
+
+    pub fn get_tile(&self, pos: Position) -> Option<&Tile> {
+        self.tiles.get(pos.as_array())
+    }
+
+    pub fn get_mut_tile(&mut self, pos: Position) -> Option<&mut Tile> {
+        self.tiles.get_mut(pos.as_array())
+    }
+
+    pub fn get_neighbor(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let neighbor = match direction {
+            Direction::North => Position(pos.0, pos.1.checked_sub(1)?),
+            Direction::West => Position(pos.0.checked_sub(1)?, pos.1),
+            _ => pos.translate(direction),
+        };
+
+        self.get_tile(neighbor).map(|_| neighbor)
+    }
+}
+
+// Where the depth first search of `solve_maze` is between steps, see `steps::SolveSteps`.
+// The path is kept from the start to the cell the search is at, crossings included.
+pub(crate) struct DfsSearch {
+    goal: Position,
+    explored: Array2<bool>,
+    path: Vec<Position>,
+}
+impl DfsSearch {
+    pub(crate) fn new(maze: &Maze, start: Position, goal: Position) -> Self {
+        let mut explored = Array2::from_elem(maze.size.as_array(), false);
+        explored[start.as_array()] = true;
+
+        Self {
+            goal,
+            explored,
+            path: vec![start],
+        }
+    }
+
+    // Moves on to a random unexplored cell, or backs up out of a dead end. Returns the cell
+    // it ends up at and the cells it stepped onto, `None` once the goal is reached or
+    // there is nowhere left to back up to.
+    pub(crate) fn step<R: RngCore + ?Sized>(&mut self, maze: &Maze, rng: &mut R) -> Option<(Position, Vec<Position>)> {
+        let current = *self.path.last()?;
+
+        if current == self.goal {
+            return None;
+        }
+
+        let moves = maze.get_valid_moves(current, &self.explored);
+
+        let Some(direction) = rng::pick(rng, &moves) else {
+            self.path.pop();
+
+            while self.path.last().is_some_and(|pos| maze.is_crossing(*pos)) {
+                self.path.pop();
+            }
+
+            return Some((*self.path.last()?, vec![]));
+        };
+
+        let mut entered = vec![];
+        let mut pos = current.translate(direction);
+
+        // Crossings are passed straight through
+        while maze.is_crossing(pos) {
+            entered.push(pos);
+            pos = pos.translate(direction);
+        }
+
+        entered.push(pos);
+        self.path.extend(&entered);
+        self.explored[pos.as_array()] = true;
+
+        Some((pos, entered))
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        self.path.clone()
+    }
+
+    pub(crate) fn path(&self) -> Option<Vec<Position>> {
+        (self.path.last() == Some(&self.goal)).then(|| self.path.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, strum_macros::Display)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+impl Direction {
+    pub fn get_axis(&self) -> Axis {
+        match self {
+            Self::East | Self::West => Axis(0),
+            Self::North | Self::South => Axis(1),
+        }
+    }
+
+    pub fn get_opposite(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// The direction a quarter turn clockwise.
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.turn_right().get_opposite()
+    }
+
+    pub fn get_perpendiculars(&self) -> [Self; 2] {
+        match self.get_axis() {
+            Axis(0) => [Self::North, Self::South],
+            Axis(1) => [Self::East, Self::West],
+            _ => panic!("Higher Axis"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position(pub usize, pub usize);
+impl Position {
+    pub fn new() -> Self {
+        Self(0, 0)
+    }
+
+    pub fn as_array(&self) -> [usize; 2] {
+        [self.0, self.1]
+    }
+
+    pub fn as_rev_array(&self) -> [usize; 2] {
+        [self.1, self.0]
+    }
+    
+    pub fn from_array(arr: [usize; 2]) -> Self {
+        Self(arr[0], arr[1])
+    }
+
+    pub fn translate(&self, direction: Direction) -> Self {
+        let mut out = *self;
+        
+        match direction {
+            Direction::North => out.1 -= 1,
+            Direction::East => out.0 += 1,
+            Direction::South => out.1 += 1,
+            Direction::West => out.0 -= 1,
+        };
+        
+        out
+    }
+
+    pub fn from_size(size: Size) -> Self {
+        Self(size.0, size.1)
+    }
+}
+impl Default for Position {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Size(pub usize, pub usize);
+impl Size {
+    pub fn as_array(&self) -> [usize; 2] {
+        [self.0, self.1]
+    }
+
+    pub fn as_rev_array(&self) -> [usize; 2] {
+        [self.1, self.0]
+    }
+
+    pub fn from_array(arr: [usize; 2]) -> Self {
+        Self(arr[0], arr[1])
+    }
+
+    pub fn get_max_pos(&self) -> Position {
+        Position(self.0 - 1, self.1 - 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Vector {
+    pub origin: Position,
+    pub direction: Direction,
+    pub magnitude: usize,
+}
+impl Vector {
+    pub fn new(origin: Position, direction: Direction, magnitude: usize) -> Self {
+        Vector {
+            origin,
+            direction,
+            magnitude,
+        }
+    }
+
+    pub fn new_from_points(origin: Position, end: Position) -> Result<Self, io::ErrorKind> {
+        let mut magnitude: Vec<isize> = vec![];
+        end.as_array()
+            .iter()
+            .zip(origin.as_array())
+            .map(|(a,b)| *a as isize - b as isize)
+            .collect_into(&mut magnitude);
+
+        
+        let mut unit: Vec<isize> = vec![];
+        magnitude.iter()
+            .map(|x| x.signum())
+            .collect_into(&mut unit);
+        
+        let unit = (unit[0], unit[1]);
+
+        
+        let direction = match unit {
+            (0,-1) => Ok(Direction::North),
+            (1,0) => Ok(Direction::East),
+            (0,1) => Ok(Direction::South),
+            (-1,0) => Ok(Direction::West),
+            _ => Err(io::ErrorKind::InvalidInput)
+        }?;
+
+        let magnitude: usize = magnitude.iter()
+            .map(|x| x.unsigned_abs())
+            .reduce(|a,b| a+b)
+            .unwrap() + 1;
+        
+        Ok(Self::new(origin, direction, magnitude))
+    }
+
+    pub fn get_end(&self) -> Position {
+        let origin = self.origin;
+        
+        let magnitude = self.magnitude - 1;
+    
+        match self.direction {
+            Direction::North => Position(
+                origin.0, 
+                origin.1 - magnitude
+            ),
+            Direction::East => Position(
+                origin.0 + magnitude,
+                origin.1
+            ),
+            Direction::South => Position(
+                origin.0,
+                origin.1 + magnitude
+            ),
+            Direction::West => Position(
+                origin.0 - magnitude, 
+                origin.1
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle {
+    pub origin: Position,
+    pub size: Size,
+}
+impl Rectangle {
+    pub fn new(origin: Position, size: Size) -> Self {
+        Rectangle {
+            origin,
+            size,
+        }
+    }
+
+    pub fn get_vectors(&self) -> [Vector; 4] {
+        let max_pos = Position::from_size(self.size);
+        
+        let right = Vector::new(
+            self.origin,
+            Direction::East,
+            max_pos.0
+        );
+        
+        let down = Vector::new(
+            self.origin,
+            Direction::South,
+            max_pos.1
+        );
+        
+        [
+            right,
+            down,
+            Vector::new(
+                down.get_end(),
+                Direction::East,
+                max_pos.0
+            ),
+            Vector::new(
+                right.get_end(),
+                Direction::South,
+                max_pos.1
+            ),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Margins {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+impl Margins {
+    pub fn none() -> Self {
+        Self {
+            top: 0,
+            left: 0,
+            bottom: 0,
+            right: 0,
+        }
+    }
+}
+impl Default for Margins {
+    fn default() -> Self {
+        Self {
+            top: 1,
+            left: 1,
+            bottom: 0,
+            right: 0,
+        }
+    }
+}
+
+/// Terminal colors a `Display` can print its pixels in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+impl Color {
+    /// The ANSI escape sequence that switches the text to this color.
+    pub fn ansi(&self) -> String {
+        format!("\x1b[{}m", 30 + *self as u8)
+    }
+
+    /// The ANSI escape sequence that switches the background to this color.
+    pub fn ansi_background(&self) -> String {
+        format!("\x1b[{}m", 40 + *self as u8)
+    }
+}
+
+/// What `draw_solution` colors the path and its end markers with.
+pub const PATH_COLOR: Color = Color::Yellow;
+pub const ENDPOINT_COLOR: Color = Color::Green;
+
+const RESET_COLOR: &str = "\x1b[0m";
+
+/// The characters a `Display` is written with. Drawing still uses `BLOCK_CHAR`,
+/// `POINT_CHAR` and `EMPTY_CHAR`, which stand for the wall, path and empty characters here
+/// when the display is written out. `draw_solution` marks the ends with `start` and `goal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Charset {
+    pub wall: char,
+    pub path: char,
+    pub empty: char,
+    pub start: char,
+    pub goal: char,
+}
+impl Default for Charset {
+    fn default() -> Self {
+        Self {
+            wall: BLOCK_CHAR,
+            path: POINT_CHAR,
+            empty: EMPTY_CHAR,
+            start: POINT_CHAR,
+            goal: POINT_CHAR,
+        }
+    }
+}
+impl Charset {
+    /// Fails if any of the characters wouldn't take up a column of its own, see
+    /// `is_drawable`.
+    pub fn validate(&self) -> Result<(), io::ErrorKind> {
+        match [self.wall, self.path, self.empty, self.start, self.goal].into_iter().all(Self::is_drawable) {
+            true => Ok(()),
+            false => Err(io::ErrorKind::InvalidInput),
+        }
+    }
+
+    /// Whether `symbol` can be a pixel: not a control character, nor one that combines with
+    /// the character before it or has no width at all.
+    pub fn is_drawable(symbol: char) -> bool {
+        let combining = matches!(
+            symbol,
+            '\u{0300}'..='\u{036f}'
+                | '\u{1ab0}'..='\u{1aff}'
+                | '\u{1dc0}'..='\u{1dff}'
+                | '\u{20d0}'..='\u{20ff}'
+                | '\u{fe00}'..='\u{fe0f}'
+                | '\u{fe20}'..='\u{fe2f}'
+        );
+
+        let zero_width = matches!(
+            symbol,
+            '\u{00ad}' | '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}'
+        );
+
+        !symbol.is_control() && !combining && !zero_width
+    }
+
+    // The character `pixel` is written as
+    fn apply(&self, pixel: char) -> char {
+        match pixel {
+            BLOCK_CHAR => self.wall,
+            POINT_CHAR => self.path,
+            EMPTY_CHAR => self.empty,
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Display {
+    pub margins: Margins,
+    pub pixels: Array2<char>,
+    /// The color of each pixel, `None` for the terminal's own.
+    pub colors: Array2<Option<Color>>,
+    /// Whether `write_to` prints the colors. `print` only does on a terminal.
+    pub color: bool,
+    pub charset: Charset,
+    /// Whether `write_to` prints every column twice, which makes cells about as wide as
+    /// they are high on most terminals.
+    pub wide: bool,
+    /// Lines of text written under the pixels, see `draw_footer`.
+    pub footer: Vec<String>,
+    pub size: Size,
+}
+impl Display {
+    pub fn new(size: Size) -> Display {
+        Display {
+            margins: Margins::default(),
+            pixels: Array2::from_elem(
+                size.as_rev_array(),
+                EMPTY_CHAR
+            ),
+            colors: Array2::from_elem(size.as_rev_array(), None),
+            color: false,
+            charset: Charset::default(),
+            wide: false,
+            footer: vec![],
+            size,
+        }
+    }
+
+    pub fn new_from_maze(maze: &Maze) -> Self {
+        Self::new(Self::size_for(maze))
+    }
+
+    /// The size a display needs to draw `maze`, see `draw_maze`.
+    pub fn size_for(maze: &Maze) -> Size {
+        Size::from_array(maze.size.as_array().map(|x| x * 2 + 1))
+    }
+
+    /// The biggest maze whose display fits in `area` columns and rows with `margins` around
+    /// it and `reserved_rows` left free below, for a footer or the prompt. A `wide` display
+    /// prints every column twice, so it gets half the columns. `None` if not even one cell
+    /// fits.
+    pub fn fit_maze(area: Size, margins: Margins, wide: bool, reserved_rows: usize) -> Option<Size> {
+        let columns = (area.0 / (wide as usize + 1)).checked_sub(margins.left + margins.right + 1)?;
+        let rows = area.1.checked_sub(margins.top + margins.bottom + reserved_rows + 1)?;
+
+        let size = Size(columns / 2, rows / 2);
+
+        match size.0 > 0 && size.1 > 0 {
+            true => Some(size),
+            false => None,
+        }
+    }
+
+    /// Fills every pixel with `EMPTY_CHAR`, so the display can be drawn on again without
+    /// allocating a new one.
+    pub fn clear(&mut self) {
+        self.fill(EMPTY_CHAR);
+    }
+
+    /// Sets every pixel to `symbol` and drops their colors.
+    pub fn fill(&mut self, symbol: char) {
+        self.pixels.fill(symbol);
+        self.colors.fill(None);
+    }
+
+    /// Clears the display and changes it to `size`. The pixels are only allocated again
+    /// if the size is different.
+    pub fn resize(&mut self, size: Size) {
+        if self.size == size {
+            self.clear();
+            return;
+        }
+
+        self.pixels = Array2::from_elem(size.as_rev_array(), EMPTY_CHAR);
+        self.colors = Array2::from_elem(size.as_rev_array(), None);
+        self.size = size;
+    }
+
+    /// Size of the printed block including margins and the footer.
+    pub fn get_outer_size(&self) -> Size {
+        Size(
+            self.margins.left + self.size.0 + self.margins.right,
+            self.margins.top + self.size.1 + self.wrap_footer(self.size.0 * (self.wide as usize + 1)).len() + self.margins.bottom
+        )
+    }
+
+    /// Writes the display to stdout, with colors only if they're set and stdout is a
+    /// terminal. Fails like any write, for example with `BrokenPipe` once the reader of a
+    /// pipe like `| head` has gone.
+    pub fn print(&self) -> io::Result<()> {
+        let color = self.color && io::stdout().is_terminal();
+        let mut stdout = io::BufWriter::new(io::stdout().lock());
+
+        self.write_with(&mut stdout, color)
+            .and_then(|_| stdout.flush())
+    }
+
+    /// Writes `render` to `w` one line at a time, without building the whole text first.
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_with(w, self.color)
+    }
+
+    /// The display as text, margins included, the way `print` shows it. Lines have no
+    /// trailing spaces, and colors are only in it if `color` is set.
+    pub fn render(&self) -> String {
+        let mut out = vec![];
+
+        self.write_to(&mut out)
+            .expect("Writing to a Vec can't fail");
+
+        String::from_utf8(out)
+            .expect("The display only writes chars")
+    }
+
+    fn write_with<W: io::Write>(&self, w: &mut W, color: bool) -> io::Result<()> {
+        self.write_window(w, color, viewport::Viewport::new(Position::new(), self.size))
+    }
+
+    // Writes the pixels inside `window`, which has to lie within the display, with the margins
+    // around them
+    fn write_window<W: io::Write>(&self, w: &mut W, color: bool, window: viewport::Viewport) -> io::Result<()> {
+        let (x, y) = (window.top_left.0, window.top_left.1);
+        let rows = s![y..y + window.size.1, x..x + window.size.0];
+
+        write!(w, "{}", "\n".repeat(self.margins.top))?;
+        
+        for (row, colors) in self.pixels.slice(rows).rows().into_iter().zip(self.colors.slice(rows).rows()) {
+            let mut rowstring = String::new();
+            let mut current = None;
+
+            let row: Vec<char> = row.iter().map(|pixel| self.charset.apply(*pixel)).collect();
+
+            // Nothing is written past the last pixel that isn't a space, right margin included
+            let end = row.iter().rposition(|pixel| *pixel != ' ').map_or(0, |i| i + 1);
+            
+            for (pixel, pixel_color) in row.iter().zip(colors).take(end) {
+                if color && *pixel_color != current {
+                    rowstring.push_str(&pixel_color.map_or(RESET_COLOR.to_string(), |x| x.ansi()));
+                    current = *pixel_color;
+                }
+
+                rowstring.push(*pixel);
+
+                if self.wide {
+                    rowstring.push(box_drawing::right_half(*pixel));
+                }
+            }
+
+            rowstring.truncate(rowstring.trim_end_matches(' ').len());
+
+            // Every line ends in the terminal's own color so nothing bleeds into the next
+            if current.is_some() {
+                rowstring.push_str(RESET_COLOR);
+            }
+
+            if end > 0 {
+                write!(w, "{}", " ".repeat(self.margins.left * (self.wide as usize + 1)))?;
+            }
+
+            writeln!(w, "{}", rowstring)?;
+        }
+
+        for line in self.wrap_footer(window.size.0 * (self.wide as usize + 1)) {
+            writeln!(w, "{}{}", " ".repeat(self.margins.left * (self.wide as usize + 1)), line)?;
+        }
+
+        write!(w, "{}", "\n".repeat(self.margins.bottom))?;
+
+        Ok(())
+    }
+
+    pub fn draw_line(&mut self, line: Vector, symbol: char) {
+        self.draw_line_colored(line, symbol, None);
+    }
+
+    pub fn draw_line_colored(&mut self, line: Vector, symbol: char, color: Option<Color>) {
+        let (start, end) = (line.origin, line.get_end());
+
+        match line.direction.get_axis() {
+            Axis(0) => {
+                for x in start.0.min(end.0)..=start.0.max(end.0) {
+                    self.draw_point_colored(Position(x, start.1), symbol, color);
+                }
+            },
+            Axis(1) => {
+                for y in start.1.min(end.1)..=start.1.max(end.1) {
+                    self.draw_point_colored(Position(start.0, y), symbol, color);
+                }
+            },
+            _ => panic!("Display dimensions too high!"),
+        }
+    }
+
+    pub fn draw_rect(&mut self, rectangle: Rectangle, symbol: char) {
+        for vector in rectangle.get_vectors() {
+            self.draw_line(vector, symbol);
+        }
+    }
+
+    /// Draws the walls of `maze` over the display, which has to be `size_for` the maze.
+    /// A display of any other size is left alone and fails, `resize` it first.
+    pub fn draw_maze(&mut self, maze: &Maze) -> Result<(), io::ErrorKind> {
+        if self.size == Self::size_for(maze) {
+            self.draw_rect(
+                Rectangle::new(
+                    Position::new(),
+                    self.size
+                ),
+                BLOCK_CHAR
+            );
+
+            
+            for ((x, y), tile) in maze.tiles.indexed_iter() {
+                let pos = Position(x,y);
+                let display_pos = Maze::to_display_pos(pos);
+
+                
+                for (direction, wall) in tile.get_sides() {
+                    if wall {
+                        let perpendicular = direction.get_perpendiculars()[0];
+                        
+                        self.draw_line(
+                            Vector::new(
+                                display_pos.translate(direction).translate(perpendicular),
+                                perpendicular.get_opposite(),
+                                3
+                            ),
+                            BLOCK_CHAR
+                        );
+                    }
+                }
+            }
+
+            for (pos, direction) in maze.get_border_openings() {
+                self.draw_point(Maze::to_display_pos(pos).translate(direction), EMPTY_CHAR);
+            }
+
+            // A crossing has no walls of its own, so its corners are filled in to keep the
+            // two passages from reading as one open room
+            for (pos, _) in &maze.crossings {
+                let center = Maze::to_display_pos(*pos);
+
+                for corner in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                    let corner = Position(
+                        center.0.checked_add_signed(corner.0).unwrap(),
+                        center.1.checked_add_signed(corner.1).unwrap()
+                    );
+
+                    self.draw_point(corner, BLOCK_CHAR);
+                }
+
+                self.draw_point(center, CROSSING_CHAR);
+            }
+            
+            return Ok(());
+        }
+        
+        Err(io::ErrorKind::InvalidInput)
+    }
+
+    pub fn draw_path(&mut self, path: Vec<Position>, symbol: char) -> Result<(), io::ErrorKind> {
+        self.draw_path_colored(path, symbol, None)
+    }
+
+    pub fn draw_path_colored(&mut self, path: Vec<Position>, symbol: char, color: Option<Color>) -> Result<(), io::ErrorKind> {
+        for vector in solution::Solution::new(path).to_vectors()? {
+            self.draw_line_colored(vector, symbol, color);
+        }
+        
+        Ok(())
+    }
+
+    pub fn draw_point(&mut self, pos: Position, symbol: char) {
+        self.draw_point_colored(pos, symbol, None);
+    }
+
+    pub fn draw_point_colored(&mut self, pos: Position, symbol: char, color: Option<Color>) {
+        self.pixels[pos.as_rev_array()] = symbol;
+        self.colors[pos.as_rev_array()] = color;
+    }
+
+    pub fn draw_solution(&mut self, path: &[Position], symbol: char) -> Result<(), io::ErrorKind> {
+        self.draw_path_colored(
+            path.iter()
+                .map(|x| Maze::to_display_pos(*x))
+                .collect(),
+            symbol,
+            Some(PATH_COLOR)
+        )?;
+
+        // The path character stands for the charset's own markers
+        let (start, goal) = match symbol {
+            POINT_CHAR => (self.charset.start, self.charset.goal),
+            _ => (symbol, symbol),
+        };
+
+        self.draw_end_markers(path, start, goal);
+
+        Ok(())
+    }
+
+    /// Marks the ends of the maze `path` with `start` and `goal`, in their border openings
+    /// or else on the outer wall next to them. Ends away from the border are marked on the
+    /// cell itself.
+    pub fn draw_end_markers(&mut self, path: &[Position], start: char, goal: char) {
+        let ends = [
+            (path.first(), START_MARKER_SIDES, Position(1,0), start),
+            (path.last(), GOAL_MARKER_SIDES, self.size.get_max_pos().translate(Direction::West), goal),
+        ];
+
+        for (end, sides, corner, symbol) in ends {
+            let marker = match end {
+                Some(pos) => {
+                    let display_pos = Maze::to_display_pos(*pos);
+
+                    self.get_border_opening(display_pos)
+                        .or_else(|| self.get_border_pixel(display_pos, sides))
+                        .unwrap_or(display_pos)
+                },
+                None => corner,
+            };
+
+            self.draw_point_colored(marker, symbol, Some(ENDPOINT_COLOR));
+        }
+    }
+
+    // The first pixel of the outer wall next to the cell drawn at `display_pos`, trying
+    // the sides in order
+    fn get_border_pixel(&self, display_pos: Position, sides: [Direction; 4]) -> Option<Position> {
+        let max = self.size.get_max_pos();
+
+        sides.into_iter()
+            .map(|direction| display_pos.translate(direction))
+            .find(|pos| pos.0 == 0 || pos.1 == 0 || pos.0 == max.0 || pos.1 == max.1)
+    }
+
+    // The first open pixel on the outer wall next to the cell drawn at `display_pos`
+    fn get_border_opening(&self, display_pos: Position) -> Option<Position> {
+        let max = self.size.get_max_pos();
+
+        Direction::iter()
+            .map(|direction| display_pos.translate(direction))
+            .filter(|pos| pos.0 == 0 || pos.1 == 0 || pos.0 == max.0 || pos.1 == max.1)
+            .find(|pos| self.pixels[pos.as_rev_array()] == EMPTY_CHAR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Pcg32;
+
+    // Two cells side by side with the wall between them open
+    fn corridor() -> Display {
+        let mut maze = Maze::new(Size(2, 1), true);
+        maze.open_wall(Position(0, 0), Direction::East).unwrap();
+
+        let mut display = Display::new_from_maze(&maze);
+        display.draw_maze(&maze).unwrap();
+        display
+    }
+
+    #[test]
+    fn displays_write_to_any_sink() {
+        let mut display = corridor();
+        display.margins = Margins::none();
+
+        let mut out = vec![];
+        display.write_to(&mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "█████\n█   █\n█████\n");
+        assert_eq!(display.render(), "█████\n█   █\n█████\n");
+
+        display.draw_solution(&[Position(0, 0), Position(1, 0)], POINT_CHAR).unwrap();
+        // The ends are marked on the outer wall next to them
+        assert_eq!(display.render(), "█•███\n█•••█\n███•█\n");
+    }
+
+    #[test]
+    fn default_margins_keep_the_old_layout() {
+        assert_eq!(corridor().render(), "\n █████\n █   █\n █████\n");
+    }
+
+    #[test]
+    fn margins_pad_every_side_once() {
+        let mut display = corridor();
+        display.margins = Margins { top: 0, left: 2, bottom: 1, right: 3 };
+
+        assert_eq!(display.render(), "  █████\n  █   █\n  █████\n\n");
+        assert_eq!(display.get_outer_size(), Size(10, 4));
+
+        display.wide = true;
+        assert!(display.render().starts_with("    ██████████\n"));
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_maze_and_solution() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let build = |seed| {
+            let mut maze = Maze::new(Size(14, 9), true);
+            maze.generate_maze_with_rng(&mut StdRng::seed_from_u64(seed));
+
+            let path = maze.solve_maze_with_rng(&mut StdRng::seed_from_u64(seed));
+
+            (maze, path)
+        };
+
+        assert_eq!(build(5), build(5));
+        assert_ne!(build(5).0, build(6).0);
+    }
+
+    #[test]
+    fn a_seeded_maze_keeps_its_rendering() {
+        // StdRng may change with rand versions, Pcg32 is ours to keep stable
+        let mut maze = Maze::new(Size(6, 4), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(7));
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution(&maze.solve_maze_with_rng(&mut Pcg32::new(7)), POINT_CHAR).unwrap();
+
+        let expected = [
+            "█•███████████",
+            "█•••••█  •••█",
+            "█████•███•█•█",
+            "█ █•••█•••█•█",
+            "█ █•███•███•█",
+            "█ █•█  •••█•█",
+            "█ █•█████•█•█",
+            "█  •••••••█•█",
+            "███████████•█",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn a_seeded_5x5_maze_keeps_its_text() {
+        let mut maze = Maze::new(Size(5, 5), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(5));
+
+        let mut display = Display::new_from_maze(&maze);
+        display.draw_maze(&maze).unwrap();
+
+        // The default margins put an empty line above and a column to the left
+        let expected = [
+            "",
+            " ███████████",
+            " █         █",
+            " █████████ █",
+            " █ █       █",
+            " █ █ ███████",
+            " █ █     █ █",
+            " █ █████ █ █",
+            " █     █ █ █",
+            " █ ███ █ █ █",
+            " █   █     █",
+            " ███████████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn trailing_spaces_are_trimmed() {
+        let mut display = corridor();
+        display.margins = Margins::none();
+        display.open_border(Position(1, 0), Direction::East).unwrap();
+
+        assert_eq!(display.render(), "█████\n█\n█████\n");
+    }
+}
@@ -0,0 +1,282 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::rng::pick;
+use crate::{Direction, Display, Maze, Position, Size, BLOCK_CHAR};
+
+/// Which cells of a maze belong to its shape, indexed like `Maze::tiles`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    pub cells: Array2<bool>,
+}
+impl Mask {
+    pub fn new(cells: Array2<bool>) -> Self {
+        Self { cells }
+    }
+
+    /// Every cell of a `size` grid.
+    pub fn full(size: Size) -> Self {
+        Self::new(Array2::from_elem(size.as_array(), true))
+    }
+
+    /// The cells whose centre lies in the ellipse touching all four sides of the grid, a
+    /// circle on square sizes.
+    pub fn circle(size: Size) -> Self {
+        let radius = (size.0 as f64 / 2.0, size.1 as f64 / 2.0);
+
+        Self::new(Array2::from_shape_fn(size.as_array(), |(x, y)| {
+            let dx = (x as f64 + 0.5 - radius.0) / radius.0;
+            let dy = (y as f64 + 0.5 - radius.1) / radius.1;
+
+            dx * dx + dy * dy <= 1.0
+        }))
+    }
+
+    pub fn size(&self) -> Size {
+        Size::from_array(self.cells.dim().into())
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        self.cells.get(pos.as_array()).copied().unwrap_or(false)
+    }
+
+    /// The first cell of the shape in reading order, top row first.
+    pub fn first(&self) -> Option<Position> {
+        self.positions().min_by_key(|pos| (pos.1, pos.0))
+    }
+
+    /// The last cell of the shape in reading order.
+    pub fn last(&self) -> Option<Position> {
+        self.positions().max_by_key(|pos| (pos.1, pos.0))
+    }
+
+    /// Whether every cell of the shape can be reached from every other through neighbours
+    /// in the shape. An empty mask isn't.
+    pub fn is_connected(&self) -> bool {
+        let Some(first) = self.first() else {
+            return false;
+        };
+
+        let mut seen = Array2::from_elem(self.cells.dim(), false);
+        let mut stack = vec![first];
+        let mut count = 0;
+
+        seen[first.as_array()] = true;
+
+        while let Some(pos) = stack.pop() {
+            count += 1;
+
+            for neighbor in self.neighbors(pos) {
+                if !seen[neighbor.as_array()] {
+                    seen[neighbor.as_array()] = true;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        count == self.positions().count()
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cells
+            .indexed_iter()
+            .filter(|(_, inside)| **inside)
+            .map(|((x, y), _)| Position(x, y))
+    }
+
+    fn neighbors(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::iter()
+            .filter_map(move |direction| {
+                let neighbor = match direction {
+                    Direction::North => Position(pos.0, pos.1.checked_sub(1)?),
+                    Direction::West => Position(pos.0.checked_sub(1)?, pos.1),
+                    _ => pos.translate(direction),
+                };
+
+                self.contains(neighbor).then_some(neighbor)
+            })
+    }
+}
+
+impl Maze {
+    pub fn generate_masked(&mut self, mask: &Mask) -> Result<(), io::ErrorKind> {
+        self.generate_masked_with_rng(mask, &mut rand::rng())
+    }
+
+    /// Carves a backtracker maze through the cells of `mask` only, starting at its first
+    /// cell. Cells outside it are never visited and stay fully walled. Fails if the mask
+    /// doesn't match the maze's size or its cells aren't all connected.
+    pub fn generate_masked_with_rng<R: RngCore>(&mut self, mask: &Mask, rng: &mut R) -> Result<(), io::ErrorKind> {
+        if mask.size() != self.size || !mask.is_connected() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let start = mask.first().unwrap();
+
+        let mut explored = Array2::from_elem(self.size.as_array(), false);
+        let mut stack = vec![start];
+
+        explored[start.as_array()] = true;
+
+        while let Some(pos) = stack.last().copied() {
+            let dirs = self.get_valid_masked_directions(pos, mask, &explored);
+
+            let Some(direction) = pick(rng, &dirs) else {
+                stack.pop();
+                continue;
+            };
+
+            let next = pos.translate(direction);
+
+            self.set_wall(pos, direction, false)?;
+            explored[next.as_array()] = true;
+
+            stack.push(next);
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    /// The shortest path from the first to the last cell of `mask`, the masked counterpart
+    /// of the corner to corner solution.
+    pub fn solve_masked(&self, mask: &Mask) -> Option<Vec<Position>> {
+        self.shortest_path(mask.first()?, mask.last()?)
+    }
+
+    /// Like `set_waypoint`, also refusing cells outside `mask` with `InvalidInput`, since
+    /// they're walled off from every other cell.
+    pub fn set_masked_waypoint(&mut self, name: &str, pos: Position, mask: &Mask) -> Result<(), io::ErrorKind> {
+        if !mask.contains(pos) {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        self.set_waypoint(name, pos)
+    }
+
+    // Like `get_valid_directions`, also refusing to leave the mask
+    fn get_valid_masked_directions(&self, pos: Position, mask: &Mask, explored: &Array2<bool>) -> Vec<Direction> {
+        Direction::iter()
+            .filter(|direction| {
+                self.get_neighbor(pos, *direction)
+                    .is_some_and(|neighbor| mask.contains(neighbor) && !explored[neighbor.as_array()])
+            })
+            .collect()
+    }
+}
+
+impl Display {
+    /// Fills the cells outside `mask` so they render as solid blocks. Call it after
+    /// `draw_maze`, which already draws all their walls.
+    pub fn draw_mask(&mut self, mask: &Mask) {
+        for ((x, y), inside) in mask.cells.indexed_iter() {
+            if !inside {
+                self.draw_point(Maze::to_display_pos(Position(x, y)), BLOCK_CHAR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mask;
+    use crate::rng::Pcg32;
+    use crate::{Display, Margins, Maze, Position, Size};
+    use ndarray::Array2;
+    use std::io;
+
+    // Rows of '#' for cells in the mask and '.' for the others
+    fn parse(rows: &[&str]) -> Mask {
+        Mask::new(Array2::from_shape_fn((rows[0].len(), rows.len()), |(x, y)| rows[y].as_bytes()[x] == b'#'))
+    }
+
+    #[test]
+    fn a_circle_leaves_out_the_corners() {
+        assert_eq!(Mask::circle(Size(5, 5)), parse(&[
+            ".###.",
+            "#####",
+            "#####",
+            "#####",
+            ".###.",
+        ]));
+        assert_eq!(Mask::circle(Size(5, 5)).first(), Some(Position(1, 0)));
+        assert_eq!(Mask::circle(Size(5, 5)).last(), Some(Position(3, 4)));
+    }
+
+    #[test]
+    fn islands_and_empty_masks_are_not_connected() {
+        assert!(parse(&["##.", ".##"]).is_connected());
+        assert!(!parse(&["#.#", "#.#"]).is_connected());
+        // Touching corners don't join cells
+        assert!(!parse(&["#.", ".#"]).is_connected());
+        assert!(!parse(&["..", ".."]).is_connected());
+    }
+
+    #[test]
+    fn masked_mazes_stay_inside_the_shape() {
+        let mask = Mask::circle(Size(9, 7));
+
+        for seed in 0..5 {
+            let mut maze = Maze::new(Size(9, 7), true);
+            maze.generate_masked_with_rng(&mask, &mut Pcg32::new(seed)).unwrap();
+
+            let distances = maze.distances_from(mask.first().unwrap());
+
+            for ((x, y), tile) in maze.tiles.indexed_iter() {
+                let inside = mask.contains(Position(x, y));
+
+                assert_eq!(distances[[x, y]].is_some(), inside, "seed {seed} at {x},{y}");
+
+                if !inside {
+                    assert!(tile.get_sides().iter().all(|(_, closed)| *closed), "seed {seed} at {x},{y}");
+                }
+            }
+
+            let path = maze.solve_masked(&mask).unwrap();
+            assert_eq!((path[0], path[path.len() - 1]), (mask.first().unwrap(), mask.last().unwrap()));
+        }
+    }
+
+    #[test]
+    fn bad_masks_are_refused() {
+        let mut maze = Maze::new(Size(3, 2), true);
+        let mut rng = Pcg32::new(1);
+
+        assert_eq!(maze.generate_masked_with_rng(&Mask::full(Size(2, 3)), &mut rng), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze.generate_masked_with_rng(&parse(&["#.#", "#.#"]), &mut rng), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze, Maze::new(Size(3, 2), true));
+
+        let mask = parse(&["##.", ".##"]);
+        maze.generate_masked_with_rng(&mask, &mut rng).unwrap();
+
+        assert_eq!(maze.set_masked_waypoint("key", Position(2, 0), &mask), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze.set_masked_waypoint("key", Position(2, 1), &mask), Ok(()));
+    }
+
+    #[test]
+    fn cells_outside_are_drawn_solid() {
+        let mask = parse(&["##.", ".##"]);
+        let mut maze = Maze::new(Size(3, 2), true);
+        maze.generate_masked_with_rng(&mask, &mut Pcg32::new(1)).unwrap();
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_mask(&mask);
+
+        let expected = [
+            "███████",
+            "█   ███",
+            "███ ███",
+            "███   █",
+            "███████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
@@ -0,0 +1,264 @@
+use strum::IntoEnumIterator;
+
+use crate::{Direction, Maze, Position};
+
+/// Numbers describing one maze, computed from a single distance map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MazeMetrics {
+    pub cells: usize,
+    /// Cells on the shortest solution, `None` if its ends aren't connected.
+    pub solution_length: Option<usize>,
+    pub dead_ends: usize,
+}
+impl MazeMetrics {
+    /// Share of all cells on the shortest solution, like `Maze::difficulty_score`.
+    pub fn difficulty_score(&self) -> Option<f64> {
+        self.solution_length
+            .map(|length| length as f64 / self.cells as f64)
+    }
+}
+
+impl Maze {
+    pub fn metrics(&self) -> MazeMetrics {
+        self.metrics_between(Position::new(), self.size.get_max_pos())
+    }
+
+    /// Like `metrics`, with the solution running from `start` to `goal` instead of between
+    /// the corners.
+    pub fn metrics_between(&self, start: Position, goal: Position) -> MazeMetrics {
+        let distances = self.distances_from(start);
+
+        MazeMetrics {
+            cells: self.tiles.len(),
+            solution_length: distances[goal.as_array()].map(|x| x as usize + 1),
+            dead_ends: self.get_dead_ends().len(),
+        }
+    }
+}
+
+/// The numbers `--stats` prints for one maze and its solution, see `MazeStats::compute`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MazeStats {
+    /// Cells with one open side, see `Maze::is_dead_end`.
+    pub dead_ends: usize,
+    /// Cells with three open sides.
+    pub three_way: usize,
+    /// Cells with four open sides. Crossings of a weave maze are two passages over each
+    /// other rather than a junction, so they aren't counted.
+    pub four_way: usize,
+    /// Cells on the solution, both ends included.
+    pub solution_length: usize,
+    /// Cells on the longest of the shortest paths between any two connected cells.
+    pub longest_path: usize,
+    /// Share of all cells on the solution, in percent.
+    pub solution_share: f64,
+    /// Mean number of cells in the straight runs of two or more cells, across and down. 0
+    /// if there are none.
+    pub average_corridor: f64,
+}
+impl MazeStats {
+    /// Counts everything for `maze` with `solution` as its solution, which isn't checked
+    /// against the walls. Open sides on the outer border, like exits, count like any other.
+    pub fn compute(maze: &Maze, solution: &[Position]) -> Self {
+        let cells: Vec<Position> = maze.tiles.indexed_iter()
+            .map(|((x, y), _)| Position(x, y))
+            .collect();
+
+        let open_sides = |pos: Position| maze.get_tile(pos)
+            .map_or(0, |tile| tile.get_sides().iter().filter(|(_, closed)| !closed).count());
+
+        let junctions = |sides| cells.iter()
+            .filter(|pos| open_sides(**pos) == sides && !maze.is_crossing(**pos))
+            .count();
+
+        let corridors = corridor_lengths(maze);
+
+        Self {
+            dead_ends: maze.get_dead_ends().len(),
+            three_way: junctions(3),
+            four_way: junctions(4),
+            solution_length: solution.len(),
+            longest_path: longest_shortest_path(maze, &cells),
+            solution_share: match cells.len() {
+                0 => 0.0,
+                count => solution.len() as f64 * 100.0 / count as f64,
+            },
+            average_corridor: match corridors.len() {
+                0 => 0.0,
+                count => corridors.iter().sum::<usize>() as f64 / count as f64,
+            },
+        }
+    }
+}
+
+// Two floods find the longest path of a maze without loops, see `Maze::longest_path`. One
+// with loops or cells cut off needs a flood from every cell.
+fn longest_shortest_path(maze: &Maze, cells: &[Position]) -> usize {
+    let passages: usize = cells.iter()
+        .map(|pos| Direction::iter()
+            .filter(|direction| !maze.get_tile(*pos).unwrap().get_side(*direction))
+            .filter(|direction| maze.get_neighbor(*pos, *direction).is_some())
+            .count())
+        .sum::<usize>() / 2;
+
+    let connected = maze.distances_from(Position::new()).iter().all(Option::is_some);
+
+    if !cells.is_empty() && connected && passages + 1 == cells.len() {
+        return maze.longest_path().len();
+    }
+
+    cells.iter()
+        .filter_map(|pos| maze.distances_from(*pos).iter().flatten().max().copied())
+        .max()
+        .map_or(0, |distance| distance as usize + 1)
+}
+
+// Cells in each straight run of two or more, found by following open east sides along the
+// rows and open south sides down the columns
+fn corridor_lengths(maze: &Maze) -> Vec<usize> {
+    let mut lengths = vec![];
+
+    for (direction, along, across) in [(Direction::East, 0, 1), (Direction::South, 1, 0)] {
+        let size = maze.size.as_array();
+
+        for j in 0..size[across] {
+            let mut run = 1;
+
+            for i in 0..size[along] {
+                let mut pos = [0; 2];
+                pos[along] = i;
+                pos[across] = j;
+
+                let pos = Position::from_array(pos);
+
+                if i + 1 < size[along] && !maze.get_tile(pos).unwrap().get_side(direction) {
+                    run += 1;
+                    continue;
+                }
+
+                if run > 1 {
+                    lengths.push(run);
+                }
+
+                run = 1;
+            }
+        }
+    }
+
+    lengths
+}
+
+/// Minimum, median, mean and maximum of a list of values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub max: f64,
+}
+impl Summary {
+    /// `None` for an empty list. The median of an even number of values is the mean of the
+    /// middle two.
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let middle = sorted.len() / 2;
+
+        let median = match sorted.len() % 2 {
+            0 => (sorted[middle - 1] + sorted[middle]) / 2.0,
+            _ => sorted[middle],
+        };
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MazeStats;
+    use crate::rng::Pcg32;
+    use crate::test_support::opened;
+    use crate::{Direction, Maze, Position, Size};
+
+    #[test]
+    fn a_t_junction_with_a_cell_cut_off() {
+        let maze = opened(Size(3, 2), &[
+            (Position(0, 0), Direction::East),
+            (Position(1, 0), Direction::East),
+            (Position(1, 0), Direction::South),
+            (Position(1, 1), Direction::East),
+        ]);
+
+        let stats = MazeStats::compute(&maze, &maze.solve_bfs());
+
+        assert_eq!(stats, MazeStats {
+            dead_ends: 3,
+            three_way: 1,
+            four_way: 0,
+            solution_length: 4,
+            longest_path: 4,
+            solution_share: 4.0 * 100.0 / 6.0,
+            // The top row, the bottom right pair and the middle column
+            average_corridor: 7.0 / 3.0,
+        });
+    }
+
+    #[test]
+    fn an_open_room_has_every_kind_of_junction() {
+        let mut walls = vec![];
+
+        for y in 0..3 {
+            for x in 0..3 {
+                if x < 2 {
+                    walls.push((Position(x, y), Direction::East));
+                }
+
+                if y < 2 {
+                    walls.push((Position(x, y), Direction::South));
+                }
+            }
+        }
+
+        let maze = opened(Size(3, 3), &walls);
+        let stats = MazeStats::compute(&maze, &maze.solve_bfs());
+
+        assert_eq!((stats.dead_ends, stats.three_way, stats.four_way), (0, 4, 1));
+        assert_eq!((stats.solution_length, stats.longest_path), (5, 5));
+        assert_eq!(stats.average_corridor, 3.0);
+    }
+
+    #[test]
+    fn longest_paths_match_a_flood_from_every_cell() {
+        for (size, braid) in [(Size(13, 7), 0.0), (Size(7, 13), 0.0), (Size(13, 7), 0.5), (Size(1, 9), 0.0)] {
+            let mut maze = Maze::new(size, true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(9));
+            maze.braid_with_rng(braid, &mut Pcg32::new(9));
+
+            let stats = MazeStats::compute(&maze, &maze.solve_bfs());
+
+            let longest = maze.tiles.indexed_iter()
+                .map(|((x, y), _)| maze.distances_from(Position(x, y)).iter().flatten().max().copied().unwrap())
+                .max()
+                .unwrap();
+
+            assert_eq!(stats.longest_path, longest as usize + 1, "{size:?} braided {braid}");
+
+            // A tree has two more leaves than the branches its junctions add
+            if braid == 0.0 {
+                assert_eq!(stats.dead_ends, stats.three_way + 2 * stats.four_way + 2, "{size:?}");
+            }
+
+            assert_eq!(stats.solution_share, stats.solution_length as f64 * 100.0 / maze.tiles.len() as f64);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use rand::RngCore;
+
+use crate::rng::pick;
+use crate::{Maze, Position};
+
+/// How many moves `RandomMouseSolver` gets per cell before the mouse counts as lost.
+pub const MOUSE_STEPS_PER_CELL: usize = 100;
+
+impl Maze {
+    /// Wanders from `(0, 0)` through random open passages until it happens upon the bottom
+    /// right corner. Returns every cell it stepped on in order, repeats and all, or `None`
+    /// if it took `max_steps` moves without getting there. A move over crossings counts
+    /// once but adds each cell it passes.
+    pub fn solve_random_mouse<R: RngCore>(&self, rng: &mut R, max_steps: usize) -> Option<Vec<Position>> {
+        self.solve_random_mouse_between(Position::new(), self.size.get_max_pos(), rng, max_steps, &mut 0)
+    }
+
+    // `solve_random_mouse` between any two cells in the maze, adding the moves it made to
+    // `expanded`
+    pub(crate) fn solve_random_mouse_between<R: RngCore + ?Sized>(
+        &self,
+        start: Position,
+        goal: Position,
+        rng: &mut R,
+        max_steps: usize,
+        expanded: &mut usize
+    ) -> Option<Vec<Position>> {
+        let mut search = MouseSearch::new(start, goal, max_steps);
+
+        while search.step(self, rng).is_some() {
+            *expanded += 1;
+        }
+
+        search.path()
+    }
+}
+
+// Where `Maze::solve_random_mouse` is between steps, see `steps::SolveSteps`
+pub(crate) struct MouseSearch {
+    goal: Position,
+    trace: Vec<Position>,
+    moves_left: usize,
+}
+impl MouseSearch {
+    pub(crate) fn new(start: Position, goal: Position, max_steps: usize) -> Self {
+        Self {
+            goal,
+            trace: vec![start],
+            moves_left: max_steps,
+        }
+    }
+
+    // Makes one random move. Returns the cell it ends up at and the cells it passed on
+    // the way, `None` once the goal is reached, the moves have run out or there is no way
+    // on.
+    pub(crate) fn step<R: RngCore + ?Sized>(&mut self, maze: &Maze, rng: &mut R) -> Option<(Position, Vec<Position>)> {
+        let pos = *self.trace.last().unwrap();
+
+        if pos == self.goal || self.moves_left == 0 {
+            return None;
+        }
+
+        self.moves_left -= 1;
+
+        let (direction, next) = pick(rng, &maze.get_moves(pos))?;
+
+        let mut entered = vec![];
+        let mut between = pos;
+
+        while between != next {
+            between = between.translate(direction);
+            entered.push(between);
+        }
+
+        self.trace.extend(&entered);
+
+        Some((next, entered))
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        vec![*self.trace.last().unwrap()]
+    }
+
+    pub(crate) fn path(&self) -> Option<Vec<Position>> {
+        (*self.trace.last().unwrap() == self.goal).then(|| self.trace.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MOUSE_STEPS_PER_CELL;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+
+    #[test]
+    fn the_trace_is_a_walk_to_the_goal() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(6, 5), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+            let trace = maze.solve_random_mouse(&mut Pcg32::new(seed), 30 * MOUSE_STEPS_PER_CELL).unwrap();
+
+            assert_eq!((trace[0], trace[trace.len() - 1]), (Position(0, 0), Position(5, 4)), "seed {seed}");
+            assert!(trace.len() >= maze.solve_bfs().len(), "seed {seed}");
+
+            for step in trace.windows(2) {
+                assert!(maze.get_moves(step[0]).iter().any(|(_, next)| *next == step[1]), "seed {seed} {step:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn the_same_seed_takes_the_same_walk() {
+        let mut maze = Maze::new(Size(8, 8), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(3));
+
+        assert_eq!(maze.solve_random_mouse(&mut Pcg32::new(7), 6400), maze.solve_random_mouse(&mut Pcg32::new(7), 6400));
+    }
+
+    #[test]
+    fn a_mouse_gets_lost_without_moves_or_a_way() {
+        let mut maze = Maze::new(Size(8, 8), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(3));
+
+        // The goal can't be reached in fewer moves than the shortest path has
+        let shortest = maze.solve_bfs().len() - 1;
+        assert_eq!(maze.solve_random_mouse(&mut Pcg32::new(7), shortest - 1), None);
+
+        // A closed cell has no way out at all
+        assert_eq!(Maze::new(Size(3, 3), true).solve_random_mouse(&mut Pcg32::new(7), 100), None);
+
+        // Already there
+        assert_eq!(Maze::new(Size(1, 1), true).solve_random_mouse(&mut Pcg32::new(7), 0), Some(vec![Position(0, 0)]));
+    }
+}
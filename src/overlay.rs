@@ -0,0 +1,155 @@
+use std::io::{self, Write};
+
+use ndarray::Array2;
+
+use crate::{solution::Solution, Axis, Color, Display, Position, POINT_CHAR, RESET_COLOR};
+
+/// Symbols to tell overlaid paths apart by, in the order they're drawn.
+pub const OVERLAY_SYMBOLS: [char; 4] = [POINT_CHAR, '◦', '+', '~'];
+
+/// And the colors they're printed in when the display has color on.
+pub const OVERLAY_COLORS: [Color; 4] = [Color::Yellow, Color::Cyan, Color::Blue, Color::Red];
+
+/// What `Display::draw_paths` marks pixels that more than one path runs through with.
+pub const OVERLAP_CHAR: char = '◆';
+pub const OVERLAP_COLOR: Color = Color::Magenta;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cover {
+    Free,
+    Path(usize),
+    Shared,
+}
+
+impl Display {
+    /// Draws every path like `draw_path` does, each with its own symbol and a color from
+    /// `OVERLAY_COLORS`, except that pixels covered by more than one of them get
+    /// `OVERLAP_CHAR` instead of whichever was drawn last.
+    pub fn draw_paths(&mut self, paths: &[(Vec<Position>, char)]) -> Result<(), io::ErrorKind> {
+        let mut cover = Array2::from_elem(self.pixels.dim(), Cover::Free);
+
+        for (index, (path, _)) in paths.iter().enumerate() {
+            for vector in Solution::new(path.clone()).to_vectors()? {
+                let (start, end) = (vector.origin, vector.get_end());
+
+                let pixels: Vec<Position> = match vector.direction.get_axis() {
+                    Axis(0) => (start.0.min(end.0)..=start.0.max(end.0)).map(|x| Position(x, start.1)).collect(),
+                    _ => (start.1.min(end.1)..=start.1.max(end.1)).map(|y| Position(start.0, y)).collect(),
+                };
+
+                for pixel in pixels {
+                    let cell = &mut cover[pixel.as_rev_array()];
+
+                    *cell = match *cell {
+                        Cover::Free => Cover::Path(index),
+                        Cover::Path(other) if other == index => Cover::Path(index),
+                        _ => Cover::Shared,
+                    };
+                }
+            }
+        }
+
+        for ((y, x), cell) in cover.indexed_iter() {
+            match *cell {
+                Cover::Free => {},
+                Cover::Path(index) => self.draw_point_colored(
+                    Position(x, y),
+                    paths[index].1,
+                    Some(OVERLAY_COLORS[index % OVERLAY_COLORS.len()])
+                ),
+                Cover::Shared => self.draw_point_colored(Position(x, y), OVERLAP_CHAR, Some(OVERLAP_COLOR)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a line naming what each symbol of `draw_paths` stands for, in the same
+    /// order as the paths, and then the overlap symbol.
+    pub fn write_legend_to<W: Write>(&self, w: &mut W, entries: &[(char, &str)]) -> io::Result<()> {
+        let entries = entries.iter()
+            .enumerate()
+            .map(|(index, (symbol, name))| (*symbol, *name, OVERLAY_COLORS[index % OVERLAY_COLORS.len()]))
+            .chain([(OVERLAP_CHAR, "shared", OVERLAP_COLOR)]);
+
+        let line = entries
+            .map(|(symbol, name, color)| match self.color {
+                true => format!("{}{}{} {}", color.ansi(), self.charset.apply(symbol), RESET_COLOR, name),
+                false => format!("{} {}", self.charset.apply(symbol), name),
+            })
+            .collect::<Vec<String>>()
+            .join("  ");
+
+        writeln!(w, "{}", line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OVERLAP_COLOR, OVERLAY_COLORS};
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Position, Size};
+
+    fn display() -> Display {
+        let maze = opened(Size(2, 2), &[
+            (Position(0, 0), Direction::East),
+            (Position(0, 0), Direction::South),
+            (Position(1, 0), Direction::South),
+            (Position(0, 1), Direction::East),
+        ]);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+
+        display
+    }
+
+    #[test]
+    fn shared_pixels_get_the_overlap_symbol() {
+        let mut display = display();
+
+        // Round the ring both ways, meeting at the ends
+        display.draw_paths(&[
+            (vec![Position(1, 1), Position(3, 1), Position(3, 3)], '•'),
+            (vec![Position(1, 1), Position(1, 3), Position(3, 3)], '+'),
+        ]).unwrap();
+
+        let expected = [
+            "█████",
+            "█◆••█",
+            "█+ •█",
+            "█++◆█",
+            "█████",
+        ];
+
+        assert_eq!(display.render(), expected.map(|line| format!("{line}\n")).concat());
+        assert_eq!(display.colors[[1, 3]], Some(OVERLAY_COLORS[0]));
+        assert_eq!(display.colors[[3, 1]], Some(OVERLAY_COLORS[1]));
+        assert_eq!(display.colors[[3, 3]], Some(OVERLAP_COLOR));
+    }
+
+    #[test]
+    fn a_path_crossing_itself_is_not_an_overlap() {
+        let mut display = display();
+
+        display.draw_paths(&[(vec![Position(1, 1), Position(3, 1), Position(1, 1)], '+')]).unwrap();
+
+        assert_eq!(display.pixels[[1, 1]], '+');
+    }
+
+    #[test]
+    fn the_legend_names_each_symbol() {
+        let mut display = display();
+
+        let mut out = vec![];
+        display.write_legend_to(&mut out, &[('•', "dfs"), ('+', "bfs")]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "• dfs  + bfs  ◆ shared\n");
+
+        display.color = true;
+
+        let mut out = vec![];
+        display.write_legend_to(&mut out, &[('•', "dfs")]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b[33m•\x1b[0m dfs  \x1b[35m◆\x1b[0m shared\n");
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{Direction, Maze, Position};
+
+/// How many steps `Maze::solve_pledge` may take per cell before it gives up.
+pub const PLEDGE_STEPS_PER_CELL: usize = 8;
+
+impl Maze {
+    /// Walks from `(0, 0)` towards the bottom right corner with the Pledge algorithm, which
+    /// only needs the walls of the cell it is in. It heads in `preferred` while it can. At a
+    /// wall it turns right and follows the wall on its left, counting quarter turns, and goes
+    /// back to `preferred` once the count is back at zero. Crossings are walked straight
+    /// through.
+    ///
+    /// The walk escapes any obstacle, but whether it comes across the goal depends on the
+    /// maze and `preferred`. It is `None` if the goal wasn't reached within
+    /// `PLEDGE_STEPS_PER_CELL` steps per cell, which also catches a goal walled off from
+    /// the start. Loops in the walk are cut out of the returned path.
+    pub fn solve_pledge(&self, preferred: Direction) -> Option<Vec<Position>> {
+        self.solve_pledge_between(Position::new(), self.size.get_max_pos(), preferred, &mut 0)
+    }
+
+    // `solve_pledge` between any two cells in the maze, adding the moves it made to
+    // `expanded`
+    pub(crate) fn solve_pledge_between(&self, start: Position, goal: Position, preferred: Direction, expanded: &mut usize) -> Option<Vec<Position>> {
+        let mut search = PledgeSearch::new(self, start, goal, preferred);
+
+        while search.step(self).is_some() {
+            *expanded += 1;
+        }
+
+        search.path()
+    }
+}
+
+/// The direction `PledgeSolver` prefers, along the axis the goal is furthest away on.
+pub fn heading_towards(start: Position, goal: Position) -> Direction {
+    match (goal.0.abs_diff(start.0) >= goal.1.abs_diff(start.1), goal.0 >= start.0, goal.1 >= start.1) {
+        (true, true, _) => Direction::East,
+        (true, false, _) => Direction::West,
+        (false, _, true) => Direction::South,
+        (false, _, false) => Direction::North,
+    }
+}
+
+// Where `Maze::solve_pledge` is between steps, see `steps::SolveSteps`
+pub(crate) struct PledgeSearch {
+    goal: Position,
+    preferred: Direction,
+    heading: Direction,
+    turns: i32,
+    path: Vec<Position>,
+    steps_left: usize,
+}
+impl PledgeSearch {
+    pub(crate) fn new(maze: &Maze, start: Position, goal: Position, preferred: Direction) -> Self {
+        Self {
+            goal,
+            preferred,
+            heading: preferred,
+            turns: 0,
+            path: vec![start],
+            steps_left: maze.tiles.len() * PLEDGE_STEPS_PER_CELL,
+        }
+    }
+
+    // Turns until it can move and then moves. Returns the cell it ends up at and the cells
+    // it stepped onto, `None` once the goal is reached or the steps have run out. A turn
+    // uses up a step just like a move.
+    pub(crate) fn step(&mut self, maze: &Maze) -> Option<(Position, Vec<Position>)> {
+        loop {
+            let pos = *self.path.last().unwrap();
+
+            if pos == self.goal || self.steps_left == 0 {
+                return None;
+            }
+
+            self.steps_left -= 1;
+
+            let moves = maze.get_moves(pos);
+            let is_open = |direction: Direction| moves.iter().any(|(x, _)| *x == direction);
+
+            let step = match self.turns {
+                0 if is_open(self.preferred) => Some(self.preferred),
+                0 => {
+                    self.heading = self.preferred.turn_right();
+                    self.turns = 1;
+                    None
+                },
+                _ if is_open(self.heading.turn_left()) => {
+                    self.heading = self.heading.turn_left();
+                    self.turns -= 1;
+                    Some(self.heading)
+                },
+                _ if is_open(self.heading) => Some(self.heading),
+                _ => {
+                    self.heading = self.heading.turn_right();
+                    self.turns += 1;
+                    None
+                },
+            };
+
+            if let Some(direction) = step {
+                let entered = self.advance(maze, direction);
+
+                return Some((*self.path.last().unwrap(), entered));
+            }
+        }
+    }
+
+    // Moves on from the end of the path over any crossings, and erases the loop if the walk
+    // is back on a cell it has been on. Crossings are left alone since both passages
+    // through them can be on the path. Returns the cells stepped onto.
+    fn advance(&mut self, maze: &Maze, direction: Direction) -> Vec<Position> {
+        let mut pos = maze.get_neighbor(*self.path.last().unwrap(), direction).unwrap();
+        let mut entered = vec![];
+
+        while maze.is_crossing(pos) {
+            entered.push(pos);
+            self.path.push(pos);
+            pos = maze.get_neighbor(pos, direction).unwrap();
+        }
+
+        entered.push(pos);
+
+        match self.path.iter().position(|x| *x == pos) {
+            Some(index) => self.path.truncate(index + 1),
+            None => self.path.push(pos),
+        }
+
+        entered
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        self.path.clone()
+    }
+
+    pub(crate) fn path(&self) -> Option<Vec<Position>> {
+        (self.path.last() == Some(&self.goal)).then(|| self.path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::heading_towards;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+
+    #[test]
+    fn the_heading_follows_the_longer_axis() {
+        assert_eq!(heading_towards(Position(0, 0), Position(5, 3)), Direction::East);
+        assert_eq!(heading_towards(Position(5, 3), Position(0, 0)), Direction::West);
+        assert_eq!(heading_towards(Position(2, 0), Position(0, 7)), Direction::South);
+        assert_eq!(heading_towards(Position(0, 7), Position(2, 0)), Direction::North);
+        // Equal distances go along x
+        assert_eq!(heading_towards(Position(0, 0), Position(4, 4)), Direction::East);
+    }
+
+    #[test]
+    fn found_paths_are_the_only_way_through_a_perfect_maze() {
+        let mut found = 0;
+
+        for seed in 0..20 {
+            let mut maze = Maze::new(Size(12, 9), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+            for preferred in [Direction::East, Direction::South] {
+                if let Some(path) = maze.solve_pledge(preferred) {
+                    assert_eq!(path, maze.solve_bfs(), "seed {seed} {preferred}");
+                    found += 1;
+                }
+            }
+        }
+
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn an_open_field_is_crossed_without_turning() {
+        let maze = Maze::new(Size(4, 3), false);
+
+        // Straight east along the top row, then the preferred way is blocked by the border
+        // and the wall following takes it down the right side
+        assert_eq!(maze.solve_pledge(Direction::East), Some(vec![
+            Position(0, 0), Position(1, 0), Position(2, 0), Position(3, 0), Position(3, 1), Position(3, 2),
+        ]));
+    }
+
+    #[test]
+    fn a_walled_off_goal_runs_out_of_steps() {
+        let mut maze = Maze::new(Size(5, 5), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(2));
+
+        for direction in [Direction::North, Direction::West] {
+            maze.close_wall(maze.size.get_max_pos(), direction).unwrap();
+        }
+
+        assert_eq!(maze.solve_pledge(Direction::East), None);
+    }
+}
@@ -0,0 +1,162 @@
+use image::codecs::png::PngEncoder;
+use image::{ImageFormat, Rgb, RgbImage};
+use std::io;
+use std::path::Path;
+
+use crate::{Direction, Maze, Position};
+
+/// Sizes in pixels and colors for `Maze::to_image`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageOptions {
+    /// From one wall to the next, so a cell's open space is this minus `wall_px`.
+    pub cell_px: u32,
+    pub wall_px: u32,
+    /// Thickness of the line drawn for the solution.
+    pub path_px: u32,
+    pub wall_color: [u8; 3],
+    pub corridor_color: [u8; 3],
+    pub path_color: [u8; 3],
+}
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            cell_px: 8,
+            wall_px: 2,
+            path_px: 2,
+            wall_color: [0, 0, 0],
+            corridor_color: [255, 255, 255],
+            path_color: [220, 40, 40],
+        }
+    }
+}
+
+impl Maze {
+    /// Draws the maze as an image, with `solution` as a line through the middle of its
+    /// cells. The image is `cell_px` per cell plus one wall wide and high, three bytes a
+    /// pixel. Fails if the walls or the line are too thick to leave room in the cells.
+    pub fn to_image(&self, solution: Option<&[Position]>, opts: &ImageOptions) -> Result<RgbImage, io::ErrorKind> {
+        if opts.wall_px == 0 || opts.wall_px >= opts.cell_px || opts.path_px > opts.cell_px - opts.wall_px {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let (cell, wall) = (opts.cell_px, opts.wall_px);
+
+        let mut image = RgbImage::from_pixel(
+            self.size.0 as u32 * cell + wall,
+            self.size.1 as u32 * cell + wall,
+            Rgb(opts.corridor_color)
+        );
+
+        // Every wall is drawn from both of its sides, which is the same pixels twice
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            let (left, top) = (x as u32 * cell, y as u32 * cell);
+
+            for (direction, closed) in tile.get_sides() {
+                if !closed {
+                    continue;
+                }
+
+                let (x, y, width, height) = match direction {
+                    Direction::North => (left, top, cell + wall, wall),
+                    Direction::South => (left, top + cell, cell + wall, wall),
+                    Direction::West => (left, top, wall, cell + wall),
+                    Direction::East => (left + cell, top, wall, cell + wall),
+                };
+
+                fill(&mut image, x, y, width, height, opts.wall_color);
+            }
+        }
+
+        // Neighboring cells in the path are joined by a bar from one middle to the other
+        let middle = |pos: Position| (
+            pos.0 as u32 * cell + (cell + wall - opts.path_px) / 2,
+            pos.1 as u32 * cell + (cell + wall - opts.path_px) / 2
+        );
+
+        for pair in solution.unwrap_or_default().windows(2) {
+            let (a, b) = (middle(pair[0]), middle(pair[1]));
+            let (x, y) = (a.0.min(b.0), a.1.min(b.1));
+
+            fill(&mut image, x, y, a.0.abs_diff(b.0) + opts.path_px, a.1.abs_diff(b.1) + opts.path_px, opts.path_color);
+        }
+
+        Ok(image)
+    }
+
+    /// Writes `to_image` to `path` as a PNG.
+    pub fn save_png(&self, solution: Option<&[Position]>, opts: &ImageOptions, path: &Path) -> io::Result<()> {
+        self.to_image(solution, opts)?
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(io::Error::other)
+    }
+
+    /// Writes `to_image` to `w` as a PNG, like `save_png` does to a file.
+    pub fn write_png_to<W: io::Write>(&self, solution: Option<&[Position]>, opts: &ImageOptions, w: &mut W) -> io::Result<()> {
+        self.to_image(solution, opts)?
+            .write_with_encoder(PngEncoder::new(w))
+            .map_err(io::Error::other)
+    }
+}
+
+pub(crate) fn fill(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+    for y in y..(y + height).min(image.height()) {
+        for x in x..(x + width).min(image.width()) {
+            image.put_pixel(x, y, Rgb(color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageOptions;
+    use crate::test_support::opened;
+    use crate::{Direction, Maze, Position, Size};
+    use image::{ImageFormat, Rgb};
+    use std::io;
+
+    #[test]
+    fn the_image_is_a_cell_per_step_plus_a_wall() {
+        let maze = Maze::new(Size(5, 3), true);
+        let image = maze.to_image(None, &ImageOptions::default()).unwrap();
+
+        assert_eq!(image.dimensions(), (5 * 8 + 2, 3 * 8 + 2));
+    }
+
+    #[test]
+    fn walls_corridors_and_the_path_get_their_colors() {
+        let opts = ImageOptions::default();
+        let maze = opened(Size(2, 1), &[(Position(0, 0), Direction::East)]);
+        let path = [Position(0, 0), Position(1, 0)];
+        let image = maze.to_image(Some(&path), &opts).unwrap();
+
+        // The outer wall, the open wall between the cells on the path and the corridor
+        // beside the path
+        assert_eq!(image.get_pixel(0, 0), &Rgb(opts.wall_color));
+        assert_eq!(image.get_pixel(8, 4), &Rgb(opts.path_color));
+        assert_eq!(image.get_pixel(8, 2), &Rgb(opts.corridor_color));
+        assert_eq!(image.get_pixel(17, 5), &Rgb(opts.wall_color));
+    }
+
+    #[test]
+    fn written_pngs_decode_to_the_same_size() {
+        let mut maze = Maze::new(Size(6, 4), true);
+        maze.generate_maze();
+
+        let mut bytes = vec![];
+        maze.write_png_to(Some(&maze.solve_bfs()), &ImageOptions::default(), &mut bytes).unwrap();
+
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png).unwrap();
+        assert_eq!((image.width(), image.height()), (6 * 8 + 2, 4 * 8 + 2));
+    }
+
+    #[test]
+    fn walls_too_thick_for_the_cells_are_refused() {
+        let maze = Maze::new(Size(2, 2), true);
+
+        for (cell_px, wall_px, path_px) in [(8, 0, 2), (8, 8, 0), (8, 2, 7)] {
+            let opts = ImageOptions { cell_px, wall_px, path_px, ..ImageOptions::default() };
+            assert_eq!(maze.to_image(None, &opts).err(), Some(io::ErrorKind::InvalidInput));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use rand::RngCore;
+use std::io;
+
+use crate::rng::random_index;
+use crate::{Direction, Maze, Position, Rectangle, Size};
+
+/// Tries per room before `carve_random_rooms` gives up on finding free space for it.
+const MAX_ROOM_ATTEMPTS: usize = 100;
+
+impl Maze {
+    /// Opens every wall between two cells inside `rect`, which is given in cells. Walls on
+    /// its edge are left alone, so the room is reached through whatever passages already
+    /// lead into it, or the ones a generator run afterwards carves.
+    pub fn carve_room(&mut self, rect: Rectangle) -> Result<(), io::ErrorKind> {
+        let end = Position(rect.origin.0 + rect.size.0, rect.origin.1 + rect.size.1);
+
+        if rect.size.0 == 0 || rect.size.1 == 0 || end.0 > self.size.0 || end.1 > self.size.1 {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        for x in rect.origin.0..end.0 {
+            for y in rect.origin.1..end.1 {
+                if x + 1 < end.0 {
+                    self.set_wall(Position(x, y), Direction::East, false)?;
+                }
+
+                if y + 1 < end.1 {
+                    self.set_wall(Position(x, y), Direction::South, false)?;
+                }
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    pub fn carve_random_rooms(&mut self, count: usize, min: Size, max: Size) -> Result<Vec<Rectangle>, io::ErrorKind> {
+        self.carve_random_rooms_with_rng(count, min, max, &mut rand::rng())
+    }
+
+    /// Carves up to `count` rooms between `min` and `max` cells on each side at random free
+    /// spots, none overlapping another. Works before generation as well as after; rooms
+    /// carved after it add loops. Returns the rooms, fewer than `count` if no free space for
+    /// the rest was found.
+    pub fn carve_random_rooms_with_rng<R: RngCore>(
+        &mut self,
+        count: usize,
+        min: Size,
+        max: Size,
+        rng: &mut R
+    ) -> Result<Vec<Rectangle>, io::ErrorKind> {
+        let fits = min.0 > 0 && min.1 > 0 && min.0 <= max.0 && min.1 <= max.1;
+
+        if !fits || min.0 > self.size.0 || min.1 > self.size.1 {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let mut rooms: Vec<Rectangle> = vec![];
+
+        for _ in 0..count {
+            for _ in 0..MAX_ROOM_ATTEMPTS {
+                let size = Size(
+                    min.0 + random_index(rng, max.0.min(self.size.0) - min.0 + 1),
+                    min.1 + random_index(rng, max.1.min(self.size.1) - min.1 + 1),
+                );
+
+                let origin = Position(
+                    random_index(rng, self.size.0 - size.0 + 1),
+                    random_index(rng, self.size.1 - size.1 + 1),
+                );
+
+                let room = Rectangle::new(origin, size);
+
+                if rooms.iter().all(|other| !overlaps(room, *other)) {
+                    self.carve_room(room)?;
+                    rooms.push(room);
+                    break;
+                }
+            }
+        }
+
+        Ok(rooms)
+    }
+}
+
+fn overlaps(a: Rectangle, b: Rectangle) -> bool {
+    a.origin.0 < b.origin.0 + b.size.0
+        && b.origin.0 < a.origin.0 + a.size.0
+        && a.origin.1 < b.origin.1 + b.size.1
+        && b.origin.1 < a.origin.1 + a.size.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::overlaps;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Rectangle, Size};
+    use std::io;
+
+    // Walls open between two cells, each counted once
+    fn open_walls(maze: &Maze) -> usize {
+        maze.tiles.indexed_iter()
+            .map(|((x, y), tile)| [Direction::East, Direction::South].into_iter()
+                .filter(|direction| !tile.get_side(*direction) && maze.get_neighbor(Position(x, y), *direction).is_some())
+                .count())
+            .sum()
+    }
+
+    fn inside(room: Rectangle, pos: Position) -> bool {
+        overlaps(room, Rectangle::new(pos, Size(1, 1)))
+    }
+
+    #[test]
+    fn a_room_opens_only_its_inner_walls() {
+        let mut maze = Maze::new(Size(5, 5), true);
+        let room = Rectangle::new(Position(1, 2), Size(3, 2));
+
+        maze.carve_room(room).unwrap();
+
+        // Two walls inside each of the two rows and three between them
+        assert_eq!(open_walls(&maze), 7);
+
+        for ((x, y), tile) in maze.tiles.indexed_iter() {
+            let pos = Position(x, y);
+
+            for (direction, closed) in tile.get_sides() {
+                let joined = maze.get_neighbor(pos, direction).is_some_and(|next| inside(room, pos) && inside(room, next));
+                assert_eq!(closed, !joined, "{pos:?} {direction}");
+            }
+        }
+    }
+
+    #[test]
+    fn rooms_outside_the_maze_are_refused() {
+        let mut maze = Maze::new(Size(4, 3), true);
+
+        for room in [
+            Rectangle::new(Position(0, 0), Size(0, 2)),
+            Rectangle::new(Position(2, 0), Size(3, 1)),
+            Rectangle::new(Position(0, 2), Size(1, 2)),
+        ] {
+            assert_eq!(maze.carve_room(room), Err(io::ErrorKind::InvalidInput), "{room:?}");
+        }
+
+        assert_eq!(maze, Maze::new(Size(4, 3), true));
+    }
+
+    #[test]
+    fn random_rooms_keep_apart_and_in_size() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(20, 15), true);
+            let rooms = maze.carve_random_rooms_with_rng(5, Size(2, 2), Size(4, 3), &mut Pcg32::new(seed)).unwrap();
+
+            assert!(!rooms.is_empty() && rooms.len() <= 5, "seed {seed}");
+
+            for (i, room) in rooms.iter().enumerate() {
+                assert!((2..=4).contains(&room.size.0) && (2..=3).contains(&room.size.1), "seed {seed} {room:?}");
+                assert!(room.origin.0 + room.size.0 <= 20 && room.origin.1 + room.size.1 <= 15, "seed {seed} {room:?}");
+                assert!(rooms[i + 1..].iter().all(|other| !overlaps(*room, *other)), "seed {seed} {room:?}");
+            }
+
+            let inner: usize = rooms.iter()
+                .map(|room| (room.size.0 - 1) * room.size.1 + room.size.0 * (room.size.1 - 1))
+                .sum();
+            assert_eq!(open_walls(&maze), inner, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn room_sizes_have_to_fit() {
+        let mut maze = Maze::new(Size(6, 4), true);
+        let mut rng = Pcg32::new(1);
+
+        assert_eq!(maze.carve_random_rooms_with_rng(2, Size(0, 2), Size(3, 3), &mut rng), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze.carve_random_rooms_with_rng(2, Size(3, 3), Size(2, 3), &mut rng), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze.carve_random_rooms_with_rng(2, Size(2, 5), Size(2, 6), &mut rng), Err(io::ErrorKind::InvalidInput));
+
+        // A room as big as the maze leaves no space for a second one
+        assert_eq!(maze.carve_random_rooms_with_rng(2, Size(6, 4), Size(9, 9), &mut rng).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,127 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::rng::{pick, random_index};
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    /// Builds a perfect maze whose only path between the two ends of `route` is `route`
+    /// itself. The route is carved first and the rest of the maze grows off it as branches
+    /// that only ever carve into unvisited cells, so no branch can join the route a second
+    /// time and create a shortcut.
+    ///
+    /// `route` has to be a simple path of neighbouring cells inside a fully walled maze.
+    pub fn generate_from_route<R: RngCore>(&mut self, route: &[Position], rng: &mut R) -> Result<(), io::ErrorKind> {
+        if route.is_empty() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let mut explored = Array2::from_elem(self.size.as_array(), false);
+
+        for pos in route {
+            match explored.get_mut(pos.as_array()) {
+                Some(visited) if !*visited => *visited = true,
+                _ => return Err(io::ErrorKind::InvalidInput),
+            }
+        }
+
+        self.carve_route(route)?;
+
+        let mut stack = route.to_vec();
+
+        for i in 0..stack.len() {
+            let other = i + random_index(rng, stack.len() - i);
+            stack.swap(i, other);
+        }
+
+        while let Some(pos) = stack.last().copied() {
+            let dirs = self.get_valid_branches(pos, &explored);
+
+            let Some(direction) = pick(rng, &dirs) else {
+                stack.pop();
+                continue;
+            };
+
+            let next = pos.translate(direction);
+
+            self.set_wall(pos, direction, false)?;
+            explored[next.as_array()] = true;
+
+            stack.push(next);
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+
+    fn get_valid_branches(&self, pos: Position, explored: &Array2<bool>) -> Vec<Direction> {
+        Direction::iter()
+            .filter(|direction| {
+                self.get_neighbor(pos, *direction)
+                    .is_some_and(|neighbor| !explored[neighbor.as_array()])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+
+    // An S drawn down the middle of a 9x9 maze
+    fn letter() -> Vec<Position> {
+        let mut route = vec![];
+
+        route.extend((2..7).rev().map(|x| Position(x, 1)));
+        route.extend((2..4).map(|y| Position(2, y)));
+        route.extend((2..7).map(|x| Position(x, 4)));
+        route.extend((5..7).map(|y| Position(6, y)));
+        route.extend((2..7).rev().map(|x| Position(x, 7)));
+
+        route
+    }
+
+    #[test]
+    fn the_route_is_the_only_solution() {
+        for seed in 0..10 {
+            let route = letter();
+            let (start, goal) = (route[0], route[route.len() - 1]);
+
+            let mut maze = Maze::new(Size(9, 9), true);
+            maze.generate_from_route(&route, &mut Pcg32::new(seed)).unwrap();
+
+            assert_eq!(maze.solve_bfs_between(start, goal, &mut 0), route, "seed {seed}");
+
+            // Perfect, so every cell is reachable and there is one passage fewer than cells
+            let reached = maze.distances_from(start).iter().filter(|x| x.is_some()).count();
+            let open: usize = maze.tiles.iter()
+                .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+                .sum();
+
+            assert_eq!(reached, 81, "seed {seed}");
+            assert_eq!(open / 2, 80, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn broken_routes_are_refused() {
+        let routes = [
+            vec![],
+            vec![Position(0, 0), Position(1, 1)],
+            vec![Position(0, 0), Position(1, 0), Position(0, 0)],
+            vec![Position(8, 0), Position(9, 0)],
+        ];
+
+        for route in routes {
+            let mut maze = Maze::new(Size(9, 9), true);
+
+            assert!(maze.generate_from_route(&route, &mut Pcg32::new(1)).is_err(), "{route:?}");
+        }
+    }
+}
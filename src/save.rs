@@ -0,0 +1,165 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::Display;
+
+/// How `Display::save_with` writes its file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SaveOptions {
+    /// Adds the display to the end of the file, a blank line after whatever is in it
+    /// already, instead of replacing it.
+    pub append: bool,
+    /// Writes the colors into the file when the display has `color` on. Left off, the
+    /// file is plain text either way.
+    pub keep_color: bool,
+}
+
+impl Display {
+    /// Writes the display to the file at `path` like `write_to` does, without colors,
+    /// creating or replacing the file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        self.save_with(path, SaveOptions::default())
+    }
+
+    /// Replacing the file goes through `write_atomically`, appending writes to it directly.
+    pub fn save_with(&self, path: &Path, options: SaveOptions) -> io::Result<()> {
+        let keep_color = self.color && options.keep_color;
+
+        if !options.append {
+            return write_atomically(path, |w| self.write_with(w, keep_color));
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        // The top margin already leaves a blank line between two displays
+        let separate = self.margins.top == 0 && file.metadata()?.len() > 0;
+
+        let mut w = BufWriter::new(file);
+
+        if separate {
+            writeln!(w)?;
+        }
+
+        self.write_with(&mut w, keep_color)?;
+
+        w.flush()
+    }
+}
+
+/// Creates or replaces the file at `path` with what `write` writes. It goes to a hidden
+/// temporary file next to `path` first, which is renamed over it once it's complete, so a
+/// run that fails or is interrupted half way leaves the old file alone.
+pub fn write_atomically<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+{
+    let name = path.file_name().ok_or(io::ErrorKind::InvalidInput)?;
+    let temporary = path.with_file_name(format!(".{}.tmp", name.to_string_lossy()));
+
+    let written = File::create(&temporary)
+        .and_then(|file| {
+            let mut w = BufWriter::new(file);
+            write(&mut w)?;
+
+            w.into_inner().map_err(|error| error.into_error())?.sync_all()
+        })
+        .and_then(|_| fs::rename(&temporary, path));
+
+    if written.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_atomically, SaveOptions};
+    use crate::{Display, Margins, Maze, Size};
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::PathBuf;
+
+    // A fresh directory of its own for each test, so they can run side by side
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("maze-save-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn display(color: bool) -> Display {
+        let maze = Maze::new(Size(1, 1), true);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.color = color;
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution(&maze.solve_bfs(), '*').unwrap();
+
+        display
+    }
+
+    #[test]
+    fn saved_files_are_plain_text_unless_asked() {
+        let dir = scratch("color");
+        let path = dir.join("maze.txt");
+
+        display(true).save(&path).unwrap();
+        let plain = fs::read_to_string(&path).unwrap();
+        assert_eq!(plain, display(false).render());
+        assert!(!plain.contains('\x1b'));
+
+        display(true).save_with(&path, SaveOptions { keep_color: true, ..SaveOptions::default() }).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains('\x1b'));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn appended_displays_are_a_blank_line_apart() {
+        let dir = scratch("append");
+        let path = dir.join("mazes.txt");
+        let append = SaveOptions { append: true, ..SaveOptions::default() };
+
+        display(false).save_with(&path, append).unwrap();
+        display(false).save_with(&path, append).unwrap();
+
+        let one = display(false).render();
+        assert_eq!(fs::read_to_string(&path).unwrap(), format!("{one}\n{one}"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn a_failed_write_leaves_the_old_file() {
+        let dir = scratch("atomic");
+        let path = dir.join("maze.txt");
+        fs::write(&path, "old").unwrap();
+
+        let written = write_atomically(&path, |w| {
+            w.write_all(b"half")?;
+            Err(io::ErrorKind::WriteZero.into())
+        });
+
+        assert_eq!(written.unwrap_err().kind(), io::ErrorKind::WriteZero);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_directories_are_errors() {
+        let dir = scratch("missing");
+
+        assert_eq!(display(false).save(&dir.join("no").join("maze.txt")).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
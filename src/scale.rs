@@ -0,0 +1,138 @@
+use std::cmp::Ordering;
+
+use crate::{Display, Position, Size};
+
+impl Display {
+    /// A copy of the display where every corridor is `scale.0` columns wide and `scale.1`
+    /// rows high, while the walls between them stay a single pixel. Odd columns and rows
+    /// are the corridors, see `Maze::to_display_pos`. A pixel fills the middle of its
+    /// stretched block and only reaches out towards neighbours drawn with the same
+    /// character, so paths stay thin lines and markers stay single characters. A marker on
+    /// the outer wall ends up in a gap as wide as the corridor next to it. A scale of 1
+    /// gives back the same pixels.
+    pub fn scaled(&self, scale: Size) -> Display {
+        let columns = stretch(self.size.0, scale.0.max(1));
+        let rows = stretch(self.size.1, scale.1.max(1));
+
+        let size = Size(
+            columns.last().map_or(0, |(start, length)| start + length),
+            rows.last().map_or(0, |(start, length)| start + length)
+        );
+
+        let mut scaled = Display {
+            margins: self.margins,
+            color: self.color,
+            charset: self.charset,
+            wide: self.wide,
+            footer: self.footer.clone(),
+            ..Display::new(size)
+        };
+
+        let same = |x: usize, y: usize, dx: isize, dy: isize| match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+            _ if dx == 0 && dy == 0 => true,
+            (Some(nx), Some(ny)) => self.pixels.get([ny, nx]) == Some(&self.pixels[[y, x]]),
+            _ => false,
+        };
+
+        for ((y, x), pixel) in self.pixels.indexed_iter() {
+            let ((left, width), (top, height)) = (columns[x], rows[y]);
+
+            for j in 0..height {
+                for i in 0..width {
+                    let (dx, dy) = (offset(i, width), offset(j, height));
+
+                    if same(x, y, dx, 0) && same(x, y, 0, dy) && same(x, y, dx, dy) {
+                        scaled.draw_point_colored(Position(left + i, top + j), *pixel, self.colors[[y, x]]);
+                    }
+                }
+            }
+        }
+
+        scaled
+    }
+}
+
+// Where each of `length` pixels starts once the odd ones are `factor` long, and how long it is
+fn stretch(length: usize, factor: usize) -> Vec<(usize, usize)> {
+    let mut start = 0;
+
+    (0..length)
+        .map(|i| {
+            let span = (start, if i % 2 == 1 { factor } else { 1 });
+            start += span.1;
+
+            span
+        })
+        .collect()
+}
+
+// Which side of the middle of a block `length` long the `i`th copy is on
+fn offset(i: usize, length: usize) -> isize {
+    match i.cmp(&(length / 2)) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Direction, Display, Margins, Maze, Position, Size, POINT_CHAR};
+
+    fn solved(size: Size, seed: u64) -> Display {
+        let mut maze = Maze::new(size, true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution(&maze.solve_bfs(), POINT_CHAR).unwrap();
+
+        display
+    }
+
+    #[test]
+    fn scale_one_changes_nothing() {
+        for seed in 0..5 {
+            let display = solved(Size(9, 6), seed);
+
+            assert_eq!(display.scaled(Size(1, 1)).render(), display.render());
+        }
+    }
+
+    #[test]
+    fn corridors_stretch_and_walls_dont() {
+        let display = solved(Size(7, 5), 2);
+        let scaled = display.scaled(Size(3, 2));
+
+        // Seven corridors three wide between eight walls, five two high between six
+        assert_eq!(scaled.size, Size(7 * 3 + 8, 5 * 2 + 6));
+        assert_eq!(scaled.pixels[[0, 0]], display.pixels[[0, 0]]);
+    }
+
+    #[test]
+    fn paths_stay_thin() {
+        let mut maze = Maze::new(Size(2, 2), true);
+        maze.open_wall(Position(0, 0), Direction::East).unwrap();
+        maze.open_wall(Position(1, 0), Direction::South).unwrap();
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+        display.draw_solution(&maze.solve_bfs(), POINT_CHAR).unwrap();
+
+        // The end markers open gaps as wide as the corridors next to them
+        let expected = [
+            "█ • █████",
+            "█ •     █",
+            "█ ••••• █",
+            "█████ • █",
+            "█   █ • █",
+            "█   █ • █",
+            "█████ • █",
+        ];
+
+        assert_eq!(display.scaled(Size(3, 2)).render(), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
@@ -0,0 +1,118 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use ndarray::Axis;
+
+use crate::json::MazeError;
+use crate::{Maze, Position, Size, Tile};
+
+/// How a `Maze` is laid out for serde, with the tiles flattened row by row from the top left,
+/// `width` to a row, like the cells of `Maze::to_json`. A crossing stores the index of the
+/// axis of the passage underneath, 0 for horizontal and 1 for vertical. Reading one back into
+/// a `Maze` checks that the tiles fill the size and that both sides of every wall agree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawMaze {
+    pub width: usize,
+    pub height: usize,
+    pub tiles: Vec<Tile>,
+    #[serde(default)]
+    pub waypoints: Vec<(String, Position)>,
+    #[serde(default)]
+    pub crossings: Vec<(Position, usize)>,
+}
+
+impl From<Maze> for RawMaze {
+    fn from(maze: Maze) -> Self {
+        Self {
+            width: maze.size.0,
+            height: maze.size.1,
+            tiles: maze.tiles.t().iter().copied().collect(),
+            waypoints: maze.waypoints,
+            crossings: maze.crossings.into_iter().map(|(pos, under)| (pos, under.index())).collect(),
+        }
+    }
+}
+
+impl TryFrom<RawMaze> for Maze {
+    type Error = MazeError;
+
+    fn try_from(raw: RawMaze) -> Result<Self, Self::Error> {
+        let size = Size(raw.width, raw.height);
+
+        if size.0 == 0 || size.1 == 0 {
+            return Err(MazeError::EmptySize);
+        }
+
+        let expected = size.0.checked_mul(size.1).ok_or(MazeError::Io(io::ErrorKind::InvalidData))?;
+
+        if raw.tiles.len() != expected {
+            return Err(MazeError::CellCount { expected, found: raw.tiles.len() });
+        }
+
+        let mut maze = Maze::new(size, true);
+
+        for (i, tile) in raw.tiles.into_iter().enumerate() {
+            maze.tiles[[i % size.0, i / size.0]] = tile;
+        }
+
+        maze.waypoints = raw.waypoints;
+
+        for (i, (pos, under)) in raw.crossings.into_iter().enumerate() {
+            if under > 1 {
+                return Err(MazeError::Field(format!("crossings[{}]", i)));
+            }
+
+            maze.crossings.push((pos, Axis(under)));
+        }
+
+        maze.check_consistency()?;
+
+        Ok(maze)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_serde_json() {
+        let mut maze = Maze::new(Size(7, 4), true);
+        maze.generate_maze_seeded(5);
+        maze.set_waypoint("key", Position(3, 2)).unwrap();
+
+        let text = serde_json::to_string(&maze).unwrap();
+
+        assert_eq!(serde_json::from_str::<Maze>(&text).unwrap(), maze);
+    }
+
+    #[test]
+    fn shape_and_walls_are_checked() {
+        let mut raw = RawMaze::from(Maze::new(Size(3, 2), true));
+        raw.tiles.pop();
+
+        assert_eq!(Maze::try_from(raw), Err(MazeError::CellCount { expected: 6, found: 5 }));
+
+        let mut raw = RawMaze::from(Maze::new(Size(3, 2), true));
+        raw.tiles[0].right = false;
+
+        assert_eq!(
+            Maze::try_from(raw),
+            Err(MazeError::MismatchedWall { cell: Position(0, 0), neighbor: Position(1, 0) })
+        );
+    }
+
+    #[test]
+    fn huge_sizes_are_an_error() {
+        let raw = RawMaze {
+            width: usize::MAX,
+            height: 2,
+            tiles: vec![],
+            waypoints: vec![],
+            crossings: vec![],
+        };
+
+        assert_eq!(Maze::try_from(raw), Err(MazeError::Io(io::ErrorKind::InvalidData)));
+    }
+}
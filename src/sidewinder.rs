@@ -0,0 +1,83 @@
+use rand::RngCore;
+
+use crate::rng::{chance, pick};
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    pub fn generate_sidewinder(&mut self) {
+        self.generate_sidewinder_with_rng(&mut rand::rng());
+    }
+
+    /// Sweeps each row left to right, extending a run of cells east until it randomly
+    /// closes and one of its cells is opened to the north. The top row has nothing to its
+    /// north and becomes a single corridor, and runs always close at the last column.
+    pub fn generate_sidewinder_with_rng<R: RngCore>(&mut self, rng: &mut R) {
+        for y in 0..self.size.1 {
+            let mut run = vec![];
+
+            for x in 0..self.size.0 {
+                run.push(Position(x, y));
+
+                let last_column = x + 1 == self.size.0;
+                let top_row = y == 0;
+
+                if !last_column && (top_row || chance(rng, 0.5)) {
+                    self.set_wall(Position(x, y), Direction::East, false).unwrap();
+                    continue;
+                }
+
+                if !top_row {
+                    let cell = pick(rng, &run).unwrap();
+                    self.set_wall(cell, Direction::North, false).unwrap();
+                }
+
+                run.clear();
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+
+    #[test]
+    fn the_top_row_is_one_corridor() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(9, 6), true);
+            maze.generate_sidewinder_with_rng(&mut Pcg32::new(seed));
+
+            for x in 0..8 {
+                assert!(!maze.tiles[[x, 0]].get_side(Direction::East), "seed {seed} at {x}");
+            }
+
+            assert!(maze.tiles[[8, 0]].get_side(Direction::East));
+
+            let path = maze.solve_maze_with_rng(&mut Pcg32::new(seed));
+            assert_eq!((path[0], path[path.len() - 1]), (Position(0, 0), Position(8, 5)), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn every_size_is_perfect() {
+        for size in [Size(1, 1), Size(1, 6), Size(6, 1), Size(2, 2), Size(11, 7)] {
+            for seed in 0..5 {
+                let mut maze = Maze::new(size, true);
+                maze.generate_sidewinder_with_rng(&mut Pcg32::new(seed));
+
+                let cells = size.0 * size.1;
+                let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+                let open: usize = maze.tiles.iter()
+                    .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+                    .sum();
+
+                assert_eq!((reached, open / 2), (cells, cells - 1), "{size:?} seed {seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use ndarray::Array2;
+use std::io;
+
+use crate::{Direction, Display, Maze, Position};
+
+impl Maze {
+    /// Every path from `start` to `goal` that doesn't visit a cell twice, found by
+    /// backtracking, stopping after `limit` of them. Crossings are passed straight through
+    /// and may be on a path once in each direction. Empty if either end is outside the maze
+    /// or they aren't connected.
+    pub fn all_solutions(&self, start: Position, goal: Position, limit: usize) -> Vec<Vec<Position>> {
+        if limit == 0 || self.get_tile(start).is_none() || self.get_tile(goal).is_none() {
+            return vec![];
+        }
+
+        if start == goal {
+            return vec![vec![start]];
+        }
+
+        let mut solutions = vec![];
+        let mut on_path: Array2<bool> = Array2::from_elem(self.size.as_array(), false);
+        let mut path = vec![start];
+
+        // The moves left to try from each cell on the path, and how long the path was
+        // before the cell was added
+        let mut frames: Vec<(Vec<(Direction, Position)>, usize)> = vec![(self.get_moves(start), 0)];
+
+        on_path[start.as_array()] = true;
+
+        while let Some((moves, length)) = frames.last_mut() {
+            let Some((direction, next)) = moves.pop() else {
+                on_path[path.last().unwrap().as_array()] = false;
+                path.truncate(*length);
+                frames.pop();
+
+                continue;
+            };
+
+            if on_path[next.as_array()] {
+                continue;
+            }
+
+            let length = path.len();
+            let mut between = *path.last().unwrap();
+
+            while between != next {
+                between = between.translate(direction);
+                path.push(between);
+            }
+
+            if next == goal {
+                solutions.push(path.clone());
+                path.truncate(length);
+
+                if solutions.len() == limit {
+                    break;
+                }
+
+                continue;
+            }
+
+            on_path[next.as_array()] = true;
+            frames.push((self.get_moves(next), length));
+        }
+
+        solutions
+    }
+
+    /// How many solutions run between the corners, counting no further than `limit`. A
+    /// perfect maze has exactly one.
+    pub fn solution_count(&self, limit: usize) -> usize {
+        self.all_solutions(Position::new(), self.size.get_max_pos(), limit).len()
+    }
+}
+
+impl Display {
+    /// Draws `paths` like `draw_solution`, each with the symbol at the same index and the
+    /// first ones on top where they share cells. Paths without a symbol are left out.
+    pub fn draw_solutions(&mut self, paths: &[Vec<Position>], symbols: &[char]) -> Result<(), io::ErrorKind> {
+        for (path, symbol) in paths.iter().zip(symbols).rev() {
+            self.draw_solution(path, *symbol)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Display, Maze, Position, Size};
+
+    #[test]
+    fn a_perfect_maze_has_one_solution() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(9, 7), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+            assert_eq!(maze.all_solutions(Position::new(), maze.size.get_max_pos(), 5), vec![maze.solve_bfs()]);
+            assert_eq!(maze.solution_count(5), 1, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn an_open_square_has_both_ways_round() {
+        let maze = Maze::new(Size(2, 2), false);
+        let solutions = maze.all_solutions(Position(0, 0), Position(1, 1), 10);
+
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions.contains(&vec![Position(0, 0), Position(0, 1), Position(1, 1)]));
+        assert!(solutions.contains(&vec![Position(0, 0), Position(1, 0), Position(1, 1)]));
+    }
+
+    #[test]
+    fn the_limit_stops_the_search() {
+        // 12 simple paths cross an open 3x3 between the corners
+        let maze = Maze::new(Size(3, 3), false);
+
+        assert_eq!(maze.solution_count(100), 12);
+        assert_eq!(maze.solution_count(5), 5);
+        assert_eq!(maze.solution_count(0), 0);
+    }
+
+    #[test]
+    fn odd_ends_give_the_obvious_answers() {
+        let maze = Maze::new(Size(3, 3), false);
+
+        assert_eq!(maze.all_solutions(Position(1, 1), Position(1, 1), 3), vec![vec![Position(1, 1)]]);
+        assert!(maze.all_solutions(Position(0, 0), Position(3, 0), 3).is_empty());
+
+        // A closed maze joins nothing
+        assert_eq!(Maze::new(Size(3, 3), true).solution_count(3), 0);
+    }
+
+    #[test]
+    fn paths_without_a_symbol_are_left_out() {
+        let maze = Maze::new(Size(2, 2), false);
+        let solutions = maze.all_solutions(Position(0, 0), Position(1, 1), 10);
+
+        let mut both = Display::new_from_maze(&maze);
+        both.draw_solutions(&solutions, &['*']).unwrap();
+
+        let mut first = Display::new_from_maze(&maze);
+        first.draw_solution(&solutions[0], '*').unwrap();
+
+        assert_eq!(both.render(), first.render());
+    }
+}
@@ -0,0 +1,252 @@
+use derive_more::AsRef;
+use std::io;
+
+use crate::json::JsonValue;
+use crate::{Direction, Position, Vector};
+
+#[derive(Debug, Clone, PartialEq, Eq, AsRef)]
+pub struct Solution {
+    #[as_ref([Position], Vec<Position>)]
+    pub path: Vec<Position>,
+}
+impl Solution {
+    pub fn new(path: Vec<Position>) -> Self {
+        Self { path }
+    }
+
+    /// Merges runs of steps in the same direction into one `Vector` each. Consecutive
+    /// vectors share their corner cell; a single-cell path becomes one vector of magnitude 1.
+    pub fn to_vectors(&self) -> Result<Vec<Vector>, io::ErrorKind> {
+        if let [pos] = self.path.as_slice() {
+            return Ok(vec![Vector::new(*pos, Direction::East, 1)]);
+        }
+
+        let mut out: Vec<Vector> = vec![];
+
+        for pair in self.path.windows(2) {
+            let step = Vector::new_from_points(pair[0], pair[1])?;
+
+            match out.last_mut() {
+                Some(last) if last.direction == step.direction => {
+                    last.magnitude += step.magnitude - 1;
+                },
+                _ => out.push(step),
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Expands vectors back into every cell they cover, the inverse of `to_vectors` for
+    /// paths made of adjacent cells.
+    pub fn from_vectors(vectors: &[Vector]) -> Self {
+        let mut path: Vec<Position> = vec![];
+
+        for vector in vectors {
+            let mut pos = vector.origin;
+
+            for i in 0..vector.magnitude {
+                if i != 0 {
+                    pos = pos.translate(vector.direction);
+                }
+
+                if path.last() != Some(&pos) {
+                    path.push(pos);
+                }
+            }
+        }
+
+        Self::new(path)
+    }
+
+    /// The path as one letter per step (`N`, `E`, `S` or `W`), the format read by
+    /// `parse_moves`. Fails if two consecutive cells aren't neighbours.
+    pub fn to_moves(&self) -> Result<String, io::ErrorKind> {
+        self.path
+            .windows(2)
+            .map(|pair| match Vector::new_from_points(pair[0], pair[1])? {
+                step if step.magnitude == 2 => Ok(move_letter(step.direction)),
+                _ => Err(io::ErrorKind::InvalidInput),
+            })
+            .collect()
+    }
+
+    /// The path as JSON, for example
+    /// `{"summary":{"length":3,"turns":1,"start":{"x":0,"y":0},"goal":{"x":1,"y":1},"seed":7},
+    /// "path":[{"step":0,"x":0,"y":0},{"step":1,"x":1,"y":0},{"step":2,"x":1,"y":1}]}`.
+    ///
+    /// Coordinates are maze cells, not display pixels: `x` is the column counted from the
+    /// left and `y` the row counted from the top. `length` counts cells, `turns` changes of
+    /// direction, and `start`, `goal` and `seed` are `null` when unknown.
+    pub fn to_json(&self, seed: Option<u64>) -> Result<String, io::ErrorKind> {
+        let point = |pos: Option<&Position>| pos.map_or("null".to_string(), |pos| {
+            format!("{{\"x\":{},\"y\":{}}}", pos.0, pos.1)
+        });
+
+        let turns = match self.path.is_empty() {
+            true => 0,
+            false => self.to_vectors()?.len() - 1,
+        };
+
+        let steps: Vec<String> = self.path
+            .iter()
+            .enumerate()
+            .map(|(step, pos)| format!("{{\"step\":{},\"x\":{},\"y\":{}}}", step, pos.0, pos.1))
+            .collect();
+
+        Ok(format!(
+            "{{\"summary\":{{\"length\":{},\"turns\":{},\"start\":{},\"goal\":{},\"seed\":{}}},\"path\":[{}]}}",
+            self.path.len(),
+            turns,
+            point(self.path.first()),
+            point(self.path.last()),
+            seed.map_or("null".to_string(), |x| x.to_string()),
+            steps.join(",")
+        ))
+    }
+
+    /// Reads the output of `to_json` back, or any other format `parse_coords` accepts.
+    pub fn from_json(text: &str) -> Result<Self, io::ErrorKind> {
+        Ok(Self::new(parse_coords(text)?))
+    }
+}
+
+/// Reads a move string such as `EESSWN`. Letters are case-insensitive and whitespace is
+/// ignored.
+pub fn parse_moves(moves: &str) -> Result<Vec<Direction>, io::ErrorKind> {
+    moves.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c.to_ascii_uppercase() {
+            'N' => Ok(Direction::North),
+            'E' => Ok(Direction::East),
+            'S' => Ok(Direction::South),
+            'W' => Ok(Direction::West),
+            _ => Err(io::ErrorKind::InvalidData),
+        })
+        .collect()
+}
+
+/// Reads a list of cells from JSON. Accepts an array of `[x, y]` pairs, an array of
+/// `{"x": .., "y": ..}` objects such as the steps written by `Solution::to_json`, or an object
+/// with such an array under `path`.
+pub fn parse_coords(text: &str) -> Result<Vec<Position>, io::ErrorKind> {
+    let value = JsonValue::parse(text)?;
+
+    let cells = value.get("path")
+        .unwrap_or(&value)
+        .as_array()
+        .ok_or(io::ErrorKind::InvalidData)?;
+
+    cells.iter()
+        .map(|cell| {
+            let (x, y) = match cell.as_array() {
+                Some([x, y]) => (x, y),
+                Some(_) => return Err(io::ErrorKind::InvalidData),
+                None => (
+                    cell.get("x").ok_or(io::ErrorKind::InvalidData)?,
+                    cell.get("y").ok_or(io::ErrorKind::InvalidData)?
+                ),
+            };
+
+            Ok(Position(
+                x.as_usize().ok_or(io::ErrorKind::InvalidData)?,
+                y.as_usize().ok_or(io::ErrorKind::InvalidData)?
+            ))
+        })
+        .collect()
+}
+
+fn move_letter(direction: Direction) -> char {
+    match direction {
+        Direction::North => 'N',
+        Direction::East => 'E',
+        Direction::South => 'S',
+        Direction::West => 'W',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Solution;
+    use crate::rng::Pcg32;
+    use crate::{Direction, Maze, Position, Size};
+
+    fn segments(solution: &Solution) -> Vec<(Position, Direction, usize)> {
+        solution.to_vectors()
+            .unwrap()
+            .iter()
+            .map(|vector| (vector.origin, vector.direction, vector.magnitude))
+            .collect()
+    }
+
+    #[test]
+    fn collinear_steps_merge_into_one_segment() {
+        let corridor = Solution::new(vec![Position(0, 0), Position(1, 0), Position(2, 0), Position(2, 1), Position(2, 2)]);
+
+        assert_eq!(segments(&corridor), vec![
+            (Position(0, 0), Direction::East, 3),
+            (Position(2, 0), Direction::South, 3),
+        ]);
+    }
+
+    #[test]
+    fn expanding_the_segments_gives_the_path_back() {
+        // A staircase turns on every step, so each segment is a single step
+        let stairs = Solution::new(vec![Position(0, 0), Position(1, 0), Position(1, 1), Position(2, 1), Position(2, 2)]);
+        assert_eq!(segments(&stairs).len(), 4);
+
+        let mut maze = Maze::new(Size(12, 9), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(8));
+        let solved = Solution::new(maze.solve_bfs());
+
+        for solution in [stairs, solved, Solution::new(vec![Position(3, 4)])] {
+            let vectors = solution.to_vectors().unwrap();
+
+            assert_eq!(Solution::from_vectors(&vectors), solution);
+        }
+    }
+
+    #[test]
+    fn gaps_in_the_path_are_refused() {
+        let jump = Solution::new(vec![Position(0, 0), Position(2, 1)]);
+
+        assert!(jump.to_vectors().is_err());
+    }
+
+    #[test]
+    fn json_round_trips_through_serde() {
+        let mut maze = Maze::new(Size(12, 9), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(8));
+        let solution = Solution::new(maze.solve_bfs());
+
+        let text = solution.to_json(Some(8)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+        let summary = &value["summary"];
+        assert_eq!(summary["length"], solution.path.len());
+        assert_eq!(summary["turns"], solution.to_vectors().unwrap().len() - 1);
+        assert_eq!(summary["start"], serde_json::json!({"x": 0, "y": 0}));
+        assert_eq!(summary["goal"], serde_json::json!({"x": 11, "y": 8}));
+        assert_eq!(summary["seed"], 8);
+
+        // x is the column and y the row
+        for (step, cell) in value["path"].as_array().unwrap().iter().enumerate() {
+            let pos = solution.path[step];
+            assert_eq!(*cell, serde_json::json!({"step": step, "x": pos.0, "y": pos.1}));
+        }
+
+        // Reading what serde writes back, so the import doesn't depend on the exact layout
+        let pretty = serde_json::to_string_pretty(&value).unwrap();
+        assert_eq!(Solution::from_json(&pretty), Ok(solution));
+    }
+
+    #[test]
+    fn unknown_json_fields_are_null() {
+        let value: serde_json::Value = serde_json::from_str(&Solution::new(vec![]).to_json(None).unwrap()).unwrap();
+
+        assert_eq!(value, serde_json::json!({
+            "summary": {"length": 0, "turns": 0, "start": null, "goal": null, "seed": null},
+            "path": []
+        }));
+    }
+}
@@ -0,0 +1,253 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::io;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::mouse::MOUSE_STEPS_PER_CELL;
+use crate::pledge::heading_towards;
+use crate::{Maze, Position};
+
+/// A way of finding a path through a maze. Implementing it is all it takes for an outside
+/// algorithm to be used through `Maze::solve_with`.
+pub trait Solver {
+    /// A path from `start` to `goal`, both included, moving one cell at a time like the
+    /// paths `Display::draw_solution` takes. Fails with `InvalidInput` if either end is
+    /// outside the maze and `NotFound` if no path was found.
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind>;
+
+    /// Like `solve`, drawing anything random from `rng` and adding the number of cells the
+    /// search expanded to `expanded`, one for every step it took. Solvers that don't keep
+    /// count leave it as it is, and ones that don't use randomness ignore `rng`.
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        let _ = (rng, expanded);
+
+        self.solve(maze, start, goal)
+    }
+}
+
+/// The randomized depth first search of `Maze::solve_maze`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DfsSolver;
+impl Solver for DfsSolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        maze.solve_between(start, goal)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        maze.solve_dfs(start, goal, rng, expanded)
+            .ok_or(io::ErrorKind::NotFound)
+    }
+}
+
+/// The shortest path by breadth first search, see `Maze::solve_bfs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BfsSolver;
+impl Solver for BfsSolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_counted(maze, start, goal, &mut rand::rng(), &mut 0)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, _rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        match maze.solve_bfs_between(start, goal, expanded) {
+            path if path.is_empty() => Err(io::ErrorKind::NotFound),
+            path => Ok(path),
+        }
+    }
+}
+
+/// Trémaux's algorithm, see `Maze::solve_tremaux`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TremauxSolver;
+impl Solver for TremauxSolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_counted(maze, start, goal, &mut rand::rng(), &mut 0)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, _rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        match maze.solve_tremaux_between(start, goal, expanded).0 {
+            path if path.is_empty() => Err(io::ErrorKind::NotFound),
+            path => Ok(path),
+        }
+    }
+}
+
+/// Greedy best first search, see `Maze::solve_greedy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GreedySolver;
+impl Solver for GreedySolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_counted(maze, start, goal, &mut rand::rng(), &mut 0)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, _rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        maze.solve_greedy_between(start, goal, expanded)
+            .ok_or(io::ErrorKind::NotFound)
+    }
+}
+
+/// A mouse wandering at random, see `Maze::solve_random_mouse`. Its path is every cell it
+/// stepped on, repeats and all, and it fails with `NotFound` once it has made
+/// `MOUSE_STEPS_PER_CELL` moves per cell without getting there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomMouseSolver;
+impl Solver for RandomMouseSolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_counted(maze, start, goal, &mut rand::rng(), &mut 0)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        maze.solve_random_mouse_between(start, goal, rng, maze.tiles.len() * MOUSE_STEPS_PER_CELL, expanded)
+            .ok_or(io::ErrorKind::NotFound)
+    }
+}
+
+/// The Pledge algorithm, see `Maze::solve_pledge`, preferring the direction
+/// `heading_towards` gives from the start to the goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PledgeSolver;
+impl Solver for PledgeSolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_counted(maze, start, goal, &mut rand::rng(), &mut 0)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, _rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        maze.solve_pledge_between(start, goal, heading_towards(start, goal), expanded)
+            .ok_or(io::ErrorKind::NotFound)
+    }
+}
+
+/// Dijkstra's algorithm with every cell costing the same, see `Maze::solve_dijkstra`. It
+/// finds a shortest path like `BfsSolver`, going about it by cost rather than by layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DijkstraSolver;
+impl Solver for DijkstraSolver {
+    fn solve(&self, maze: &Maze, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        self.solve_counted(maze, start, goal, &mut rand::rng(), &mut 0)
+    }
+
+    fn solve_counted(&self, maze: &Maze, start: Position, goal: Position, _rng: &mut dyn RngCore, expanded: &mut usize) -> Result<Vec<Position>, io::ErrorKind> {
+        check_ends(maze, start, goal)?;
+
+        maze.solve_dijkstra_between(start, goal, Array2::from_elem(maze.size.as_array(), 1), expanded)
+            .map(|weighted| weighted.path)
+            .ok_or(io::ErrorKind::NotFound)
+    }
+}
+
+/// The built-in solvers, for code that picks one by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum SolverKind {
+    Dfs,
+    Bfs,
+    Tremaux,
+    Greedy,
+    #[strum(serialize = "random-mouse")]
+    RandomMouse,
+    Pledge,
+    Dijkstra,
+}
+impl SolverKind {
+    pub fn solver(&self) -> &'static dyn Solver {
+        match self {
+            Self::Dfs => &DfsSolver,
+            Self::Bfs => &BfsSolver,
+            Self::Tremaux => &TremauxSolver,
+            Self::Greedy => &GreedySolver,
+            Self::RandomMouse => &RandomMouseSolver,
+            Self::Pledge => &PledgeSolver,
+            Self::Dijkstra => &DijkstraSolver,
+        }
+    }
+}
+
+impl Maze {
+    /// Runs `solver` from `start` to `goal`.
+    pub fn solve_with(&self, solver: &dyn Solver, start: Position, goal: Position) -> Result<Vec<Position>, io::ErrorKind> {
+        solver.solve(self, start, goal)
+    }
+
+    /// Like `solve_with`, with anything random `solver` does drawn from `rng`.
+    pub fn solve_with_rng(&self, solver: &dyn Solver, start: Position, goal: Position, rng: &mut dyn RngCore) -> Result<Vec<Position>, io::ErrorKind> {
+        solver.solve_counted(self, start, goal, rng, &mut 0)
+    }
+}
+
+fn check_ends(maze: &Maze, start: Position, goal: Position) -> Result<(), io::ErrorKind> {
+    match maze.get_tile(start).is_some() && maze.get_tile(goal).is_some() {
+        true => Ok(()),
+        false => Err(io::ErrorKind::InvalidInput),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Pcg32;
+    use crate::Size;
+
+    // Loops leave the depth first search a choice of paths
+    fn braided_maze() -> Maze {
+        let mut maze = Maze::new(Size(8, 6), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(21));
+        maze.braid_with_rng(1.0, &mut Pcg32::new(22));
+        maze
+    }
+
+    #[test]
+    fn every_kind_walks_to_the_goal_like_its_steps() {
+        use strum::IntoEnumIterator;
+
+        let mut maze = Maze::new(Size(9, 7), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(4));
+
+        let (start, goal) = (Position::new(), maze.size.get_max_pos());
+        let shortest = maze.shortest_path(start, goal).unwrap();
+
+        for kind in SolverKind::iter() {
+            let path = maze.solve_with_rng(kind.solver(), start, goal, &mut Pcg32::new(6)).unwrap();
+
+            assert_eq!((path[0], path[path.len() - 1]), (start, goal), "{}", kind);
+            assert!(path.windows(2).all(|step| step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1) == 1), "{}", kind);
+
+            // The mouse's path is all of its wandering, the rest find the only simple one
+            if kind != SolverKind::RandomMouse {
+                assert_eq!(path, shortest, "{}", kind);
+            }
+
+            let mut steps = maze.solve_steps_with_rng(kind, Pcg32::new(6));
+            while steps.next().is_some() {}
+
+            assert_eq!(steps.path(), Some(path), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn seeded_solving_repeats() {
+        let maze = braided_maze();
+        let (start, goal) = (Position::new(), maze.size.get_max_pos());
+
+        let paths: Vec<Vec<Position>> = (0..8)
+            .map(|seed| maze.solve_with_rng(&DfsSolver, start, goal, &mut Pcg32::new(seed)).unwrap())
+            .collect();
+
+        for (seed, path) in paths.iter().enumerate() {
+            assert_eq!(&maze.solve_between_with_rng(start, goal, &mut Pcg32::new(seed as u64)).unwrap(), path);
+            assert_eq!(&maze.solve_maze_with_rng(&mut Pcg32::new(seed as u64)), path);
+        }
+
+        assert!(paths.iter().any(|path| *path != paths[0]), "the seed made no difference");
+    }
+}
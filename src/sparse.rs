@@ -0,0 +1,158 @@
+use ndarray::Array2;
+use rand::RngCore;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::rng::{pick, random_index};
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    pub fn generate_sparse(&mut self, fill: f64) -> Result<(), io::ErrorKind> {
+        self.generate_sparse_with_rng(fill, &mut rand::rng())
+    }
+
+    /// Carves a tree over roughly `fill` of the cells of a fully walled maze and leaves the
+    /// rest solid. A loop-erased random walk from the bottom right corner to `(0, 0)` is
+    /// carved first so the usual solution always exists, even if that alone takes more than
+    /// `fill` of the cells. Random cells of the tree then grow into their unvisited
+    /// neighbours until it is big enough, which leaves the solid cells in large clumps.
+    pub fn generate_sparse_with_rng<R: RngCore>(&mut self, fill: f64, rng: &mut R) -> Result<(), io::ErrorKind> {
+        if !(0.0..=1.0).contains(&fill) {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let target = (fill * self.tiles.len() as f64).ceil() as usize;
+
+        let mut in_tree = Array2::from_elem(self.size.as_array(), false);
+        let mut exits: Array2<Option<Direction>> = Array2::from_elem(self.size.as_array(), None);
+
+        in_tree[Position::new().as_array()] = true;
+
+        // Walking over a cell again overwrites the direction it was left in, which erases
+        // the loop in between
+        let mut pos = self.size.get_max_pos();
+
+        while !in_tree[pos.as_array()] {
+            let directions: Vec<Direction> = Direction::iter()
+                .filter(|direction| self.get_neighbor(pos, *direction).is_some())
+                .collect();
+
+            let direction = pick(rng, &directions).unwrap();
+
+            exits[pos.as_array()] = Some(direction);
+            pos = pos.translate(direction);
+        }
+
+        let mut active = vec![Position::new()];
+        let mut pos = self.size.get_max_pos();
+
+        while !in_tree[pos.as_array()] {
+            let direction = exits[pos.as_array()].unwrap();
+
+            in_tree[pos.as_array()] = true;
+            active.push(pos);
+            self.set_wall(pos, direction, false)?;
+
+            pos = pos.translate(direction);
+        }
+
+
+        let mut carved = active.len();
+
+        while carved < target && !active.is_empty() {
+            let index = random_index(rng, active.len());
+            let pos = active[index];
+
+            let directions: Vec<Direction> = Direction::iter()
+                .filter(|direction| {
+                    self.get_neighbor(pos, *direction)
+                        .is_some_and(|neighbor| !in_tree[neighbor.as_array()])
+                })
+                .collect();
+
+            let Some(direction) = pick(rng, &directions) else {
+                active.swap_remove(index);
+                continue;
+            };
+
+            let next = pos.translate(direction);
+
+            self.set_wall(pos, direction, false)?;
+            in_tree[next.as_array()] = true;
+
+            active.push(next);
+            carved += 1;
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+    use std::io;
+
+    // Cells reached from (0, 0) and walls open between cells
+    fn tree_size(maze: &Maze) -> (usize, usize) {
+        let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+        let open: usize = maze.tiles.iter()
+            .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+            .sum();
+
+        (reached, open / 2)
+    }
+
+    #[test]
+    fn the_tree_covers_the_fill_and_the_corners() {
+        for (fill, seed) in [(0.3, 1), (0.5, 2), (0.8, 3)] {
+            let mut maze = Maze::new(Size(16, 12), true);
+            maze.generate_sparse_with_rng(fill, &mut Pcg32::new(seed)).unwrap();
+
+            let (reached, open) = tree_size(&maze);
+            let target = (fill * 192.0).ceil() as usize;
+
+            // The first walk can overshoot the fill on its own
+            assert!(reached >= target && open == reached - 1, "fill {fill}");
+            assert!(!maze.solve_bfs().is_empty(), "fill {fill}");
+
+            let distances = maze.distances_from(Position(0, 0));
+
+            for ((x, y), tile) in maze.tiles.indexed_iter() {
+                if distances[[x, y]].is_none() {
+                    assert!(tile.get_sides().iter().all(|(_, closed)| *closed), "fill {fill} at {x},{y}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn full_and_empty_fills() {
+        let mut full = Maze::new(Size(9, 7), true);
+        full.generate_sparse_with_rng(1.0, &mut Pcg32::new(4)).unwrap();
+
+        assert_eq!(tree_size(&full), (63, 62));
+
+        // Just the walk between the corners, without any branches
+        let mut empty = Maze::new(Size(9, 7), true);
+        empty.generate_sparse_with_rng(0.0, &mut Pcg32::new(4)).unwrap();
+
+        assert_eq!(tree_size(&empty).0, empty.solve_bfs().len());
+    }
+
+    #[test]
+    fn fills_outside_zero_to_one_are_refused() {
+        let mut maze = Maze::new(Size(4, 4), true);
+
+        for fill in [-0.1, 1.5, f64::NAN] {
+            assert_eq!(maze.generate_sparse_with_rng(fill, &mut Pcg32::new(1)), Err(io::ErrorKind::InvalidInput));
+        }
+
+        assert_eq!(maze, Maze::new(Size(4, 4), true));
+    }
+}
@@ -0,0 +1,241 @@
+use ndarray::Array2;
+use rand::rngs::ThreadRng;
+use rand::RngCore;
+use std::io;
+
+use crate::distance::{BfsSearch, DijkstraSearch, GreedySearch};
+use crate::mouse::{MouseSearch, MOUSE_STEPS_PER_CELL};
+use crate::pledge::{heading_towards, PledgeSearch};
+use crate::solver::SolverKind;
+use crate::tremaux::TremauxSearch;
+use crate::{DfsSearch, Maze, Position};
+
+/// What one step of `SolveSteps` did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchStep {
+    /// The cell the search is at after the step. For the breadth first search that is the
+    /// cell it reached out from.
+    pub current: Position,
+    /// Cells seen for the first time on this step, in the order they were reached.
+    pub discovered: Vec<Position>,
+}
+
+enum Search {
+    Dfs(DfsSearch),
+    Bfs(BfsSearch),
+    Tremaux(TremauxSearch),
+    Greedy(GreedySearch),
+    Mouse(MouseSearch),
+    Pledge(PledgeSearch),
+    Dijkstra(DijkstraSearch),
+}
+
+/// A solver run one step at a time, see `Maze::solve_steps`. Each step only hands out what
+/// changed, the whole state can be looked at between steps with `visited` and `frontier`.
+/// The solvers themselves run on the same steps, so draining the iterator and taking the
+/// `path` gives what they give.
+pub struct SolveSteps<'a, R: RngCore> {
+    maze: &'a Maze,
+    search: Search,
+    visited: Array2<bool>,
+    finished: bool,
+    rng: R,
+}
+impl<R: RngCore> SolveSteps<'_, R> {
+    /// Every cell the search has been on, crossings included.
+    pub fn visited(&self) -> &Array2<bool> {
+        &self.visited
+    }
+
+    /// The cells the search can still go on from. That is the path it is on for the depth
+    /// first search, Trémaux and Pledge, the queue for the breadth first, greedy and
+    /// Dijkstra searches and the cell the random mouse is on.
+    pub fn frontier(&self) -> Vec<Position> {
+        match &self.search {
+            Search::Dfs(search) => search.frontier(),
+            Search::Bfs(search) => search.frontier(),
+            Search::Tremaux(search) => search.frontier(),
+            Search::Greedy(search) => search.frontier(),
+            Search::Mouse(search) => search.frontier(),
+            Search::Pledge(search) => search.frontier(),
+            Search::Dijkstra(search) => search.frontier(),
+        }
+    }
+
+    /// The path from the start to the goal once the search is done, `None` before that or
+    /// if the goal can't be reached.
+    pub fn path(&self) -> Option<Vec<Position>> {
+        if !self.finished {
+            return None;
+        }
+
+        match &self.search {
+            Search::Dfs(search) => search.path(),
+            Search::Bfs(search) => search.path(self.maze),
+            Search::Tremaux(search) => search.path(),
+            Search::Greedy(search) => search.path(self.maze),
+            Search::Mouse(search) => search.path(),
+            Search::Pledge(search) => search.path(),
+            Search::Dijkstra(search) => search.path(self.maze),
+        }
+    }
+}
+impl<R: RngCore> Iterator for SolveSteps<'_, R> {
+    type Item = SearchStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = match &mut self.search {
+            Search::Dfs(search) => search.step(self.maze, &mut self.rng),
+            Search::Bfs(search) => search.step(self.maze),
+            Search::Tremaux(search) => search.step(self.maze),
+            Search::Greedy(search) => search.step(self.maze),
+            Search::Mouse(search) => search.step(self.maze, &mut self.rng),
+            Search::Pledge(search) => search.step(self.maze),
+            Search::Dijkstra(search) => search.step(self.maze),
+        };
+
+        let Some((current, entered)) = step else {
+            self.finished = true;
+            return None;
+        };
+
+        let mut discovered = vec![];
+
+        for pos in entered {
+            if !self.visited[pos.as_array()] {
+                self.visited[pos.as_array()] = true;
+                discovered.push(pos);
+            }
+        }
+
+        Some(SearchStep { current, discovered })
+    }
+}
+
+impl Maze {
+    /// Runs the solver `algo` from `(0, 0)` to the bottom right corner one step at a time,
+    /// for watching the search unfold.
+    pub fn solve_steps(&self, algo: SolverKind) -> SolveSteps<'_, ThreadRng> {
+        self.solve_steps_with_rng(algo, rand::rng())
+    }
+
+    /// Like `solve_steps`, with the depth first search and the random mouse drawing from
+    /// `rng`.
+    pub fn solve_steps_with_rng<R: RngCore>(&self, algo: SolverKind, rng: R) -> SolveSteps<'_, R> {
+        self.solve_steps_between(algo, Position::new(), self.size.get_max_pos(), rng)
+            .expect("The corners are in the maze")
+    }
+
+    /// Like `solve_steps_with_rng`, from `start` to `goal` instead of between the corners.
+    /// Fails if either is outside the maze.
+    pub fn solve_steps_between<R: RngCore>(
+        &self,
+        algo: SolverKind,
+        start: Position,
+        goal: Position,
+        rng: R
+    ) -> Result<SolveSteps<'_, R>, io::ErrorKind> {
+        if self.get_tile(start).is_none() || self.get_tile(goal).is_none() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        let search = match algo {
+            SolverKind::Dfs => Search::Dfs(DfsSearch::new(self, start, goal)),
+            SolverKind::Bfs => Search::Bfs(BfsSearch::new(self, start, goal)),
+            SolverKind::Tremaux => Search::Tremaux(TremauxSearch::new(self, start, goal)),
+            SolverKind::Greedy => Search::Greedy(GreedySearch::new(self, start, goal)),
+            SolverKind::RandomMouse => Search::Mouse(MouseSearch::new(start, goal, self.tiles.len() * MOUSE_STEPS_PER_CELL)),
+            SolverKind::Pledge => Search::Pledge(PledgeSearch::new(self, start, goal, heading_towards(start, goal))),
+            SolverKind::Dijkstra => Search::Dijkstra(DijkstraSearch::new(start, goal, Array2::from_elem(self.size.as_array(), 1))),
+        };
+
+        let mut visited = Array2::from_elem(self.size.as_array(), false);
+        visited[start.as_array()] = true;
+
+        Ok(SolveSteps {
+            maze: self,
+            search,
+            visited,
+            finished: false,
+            rng,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::solver::SolverKind;
+    use crate::{Maze, Position, Size};
+    use std::io;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn draining_the_steps_gives_the_solvers_path() {
+        let mut maze = Maze::new(Size(10, 8), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(7));
+        maze.braid_with_rng(0.5, &mut Pcg32::new(7));
+
+        let (start, goal) = (Position::new(), maze.size.get_max_pos());
+
+        for solver in SolverKind::iter() {
+            let mut steps = maze.solve_steps_with_rng(solver, Pcg32::new(3));
+            steps.by_ref().for_each(drop);
+
+            let solved = maze.solve_with_rng(solver.solver(), start, goal, &mut Pcg32::new(3));
+            assert_eq!(steps.path(), solved.ok(), "{solver}");
+        }
+    }
+
+    #[test]
+    fn each_cell_is_discovered_once() {
+        let mut maze = Maze::new(Size(10, 8), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(1));
+
+        for solver in SolverKind::iter() {
+            let mut steps = maze.solve_steps_with_rng(solver, Pcg32::new(1));
+            let mut discovered = vec![Position::new()];
+
+            for step in steps.by_ref() {
+                for pos in step.discovered {
+                    assert!(!discovered.contains(&pos), "{solver} found {pos:?} twice");
+                    discovered.push(pos);
+                }
+            }
+
+            assert_eq!(discovered.len(), steps.visited().iter().filter(|seen| **seen).count(), "{solver}");
+        }
+    }
+
+    #[test]
+    fn the_path_waits_for_the_search_to_finish() {
+        let maze = Maze::new(Size(4, 4), false);
+        let mut steps = maze.solve_steps_with_rng(SolverKind::Bfs, Pcg32::new(0));
+
+        assert_eq!(steps.frontier(), vec![Position::new()]);
+
+        steps.next().unwrap();
+        assert_eq!(steps.path(), None);
+
+        steps.by_ref().for_each(drop);
+        assert_eq!(steps.path().map(|path| path.len()), Some(7));
+    }
+
+    #[test]
+    fn steps_run_between_any_two_cells() {
+        let mut maze = Maze::new(Size(9, 7), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(4));
+
+        let (start, goal) = (Position(8, 0), Position(2, 5));
+
+        for solver in SolverKind::iter() {
+            let mut steps = maze.solve_steps_between(solver, start, goal, Pcg32::new(4)).unwrap();
+            steps.by_ref().for_each(drop);
+
+            let path = steps.path().unwrap();
+            assert_eq!((path[0], path[path.len() - 1]), (start, goal), "{solver}");
+        }
+
+        assert_eq!(maze.solve_steps_between(SolverKind::Bfs, start, Position(9, 0), Pcg32::new(4)).err(), Some(io::ErrorKind::InvalidInput));
+    }
+}
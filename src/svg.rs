@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use crate::{Maze, Position};
+
+/// Sizes in user units and colors for `Maze::to_svg`. Colors are anything SVG takes, like
+/// `black` or `#c03030`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    pub cell_size: f64,
+    pub stroke_width: f64,
+    pub wall_color: String,
+    pub path_color: String,
+    pub background: Option<String>,
+}
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 16.0,
+            stroke_width: 2.0,
+            wall_color: "black".to_string(),
+            path_color: "#dc2828".to_string(),
+            background: Some("white".to_string()),
+        }
+    }
+}
+
+impl Maze {
+    /// The maze as an SVG document. All walls go into a single `<path>`, with walls in a
+    /// straight line joined into one stroke and walls between two cells drawn once, so even
+    /// big mazes stay small. `solution` is drawn over them as a `<polyline>` through the
+    /// middle of its cells.
+    pub fn to_svg(&self, solution: Option<&[Position]>, opts: &SvgOptions) -> String {
+        let (width, height) = (self.size.0, self.size.1);
+        let margin = opts.stroke_width;
+        let cell = opts.cell_size;
+
+        let corner = |x: usize, y: usize| (margin + x as f64 * cell, margin + y as f64 * cell);
+
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            width as f64 * cell + 2.0 * margin,
+            height as f64 * cell + 2.0 * margin
+        ).unwrap();
+
+        if let Some(background) = &opts.background {
+            writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, escape(background)).unwrap();
+        }
+
+        let mut walls = String::new();
+
+        // Rows of horizontal walls from the top edge down, each run of walls becoming one
+        // stroke. A wall counts if the cell on either side of it has it.
+        for y in 0..=height {
+            let is_wall = |x: usize| {
+                (y < height && self.tiles[[x, y]].up) || (y > 0 && self.tiles[[x, y - 1]].down)
+            };
+
+            for (from, to) in runs(width, is_wall) {
+                let (start, end) = (corner(from, y), corner(to, y));
+                write!(walls, "M{} {}H{}", start.0, start.1, end.0).unwrap();
+            }
+        }
+
+        // And the same for columns of vertical walls from the left edge on
+        for x in 0..=width {
+            let is_wall = |y: usize| {
+                (x < width && self.tiles[[x, y]].left) || (x > 0 && self.tiles[[x - 1, y]].right)
+            };
+
+            for (from, to) in runs(height, is_wall) {
+                let (start, end) = (corner(x, from), corner(x, to));
+                write!(walls, "M{} {}V{}", start.0, start.1, end.1).unwrap();
+            }
+        }
+
+        writeln!(
+            svg,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="square"/>"#,
+            walls,
+            escape(&opts.wall_color),
+            opts.stroke_width
+        ).unwrap();
+
+        if let Some(path) = solution.filter(|path| !path.is_empty()) {
+            let points: Vec<String> = path.iter()
+                .map(|pos| {
+                    let (x, y) = corner(pos.0, pos.1);
+                    format!("{},{}", x + cell / 2.0, y + cell / 2.0)
+                })
+                .collect();
+
+            writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"/>"#,
+                points.join(" "),
+                escape(&opts.path_color),
+                opts.stroke_width
+            ).unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+// The stretches of consecutive indices below `length` where `is_wall` holds, each as the
+// index it starts at and the one after it ends
+fn runs(length: usize, is_wall: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+    let mut runs = vec![];
+    let mut start = None;
+
+    for i in 0..=length {
+        match (start, i < length && is_wall(i)) {
+            (None, true) => start = Some(i),
+            (Some(from), false) => {
+                runs.push((from, i));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+
+    runs
+}
+
+// Keeps option values from breaking out of the attribute they are written into
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{runs, SvgOptions};
+    use crate::rng::Pcg32;
+    use crate::test_support::opened;
+    use crate::{Direction, Maze, Position, Size};
+
+    #[test]
+    fn a_small_maze_is_pinned() {
+        let maze = opened(Size(2, 1), &[(Position(0, 0), Direction::East)]);
+        let svg = maze.to_svg(Some(&[Position(0, 0), Position(1, 0)]), &SvgOptions::default());
+
+        // The top and bottom are one stroke each and the opened wall between the cells is left out
+        let expected = [
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="36" height="20" viewBox="0 0 36 20">"#,
+            r#"<rect width="100%" height="100%" fill="white"/>"#,
+            r#"<path d="M2 2H34M2 18H34M2 2V18M34 2V18" fill="none" stroke="black" stroke-width="2" stroke-linecap="square"/>"#,
+            r##"<polyline points="10,10 26,10" fill="none" stroke="#dc2828" stroke-width="2" stroke-linejoin="round"/>"##,
+            "</svg>",
+        ];
+
+        assert_eq!(svg, expected.map(|line| format!("{line}\n")).concat());
+    }
+
+    #[test]
+    fn the_document_is_well_formed() {
+        let mut maze = Maze::new(Size(12, 9), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(3));
+
+        let svg = maze.to_svg(Some(&maze.solve_bfs()), &SvgOptions::default());
+        let lines: Vec<&str> = svg.lines().collect();
+
+        // One root element around self-closed ones, with every attribute quoted in pairs
+        assert!(lines[0].starts_with("<svg ") && lines[0].ends_with('>'));
+        assert_eq!(lines[lines.len() - 1], "</svg>");
+
+        for line in &lines[1..lines.len() - 1] {
+            assert!(line.starts_with('<') && line.ends_with("/>"), "{line}");
+            assert_eq!(line.matches('"').count() % 2, 0, "{line}");
+            assert_eq!(line.matches('<').count(), 1, "{line}");
+        }
+
+        assert_eq!(svg.matches("<polyline ").count(), 1);
+    }
+
+    #[test]
+    fn option_values_are_escaped() {
+        let maze = Maze::new(Size(1, 1), true);
+        let opts = SvgOptions { wall_color: r#"red" onload="x<y&"#.to_string(), ..SvgOptions::default() };
+
+        assert!(maze.to_svg(None, &opts).contains(r#"stroke="red&quot; onload=&quot;x&lt;y&amp;""#));
+    }
+
+    #[test]
+    fn runs_join_neighboring_walls() {
+        let walls = [true, true, false, true, false, false, true];
+
+        assert_eq!(runs(walls.len(), |i| walls[i]), vec![(0, 2), (3, 4), (6, 7)]);
+        assert_eq!(runs(3, |_| false), vec![]);
+    }
+}
@@ -0,0 +1,171 @@
+use ndarray::Array2;
+use rand::RngCore;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::rng::pick;
+use crate::{Direction, Maze, Position, Size};
+
+/// How `Maze::generate_symmetric` copies the walls from one half of the maze to the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, strum_macros::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Symmetry {
+    /// Mirrored left to right.
+    Horizontal,
+    /// Mirrored top to bottom.
+    Vertical,
+    /// Unchanged by turning the maze half way round.
+    Rotational,
+}
+impl Symmetry {
+    /// The cell `pos` maps to in a maze of `size`.
+    pub fn apply(&self, size: Size, pos: Position) -> Position {
+        let max = size.get_max_pos();
+
+        match self {
+            Self::Horizontal => Position(max.0 - pos.0, pos.1),
+            Self::Vertical => Position(pos.0, max.1 - pos.1),
+            Self::Rotational => Position(max.0 - pos.0, max.1 - pos.1),
+        }
+    }
+
+    /// The side a wall on `direction` of a cell ends up on in its image.
+    pub fn apply_direction(&self, direction: Direction) -> Direction {
+        match (self, direction) {
+            (Self::Horizontal, Direction::North | Direction::South)
+            | (Self::Vertical, Direction::East | Direction::West) => direction,
+            _ => direction.get_opposite(),
+        }
+    }
+
+    // The half the generator carves, which together with its image covers the maze. A middle
+    // line on the axis belongs to both halves, and for the rotation so does the middle cell.
+    fn in_half(&self, size: Size, pos: Position) -> bool {
+        let image = self.apply(size, pos);
+
+        match self {
+            Self::Horizontal => pos.0 <= image.0,
+            Self::Vertical => pos.1 <= image.1,
+            Self::Rotational => (pos.1, pos.0) <= (image.1, image.0),
+        }
+    }
+}
+
+impl Maze {
+    pub fn generate_symmetric(&mut self, symmetry: Symmetry) {
+        self.generate_symmetric_with_rng(symmetry, &mut rand::rng());
+    }
+
+    /// Carves a spanning tree over one half of a fully walled maze with the backtracker,
+    /// opening the image of every wall it opens. If no cell is its own image the halves
+    /// don't touch yet, and one random wall across the axis is opened with its image to join
+    /// them. Both can add loops, so the maze is connected but usually not perfect.
+    pub fn generate_symmetric_with_rng<R: RngCore>(&mut self, symmetry: Symmetry, rng: &mut R) {
+        let size = self.size;
+
+        let mut explored = Array2::from_elem(size.as_array(), false);
+        explored[Position::new().as_array()] = true;
+
+        let mut stack = vec![Position::new()];
+
+        while let Some(pos) = stack.last().copied() {
+            let dirs: Vec<Direction> = Direction::iter()
+                .filter(|direction| {
+                    self.get_neighbor(pos, *direction)
+                        .is_some_and(|neighbor| symmetry.in_half(size, neighbor) && !explored[neighbor.as_array()])
+                })
+                .collect();
+
+            let Some(direction) = pick(rng, &dirs) else {
+                stack.pop();
+                continue;
+            };
+
+            let next = pos.translate(direction);
+
+            self.carve_symmetric(symmetry, pos, direction);
+            explored[next.as_array()] = true;
+
+            stack.push(next);
+        }
+
+        let shares_cells = self.tiles
+            .indexed_iter()
+            .any(|((x, y), _)| symmetry.apply(size, Position(x, y)) == Position(x, y));
+
+        if !shares_cells {
+            let crossings: Vec<(Position, Direction)> = self.tiles
+                .indexed_iter()
+                .map(|((x, y), _)| Position(x, y))
+                .filter(|pos| symmetry.in_half(size, *pos))
+                .flat_map(|pos| Direction::iter().map(move |direction| (pos, direction)))
+                .filter(|(pos, direction)| {
+                    self.get_neighbor(*pos, *direction)
+                        .is_some_and(|neighbor| !symmetry.in_half(size, neighbor))
+                })
+                .collect();
+
+            let (pos, direction) = pick(rng, &crossings).unwrap();
+
+            self.carve_symmetric(symmetry, pos, direction);
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+
+    /// Whether every wall matches the wall it maps to under `symmetry`.
+    pub fn is_symmetric(&self, symmetry: Symmetry) -> bool {
+        self.tiles.indexed_iter().all(|((x, y), tile)| {
+            let image = self
+                .get_tile(symmetry.apply(self.size, Position(x, y)))
+                .unwrap();
+
+            Direction::iter().all(|direction| tile.get_side(direction) == image.get_side(symmetry.apply_direction(direction)))
+        })
+    }
+
+    fn carve_symmetric(&mut self, symmetry: Symmetry, pos: Position, direction: Direction) {
+        self.set_wall(pos, direction, false)
+            .unwrap();
+
+        self.set_wall(symmetry.apply(self.size, pos), symmetry.apply_direction(direction), false)
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symmetry;
+    use crate::rng::Pcg32;
+    use crate::test_support::reflected;
+    use crate::{Maze, Position, Size};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn reflecting_gives_the_same_maze() {
+        for symmetry in Symmetry::iter() {
+            for size in [Size(1, 1), Size(1, 6), Size(6, 1), Size(2, 2), Size(8, 6), Size(7, 5), Size(8, 5)] {
+                for seed in 0..5 {
+                    let mut maze = Maze::new(size, true);
+                    maze.generate_symmetric_with_rng(symmetry, &mut Pcg32::new(seed));
+
+                    assert!(maze.is_symmetric(symmetry), "{symmetry} {size:?} seed {seed}");
+                    assert_eq!(reflected(&maze, symmetry), maze, "{symmetry} {size:?} seed {seed}");
+
+                    let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+                    assert_eq!(reached, size.0 * size.1, "{symmetry} {size:?} seed {seed}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordinary_mazes_arent_symmetric() {
+        let mut maze = Maze::new(Size(8, 6), true);
+        maze.generate_maze_with_rng(&mut Pcg32::new(2));
+
+        assert!(Symmetry::iter().all(|symmetry| !maze.is_symmetric(symmetry)));
+    }
+}
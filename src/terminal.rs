@@ -0,0 +1,75 @@
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::{Display, Margins, Size};
+
+/// Columns and rows of the terminal on stdin, asking `stty` first and falling back to the
+/// `COLUMNS` and `LINES` variables.
+pub fn terminal_size() -> Option<Size> {
+    let from_stty = stty(&["size"])
+        .ok()
+        .and_then(|out| {
+            let (rows, columns) = out.trim().split_once(' ')?;
+            Some(Size(columns.parse().ok()?, rows.parse().ok()?))
+        });
+
+    from_stty.or_else(|| {
+        let variable = |name| std::env::var(name).ok()?.parse().ok();
+        Some(Size(variable("COLUMNS")?, variable("LINES")?))
+    })
+}
+
+/// The biggest maze whose display with default margins fits a terminal of `terminal`
+/// columns and rows, leaving `reserved_rows` free below it. `None` if not even one cell fits.
+pub fn fit_size(terminal: Size, reserved_rows: usize) -> Option<Size> {
+    Display::fit_maze(terminal, Margins::default(), false, reserved_rows)
+}
+
+/// Puts the terminal in raw mode on the alternate screen with the cursor hidden, and puts
+/// everything back the way it was when dropped. In raw mode Ctrl-C arrives as the byte 3
+/// instead of a signal, so the program decides how to stop and the guard always runs.
+pub struct RawTerminal {
+    saved: String,
+}
+impl RawTerminal {
+    pub fn enter() -> io::Result<Self> {
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+
+        let mut stdout = io::stdout().lock();
+        write!(stdout, "\x1b[?1049h\x1b[?25l\x1b[2J\x1b[H")?;
+        stdout.flush()?;
+
+        Ok(Self {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout().lock();
+        let _ = write!(stdout, "\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = stdout.flush();
+
+        let _ = stty(&[self.saved.as_str()]);
+    }
+}
+
+// `stty` works on its stdin, so that has to be the terminal rather than a pipe
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::ErrorKind::Other.into());
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| io::ErrorKind::InvalidData.into())
+}
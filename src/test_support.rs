@@ -0,0 +1,29 @@
+use crate::symmetry::Symmetry;
+use crate::{Direction, Maze, Position, Size};
+
+// The maze with every wall moved to its image under `symmetry`
+pub(crate) fn reflected(maze: &Maze, symmetry: Symmetry) -> Maze {
+    let mut out = Maze::new(maze.size, true);
+
+    for ((x, y), tile) in maze.tiles.indexed_iter() {
+        for (direction, closed) in tile.get_sides() {
+            if !closed && maze.get_neighbor(Position(x, y), direction).is_some() {
+                let image = symmetry.apply(maze.size, Position(x, y));
+                out.set_wall(image, symmetry.apply_direction(direction), false).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+// A closed maze with just `walls` opened
+pub(crate) fn opened(size: Size, walls: &[(Position, Direction)]) -> Maze {
+    let mut maze = Maze::new(size, true);
+
+    for (pos, direction) in walls {
+        maze.open_wall(*pos, *direction).unwrap();
+    }
+
+    maze
+}
@@ -0,0 +1,215 @@
+use ndarray::Array2;
+
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    /// Solves the maze from `(0, 0)` to the bottom right corner with Trémaux's algorithm,
+    /// which only ever looks at the cell it is in and still finishes on mazes with loops.
+    /// Every passage is marked each time it is walked, and one marked twice is never taken
+    /// again. In a cell seen for the first time it takes a passage without marks, and when
+    /// it comes back to a cell it has seen through a passage marked once it turns around.
+    /// Otherwise it takes the passage with the fewest marks.
+    ///
+    /// Returns the path, made of the passages marked once, and how many times each cell was
+    /// entered. The path is empty if the goal can't be reached.
+    pub fn solve_tremaux(&self) -> (Vec<Position>, Array2<u8>) {
+        self.solve_tremaux_between(Position::new(), self.size.get_max_pos(), &mut 0)
+    }
+
+    // `solve_tremaux` between any two cells in the maze, adding the steps it took to `expanded`
+    pub(crate) fn solve_tremaux_between(&self, start: Position, goal: Position, expanded: &mut usize) -> (Vec<Position>, Array2<u8>) {
+        let mut search = TremauxSearch::new(self, start, goal);
+
+        while search.step(self).is_some() {
+            *expanded += 1;
+        }
+
+        (search.path().unwrap_or_default(), search.visits)
+    }
+}
+
+// Where `Maze::solve_tremaux` is between steps, see `steps::SolveSteps`
+pub(crate) struct TremauxSearch {
+    goal: Position,
+    // Marks on each side of each cell, indexed by `Direction`
+    marks: Array2<[u8; 4]>,
+    visits: Array2<u8>,
+    path: Vec<Position>,
+    pos: Position,
+    entry: Option<Direction>,
+}
+impl TremauxSearch {
+    pub(crate) fn new(maze: &Maze, start: Position, goal: Position) -> Self {
+        let mut visits = Array2::from_elem(maze.size.as_array(), 0);
+        visits[start.as_array()] = 1;
+
+        Self {
+            goal,
+            marks: Array2::from_elem(maze.size.as_array(), [0; 4]),
+            visits,
+            path: vec![start],
+            pos: start,
+            entry: None,
+        }
+    }
+
+    // Walks one passage. Returns the cell it leads to and the cells walked onto, `None`
+    // once the goal is reached or every passage out has been marked twice.
+    pub(crate) fn step(&mut self, maze: &Maze) -> Option<(Position, Vec<Position>)> {
+        let pos = self.pos;
+
+        if pos == self.goal || self.path.is_empty() {
+            return None;
+        }
+
+        let moves = maze.get_moves(pos);
+        let mark = |direction: Direction| self.marks[pos.as_array()][direction as usize];
+
+        let others: Vec<(Direction, Position)> = moves.iter()
+            .filter(|(direction, _)| Some(*direction) != self.entry)
+            .copied()
+            .collect();
+
+        let back = moves.iter()
+            .find(|(direction, _)| Some(*direction) == self.entry)
+            .copied();
+
+        let choice = match back {
+            _ if !others.is_empty() && others.iter().all(|(direction, _)| mark(*direction) == 0) => Some(others[0]),
+            Some(back) if mark(back.0) == 1 => Some(back),
+            _ => moves.iter()
+                .filter(|(direction, _)| mark(*direction) < 2)
+                .min_by_key(|(direction, _)| mark(*direction))
+                .copied(),
+        };
+
+        let Some((direction, next)) = choice else {
+            self.path.clear();
+            return None;
+        };
+
+        let backtracking = mark(direction) == 1;
+
+        self.marks[pos.as_array()][direction as usize] += 1;
+        self.marks[next.as_array()][direction.get_opposite() as usize] += 1;
+
+        // Crossings in between are walked too, but a passage through them has no sides to
+        // mark
+        let mut entered = vec![];
+        let mut between = pos;
+
+        while between != next {
+            between = between.translate(direction);
+            entered.push(between);
+            self.visits[between.as_array()] = self.visits[between.as_array()].saturating_add(1);
+        }
+
+        // The passages marked once lead from the start to where the walk is, so walking one
+        // again steps back along them
+        match backtracking {
+            true => {
+                if let Some(index) = self.path.iter().rposition(|x| *x == next) {
+                    self.path.truncate(index + 1);
+                }
+            },
+            false => self.path.extend(&entered),
+        }
+
+        self.pos = next;
+        self.entry = Some(direction.get_opposite());
+
+        Some((next, entered))
+    }
+
+    pub(crate) fn frontier(&self) -> Vec<Position> {
+        self.path.clone()
+    }
+
+    pub(crate) fn path(&self) -> Option<Vec<Position>> {
+        (self.pos == self.goal).then(|| self.path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TremauxSearch;
+    use crate::rng::Pcg32;
+    use crate::test_support::opened;
+    use crate::{Direction, Maze, Position, Size};
+
+    fn run(maze: &Maze) -> TremauxSearch {
+        let mut search = TremauxSearch::new(maze, Position::new(), maze.size.get_max_pos());
+
+        while search.step(maze).is_some() {
+            assert!(search.marks.iter().flatten().all(|marks| *marks <= 2));
+        }
+
+        search
+    }
+
+    #[test]
+    fn dead_ends_are_left_for_good() {
+        // A corridor east with a dead end at (2, 0) and the way to the goal going south
+        let maze = opened(Size(3, 2), &[
+            (Position(0, 0), Direction::East),
+            (Position(1, 0), Direction::East),
+            (Position(1, 0), Direction::South),
+            (Position(1, 1), Direction::East),
+        ]);
+
+        let search = run(&maze);
+
+        // The dead end is tried first, walked twice and never again
+        assert_eq!(search.marks[[1, 0]][Direction::East as usize], 2);
+        assert_eq!(search.visits[[2, 0]], 1);
+        assert_eq!(search.visits[[1, 0]], 2);
+        assert_eq!(search.path(), Some(vec![Position(0, 0), Position(1, 0), Position(1, 1), Position(2, 1)]));
+    }
+
+    #[test]
+    fn closing_a_loop_turns_back_and_takes_the_unmarked_way() {
+        // A ring of four cells, left through (0, 1) towards the goal at (1, 2)
+        let maze = opened(Size(2, 3), &[
+            (Position(0, 0), Direction::East),
+            (Position(0, 0), Direction::South),
+            (Position(1, 0), Direction::South),
+            (Position(0, 1), Direction::East),
+            (Position(0, 1), Direction::South),
+            (Position(0, 2), Direction::East),
+        ]);
+
+        let search = run(&maze);
+
+        // Around the ring back to the start, which is old, so straight back to (0, 1)
+        assert_eq!(search.visits[[0, 0]], 2);
+        assert_eq!(search.marks[[0, 0]][Direction::South as usize], 2);
+
+        // There the way east is marked once and the way south not at all
+        assert_eq!(search.marks[[0, 1]][Direction::East as usize], 1);
+        assert_eq!(search.visits[[1, 1]], 1);
+
+        assert_eq!(search.path(), Some(vec![
+            Position(0, 0), Position(1, 0), Position(1, 1), Position(0, 1), Position(0, 2), Position(1, 2)
+        ]));
+    }
+
+    #[test]
+    fn loopy_mazes_are_solved() {
+        for seed in 0..10 {
+            let mut maze = Maze::new(Size(11, 9), true);
+            maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+            maze.braid_with_rng(1.0, &mut Pcg32::new(seed));
+
+            let search = run(&maze);
+            let (path, visits) = maze.solve_tremaux();
+
+            assert_eq!(search.path(), Some(path.clone()));
+            assert_eq!((path[0], path[path.len() - 1]), (Position(0, 0), Position(10, 8)), "seed {seed}");
+            assert!(path.windows(2).all(|step| maze.get_moves(step[0]).iter().any(|(_, next)| *next == step[1])));
+            assert!(path.iter().all(|pos| visits[pos.as_array()] > 0));
+        }
+
+        // Nothing open, so nothing to walk
+        assert_eq!(Maze::new(Size(3, 3), true).solve_tremaux().0, vec![]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::endpoints::Endpoints;
+use crate::exits::ENTRANCE;
+use crate::{Direction, Maze, Position, Size};
+
+/// The two ends of the corridor `Maze::to_unicursal` makes, with the sides of the border
+/// they face.
+pub const UNICURSAL_ENDS: Endpoints = Endpoints {
+    entrance: ENTRANCE,
+    exit: (Position(0, 1), Direction::West),
+};
+
+impl Maze {
+    /// A labyrinth twice the size of `self` made of one corridor without any junctions. Every
+    /// cell becomes a 2x2 block whose cells form a ring, and every passage becomes two
+    /// parallel corridors joining the rings, so the corridor runs along both sides of each
+    /// passage and around every dead end. On a perfect maze that is a single loop, which is
+    /// cut between the two ends in `UNICURSAL_ENDS`.
+    pub fn to_unicursal(&self) -> Maze {
+        let mut out = Maze::new(Size(self.size.0 * 2, self.size.1 * 2), true);
+
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            let top_left = Position(x * 2, y * 2);
+            let top_right = top_left.translate(Direction::East);
+            let bottom_left = top_left.translate(Direction::South);
+
+            // Each side of the cell with the first cell of the ring along it and the way on
+            for (side, from, along) in [
+                (Direction::North, top_left, Direction::East),
+                (Direction::East, top_right, Direction::South),
+                (Direction::South, bottom_left, Direction::East),
+                (Direction::West, top_left, Direction::South),
+            ] {
+                if tile.get_side(side) {
+                    out.set_wall(from, along, false).unwrap();
+                } else if matches!(side, Direction::East | Direction::South) {
+                    out.set_wall(from, side, false).unwrap();
+                    out.set_wall(from.translate(along), side, false).unwrap();
+                }
+            }
+        }
+
+        // Nothing leaves the top left cell to the west, so its ring always joins the two ends
+        out.set_wall(UNICURSAL_ENDS.entrance.0, Direction::South, true)
+            .unwrap();
+
+        if cfg!(debug_assertions) {
+            out.assert_consistent();
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UNICURSAL_ENDS;
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+
+    #[test]
+    fn one_corridor_through_every_cell() {
+        for size in [Size(1, 1), Size(1, 4), Size(4, 1), Size(5, 3), Size(8, 8)] {
+            for seed in 0..5 {
+                let mut maze = Maze::new(size, true);
+                maze.generate_maze_with_rng(&mut Pcg32::new(seed));
+
+                let labyrinth = maze.to_unicursal();
+                let (entrance, exit) = (UNICURSAL_ENDS.entrance.0, UNICURSAL_ENDS.exit.0);
+
+                assert_eq!(labyrinth.size, Size(size.0 * 2, size.1 * 2));
+                assert_eq!(labyrinth.get_dead_ends(), vec![entrance, exit], "{size:?} seed {seed}");
+
+                for ((x, y), tile) in labyrinth.tiles.indexed_iter() {
+                    let open = tile.get_sides().iter().filter(|(_, closed)| !*closed).count();
+
+                    assert!(open == 2 || labyrinth.is_dead_end(Position(x, y)), "{size:?} seed {seed}");
+                }
+
+                // No junctions and no loops, so the way between the ends passes every cell
+                let path = labyrinth.shortest_path(entrance, exit).unwrap();
+                assert_eq!(path.len(), labyrinth.tiles.len(), "{size:?} seed {seed}");
+            }
+        }
+    }
+}
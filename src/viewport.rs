@@ -0,0 +1,130 @@
+use std::io::{self, IsTerminal, Write};
+
+use crate::{Display, Position, Size};
+
+/// A window of `size` pixels onto a `Display`, see `Display::viewport`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub top_left: Position,
+    pub size: Size,
+}
+impl Viewport {
+    pub fn new(top_left: Position, size: Size) -> Self {
+        Self { top_left, size }
+    }
+
+    /// Moves the window `dx` columns right and `dy` rows down, stopping at the edges of a
+    /// display of `bounds`. Only the window moves, nothing is drawn again.
+    pub fn scroll(&mut self, dx: isize, dy: isize, bounds: Size) {
+        let shift = |from: usize, by: isize, length: usize, bound: usize| {
+            from.saturating_add_signed(by).min(bound.saturating_sub(length))
+        };
+
+        self.top_left = Position(
+            shift(self.top_left.0, dx, self.size.0, bounds.0),
+            shift(self.top_left.1, dy, self.size.1, bounds.1)
+        );
+    }
+
+    /// Whether part of a display of `bounds` is outside the window.
+    pub fn is_clipped(&self, bounds: Size) -> bool {
+        self.size.0 < bounds.0 || self.size.1 < bounds.1
+    }
+}
+
+impl Display {
+    /// The window of `size` pixels at `top_left`, made smaller and moved back where it
+    /// would go past the edge of the display.
+    pub fn viewport(&self, top_left: Position, size: Size) -> Viewport {
+        let size = Size(size.0.min(self.size.0), size.1.min(self.size.1));
+
+        Viewport::new(
+            Position(
+                top_left.0.min(self.size.0 - size.0),
+                top_left.1.min(self.size.1 - size.1)
+            ),
+            size
+        )
+    }
+
+    /// Like `viewport`, with the window as close to centered on `center` as fits.
+    pub fn viewport_around(&self, center: Position, size: Size) -> Viewport {
+        self.viewport(
+            Position(center.0.saturating_sub(size.0 / 2), center.1.saturating_sub(size.1 / 2)),
+            size
+        )
+    }
+
+    /// Prints the window of `size` pixels at `top_left` like `print`, see `viewport`.
+    pub fn print_viewport(&self, top_left: Position, size: Size) {
+        let color = self.color && io::stdout().is_terminal();
+        let mut stdout = io::BufWriter::new(io::stdout().lock());
+
+        self.write_window(&mut stdout, color, self.viewport(top_left, size))
+            .and_then(|_| stdout.flush())
+            .unwrap();
+    }
+
+    /// Writes only the pixels in `viewport` like `write_to`, margins around them.
+    pub fn write_viewport_to<W: io::Write>(&self, w: &mut W, viewport: Viewport) -> io::Result<()> {
+        self.write_window(w, self.color, self.viewport(viewport.top_left, viewport.size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Viewport;
+    use crate::test_support::opened;
+    use crate::{Direction, Display, Margins, Position, Size};
+
+    fn display() -> Display {
+        let maze = opened(Size(3, 2), &[(Position(0, 0), Direction::East), (Position(1, 0), Direction::South)]);
+
+        let mut display = Display::new_from_maze(&maze);
+        display.margins = Margins::none();
+        display.draw_maze(&maze).unwrap();
+
+        display
+    }
+
+    #[test]
+    fn windows_are_kept_inside_the_display() {
+        let display = display();
+
+        assert_eq!(display.viewport(Position(6, 4), Size(3, 2)), Viewport::new(Position(4, 3), Size(3, 2)));
+        assert_eq!(display.viewport(Position(2, 1), Size(20, 20)), Viewport::new(Position(0, 0), Size(7, 5)));
+        assert_eq!(display.viewport_around(Position(0, 4), Size(3, 3)), Viewport::new(Position(0, 2), Size(3, 3)));
+    }
+
+    #[test]
+    fn scrolling_stops_at_the_edges() {
+        let bounds = Size(7, 5);
+        let mut viewport = Viewport::new(Position(1, 1), Size(3, 2));
+
+        viewport.scroll(-5, 10, bounds);
+        assert_eq!(viewport.top_left, Position(0, 3));
+
+        viewport.scroll(2, -1, bounds);
+        assert_eq!(viewport.top_left, Position(2, 2));
+
+        assert!(viewport.is_clipped(bounds));
+        assert!(!Viewport::new(Position(0, 0), bounds).is_clipped(bounds));
+    }
+
+    #[test]
+    fn only_the_window_is_written() {
+        let display = display();
+
+        let mut out = vec![];
+        display.write_viewport_to(&mut out, Viewport::new(Position(2, 0), Size(4, 3))).unwrap();
+
+        // Columns 2 to 5 of the top three rows
+        let expected = [
+            "████",
+            "  █",
+            "█ ██",
+        ];
+
+        assert_eq!(String::from_utf8(out).unwrap(), expected.map(|line| format!("{line}\n")).concat());
+    }
+}
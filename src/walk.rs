@@ -0,0 +1,116 @@
+use std::fmt;
+use std::io;
+use strum::IntoEnumIterator;
+
+use crate::{Direction, Display, Maze, Position, INCOMPLETE_CHAR};
+
+/// The first place a walk through a maze goes wrong. `step` counts from 0 for the first
+/// cell of the walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    Empty,
+    OutOfBounds { step: usize, pos: Position },
+    LeavesGrid { step: usize, from: Position, direction: Direction },
+    NotAdjacent { step: usize, from: Position, to: Position },
+    HitsWall { step: usize, from: Position, direction: Direction },
+}
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "The walk is empty"),
+            Self::OutOfBounds { step, pos } => write!(
+                f, "Step {}: ({}, {}) is outside the maze", step, pos.0, pos.1
+            ),
+            Self::LeavesGrid { step, from, direction } => write!(
+                f, "Step {}: moving {} from ({}, {}) leaves the maze", step, direction, from.0, from.1
+            ),
+            Self::NotAdjacent { step, from, to } => write!(
+                f, "Step {}: ({}, {}) isn't next to ({}, {})", step, to.0, to.1, from.0, from.1
+            ),
+            Self::HitsWall { step, from, direction } => write!(
+                f, "Step {}: moving {} from ({}, {}) runs into a wall", step, direction, from.0, from.1
+            ),
+        }
+    }
+}
+
+impl Maze {
+    /// Checks that `path` starts inside the maze and only ever steps to a neighbouring cell
+    /// through an open wall. Where it starts and ends is up to the caller.
+    pub fn verify_path(&self, path: &[Position]) -> Result<(), PathError> {
+        let first = *path.first().ok_or(PathError::Empty)?;
+
+        if self.get_tile(first).is_none() {
+            return Err(PathError::OutOfBounds { step: 0, pos: first });
+        }
+
+        for (i, pair) in path.windows(2).enumerate() {
+            let (from, to) = (pair[0], pair[1]);
+            let step = i + 1;
+
+            if self.get_tile(to).is_none() {
+                return Err(PathError::OutOfBounds { step, pos: to });
+            }
+
+            let direction = Direction::iter()
+                .find(|direction| self.get_neighbor(from, *direction) == Some(to))
+                .ok_or(PathError::NotAdjacent { step, from, to })?;
+
+            if self.get_tile(from).unwrap().get_side(direction) {
+                return Err(PathError::HitsWall { step, from, direction });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Follows `moves` from `start` and returns every cell visited, stopping at the first
+    /// move that leaves the grid or runs into a wall.
+    pub fn walk_moves(&self, start: Position, moves: &[Direction]) -> Result<Vec<Position>, PathError> {
+        self.verify_path(&[start])?;
+
+        let mut path = vec![start];
+
+        for (i, direction) in moves.iter().enumerate() {
+            let from = *path.last().unwrap();
+            let step = i + 1;
+
+            let to = self
+                .get_neighbor(from, *direction)
+                .ok_or(PathError::LeavesGrid { step, from, direction: *direction })?;
+
+            if self.get_tile(from).unwrap().get_side(*direction) {
+                return Err(PathError::HitsWall { step, from, direction: *direction });
+            }
+
+            path.push(to);
+        }
+
+        Ok(path)
+    }
+}
+
+impl Display {
+    /// Draws a verified walk. A complete walk looks like a solution, an incomplete one keeps
+    /// the entrance marker and ends in `INCOMPLETE_CHAR` instead of reaching the exit.
+    pub fn draw_walk(&mut self, path: &[Position], complete: bool, symbol: char) -> Result<(), io::ErrorKind> {
+        if complete {
+            return self.draw_solution(path, symbol);
+        }
+
+        self.draw_path(
+            path.iter()
+                .map(|x| Maze::to_display_pos(*x))
+                .collect(),
+            symbol
+        )?;
+
+        self.draw_point(Position(1,0), symbol);
+
+        if let Some(last) = path.last() {
+            self.draw_point(Maze::to_display_pos(*last), INCOMPLETE_CHAR);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::io;
+
+use crate::{Display, Maze, Position};
+
+/// Most targets `Maze::solve_visiting_all` puts in an optimal order. The exact search takes
+/// time and memory exponential in the number of targets.
+pub const MAX_EXACT_TARGETS: usize = 12;
+
+/// Why `Maze::solve_via` couldn't build a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    UnknownWaypoint(String),
+    Unreachable { from: Position, to: Position },
+}
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownWaypoint(name) => write!(f, "There is no waypoint called '{}'", name),
+            Self::Unreachable { from, to } => write!(
+                f, "({}, {}) can't be reached from ({}, {})", to.0, to.1, from.0, from.1
+            ),
+        }
+    }
+}
+
+impl Maze {
+    /// Names the cell at `pos`. Setting a name that is already used moves that waypoint, and
+    /// several names may share a cell. A cell outside the maze is `InvalidInput`, and so is
+    /// one outside the shape with `set_masked_waypoint`.
+    pub fn set_waypoint(&mut self, name: &str, pos: Position) -> Result<(), io::ErrorKind> {
+        if self.get_tile(pos).is_none() {
+            return Err(io::ErrorKind::InvalidInput);
+        }
+
+        match self.waypoints.iter_mut().find(|(x, _)| x == name) {
+            Some(waypoint) => waypoint.1 = pos,
+            None => self.waypoints.push((name.to_string(), pos)),
+        }
+
+        Ok(())
+    }
+
+    pub fn get_waypoint(&self, name: &str) -> Option<Position> {
+        self.waypoints
+            .iter()
+            .find(|(x, _)| x == name)
+            .map(|(_, pos)| *pos)
+    }
+
+    pub fn remove_waypoint(&mut self, name: &str) -> Option<Position> {
+        let index = self.waypoints.iter().position(|(x, _)| x == name)?;
+
+        Some(self.waypoints.remove(index).1)
+    }
+
+    /// A path from `start` through the named waypoints in order to `goal`, made of one
+    /// shortest path per leg. A waypoint on the cell the walk is already at adds no steps.
+    pub fn solve_via(&self, start: Position, goal: Position, via: &[&str]) -> Result<Vec<Position>, RouteError> {
+        let mut stops = vec![start];
+
+        for name in via {
+            stops.push(
+                self.get_waypoint(name)
+                    .ok_or_else(|| RouteError::UnknownWaypoint(name.to_string()))?
+            );
+        }
+
+        stops.push(goal);
+
+
+        let mut path = vec![start];
+
+        for leg in stops.windows(2) {
+            let (from, to) = (leg[0], leg[1]);
+
+            let steps = self
+                .shortest_path(from, to)
+                .ok_or(RouteError::Unreachable { from, to })?;
+
+            path.extend(&steps[1..]);
+        }
+
+        Ok(path)
+    }
+
+    /// The shortest path from `start` to `goal` that visits every target, and the order of
+    /// the targets as indices into `targets`. Up to `MAX_EXACT_TARGETS` targets the order is
+    /// optimal, found by dynamic programming over subsets of pairwise distances. Beyond that
+    /// it always heads for the nearest target not visited yet, which can be noticeably longer.
+    pub fn solve_visiting_all(
+        &self,
+        start: Position,
+        goal: Position,
+        targets: &[Position]
+    ) -> Result<(Vec<Position>, Vec<usize>), RouteError> {
+        // Points are the targets followed by the start and the goal
+        let mut points = targets.to_vec();
+        points.extend([start, goal]);
+
+        let mut distances = vec![];
+
+        for from in &points {
+            let map = self.distances_from(*from);
+
+            let row: Option<Vec<u32>> = points.iter()
+                .map(|to| map.get(to.as_array()).copied().flatten())
+                .collect();
+
+            distances.push(row.ok_or_else(|| {
+                let to = points.iter()
+                    .find(|to| map.get(to.as_array()).copied().flatten().is_none())
+                    .unwrap();
+
+                RouteError::Unreachable { from: *from, to: *to }
+            })?);
+        }
+
+        let order = match targets.len() <= MAX_EXACT_TARGETS {
+            true => exact_order(&distances, targets.len()),
+            false => nearest_neighbor_order(&distances, targets.len()),
+        };
+
+
+        let mut stops = vec![start];
+        stops.extend(order.iter().map(|i| targets[*i]));
+        stops.push(goal);
+
+        let mut path = vec![start];
+
+        for leg in stops.windows(2) {
+            path.extend(&self.shortest_path(leg[0], leg[1]).unwrap()[1..]);
+        }
+
+        Ok((path, order))
+    }
+}
+
+impl Display {
+    /// Marks every waypoint with the first letter or digit of its name, or with its number
+    /// counting from 1 if the name doesn't start with one.
+    pub fn draw_waypoints(&mut self, maze: &Maze) {
+        for (i, (name, pos)) in maze.waypoints.iter().enumerate() {
+            let label = name
+                .chars()
+                .next()
+                .filter(|c| c.is_alphanumeric())
+                .or(char::from_digit((i as u32 + 1) % 10, 10))
+                .unwrap();
+
+            self.draw_point(Maze::to_display_pos(*pos), label);
+        }
+    }
+}
+
+// Held-Karp over the `count` targets: `cost[mask][last]` is the shortest walk from the start
+// through the targets in `mask` that ends on target `last`
+fn exact_order(distances: &[Vec<u32>], count: usize) -> Vec<usize> {
+    let (start, goal) = (count, count + 1);
+
+    if count == 0 {
+        return vec![];
+    }
+
+    let full = (1 << count) - 1;
+    let mut cost = vec![vec![u32::MAX; count]; 1 << count];
+    let mut previous = vec![vec![usize::MAX; count]; 1 << count];
+
+    for last in 0..count {
+        cost[1 << last][last] = distances[start][last];
+    }
+
+    for mask in 1..=full {
+        for last in 0..count {
+            if mask & (1 << last) == 0 || cost[mask][last] == u32::MAX {
+                continue;
+            }
+
+            for next in 0..count {
+                if mask & (1 << next) != 0 {
+                    continue;
+                }
+
+                let next_mask = mask | (1 << next);
+                let next_cost = cost[mask][last] + distances[last][next];
+
+                if next_cost < cost[next_mask][next] {
+                    cost[next_mask][next] = next_cost;
+                    previous[next_mask][next] = last;
+                }
+            }
+        }
+    }
+
+    let mut last = (0..count)
+        .min_by_key(|last| cost[full][*last] + distances[*last][goal])
+        .unwrap();
+
+    let mut order = vec![];
+    let mut mask = full;
+
+    while last != usize::MAX {
+        order.push(last);
+
+        let before = previous[mask][last];
+        mask &= !(1 << last);
+        last = before;
+    }
+
+    order.reverse();
+    order
+}
+
+fn nearest_neighbor_order(distances: &[Vec<u32>], count: usize) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..count).collect();
+    let mut current = count;
+    let mut order = vec![];
+
+    while let Some(index) = (0..remaining.len()).min_by_key(|i| distances[current][remaining[*i]]) {
+        current = remaining.remove(index);
+        order.push(current);
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mask::Mask;
+    use crate::Size;
+
+    fn seeded_maze() -> Maze {
+        let mut maze = Maze::new(Size(9, 7), true);
+        maze.generate_maze_seeded(11);
+        maze
+    }
+
+    #[test]
+    fn legs_are_chained_through_the_waypoints() {
+        let mut maze = seeded_maze();
+        let (start, goal) = (Position(0, 0), Position(8, 6));
+        maze.set_waypoint("shrine", Position(8, 0)).unwrap();
+        maze.set_waypoint("fountain", Position(0, 6)).unwrap();
+
+        let path = maze.solve_via(start, goal, &["shrine", "fountain"]).unwrap();
+
+        let legs = [(start, Position(8, 0)), (Position(8, 0), Position(0, 6)), (Position(0, 6), goal)];
+        let expected: usize = legs.iter().map(|(from, to)| maze.shortest_path(*from, *to).unwrap().len() - 1).sum();
+
+        assert_eq!(path.len() - 1, expected);
+        assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+        assert!(path.contains(&Position(8, 0)) && path.contains(&Position(0, 6)));
+        assert!(path.windows(2).all(|step| maze.get_moves(step[0]).iter().any(|(_, next)| *next == step[1])));
+    }
+
+    #[test]
+    fn unknown_and_unreachable_waypoints_are_errors() {
+        let mut maze = seeded_maze();
+        let (start, goal) = (Position(0, 0), Position(8, 6));
+
+        assert_eq!(
+            maze.solve_via(start, goal, &["nowhere"]),
+            Err(RouteError::UnknownWaypoint("nowhere".to_string()))
+        );
+
+        let mut walled = Maze::new(Size(3, 3), true);
+        walled.set_waypoint("island", Position(1, 1)).unwrap();
+
+        assert!(matches!(
+            walled.solve_via(Position(0, 0), Position(2, 2), &["island"]),
+            Err(RouteError::Unreachable { .. })
+        ));
+
+        maze.set_waypoint("start", start).unwrap();
+        assert_eq!(maze.solve_via(start, goal, &["start"]), Ok(maze.shortest_path(start, goal).unwrap()));
+    }
+
+    #[test]
+    fn names_are_unique_and_cells_must_be_inside() {
+        let mut maze = seeded_maze();
+        maze.set_waypoint("shrine", Position(1, 1)).unwrap();
+        maze.set_waypoint("shrine", Position(2, 2)).unwrap();
+
+        assert_eq!(maze.waypoints, vec![("shrine".to_string(), Position(2, 2))]);
+        assert_eq!(maze.set_waypoint("far", Position(9, 0)), Err(io::ErrorKind::InvalidInput));
+
+        let mask = Mask::circle(maze.size);
+
+        assert_eq!(maze.set_masked_waypoint("corner", Position(0, 0), &mask), Err(io::ErrorKind::InvalidInput));
+        assert_eq!(maze.set_masked_waypoint("middle", Position(4, 3), &mask), Ok(()));
+        assert_eq!(maze.get_waypoint("corner"), None);
+    }
+
+    #[test]
+    fn waypoints_survive_json() {
+        let mut maze = seeded_maze();
+        maze.set_waypoint("shrine", Position(5, 2)).unwrap();
+        maze.set_waypoint("fountain", Position(1, 6)).unwrap();
+
+        assert_eq!(Maze::from_json(&maze.to_json()), Ok(maze));
+    }
+
+    #[test]
+    fn the_best_order_beats_fixed_ones() {
+        let maze = seeded_maze();
+        let (start, goal) = (Position(0, 0), Position(8, 6));
+        let targets = [Position(8, 0), Position(0, 6), Position(4, 3)];
+
+        let (path, order) = maze.solve_visiting_all(start, goal, &targets).unwrap();
+
+        let length = |order: &[usize]| {
+            let mut stops = vec![start];
+            stops.extend(order.iter().map(|i| targets[*i]));
+            stops.push(goal);
+
+            stops.windows(2).map(|leg| maze.shortest_path(leg[0], leg[1]).unwrap().len() - 1).sum::<usize>()
+        };
+
+        let orders = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+        assert_eq!(path.len() - 1, length(&order));
+        assert_eq!(path.len() - 1, orders.iter().map(|order| length(order)).min().unwrap());
+        assert!(orders.iter().any(|order| length(order) > path.len() - 1));
+        assert!(targets.iter().all(|target| path.contains(target)));
+    }
+
+    #[test]
+    fn many_targets_still_visit_every_one() {
+        let maze = seeded_maze();
+        let targets: Vec<Position> = (0..9).flat_map(|x| [Position(x, 1), Position(x, 5)]).collect();
+
+        assert!(targets.len() > MAX_EXACT_TARGETS);
+
+        let (path, mut order) = maze.solve_visiting_all(Position(0, 0), Position(8, 6), &targets).unwrap();
+
+        assert!(targets.iter().all(|target| path.contains(target)));
+        assert!(path.windows(2).all(|step| maze.get_moves(step[0]).iter().any(|(_, next)| *next == step[1])));
+
+        order.sort();
+        assert_eq!(order, (0..targets.len()).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,169 @@
+use ndarray::Array2;
+use rand::RngCore;
+use strum::IntoEnumIterator;
+
+use crate::rng::{chance, pick};
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    /// Whether a passage runs under the one through `pos`, see `Maze::crossings`.
+    pub fn is_crossing(&self, pos: Position) -> bool {
+        self.crossings.iter().any(|(crossing, _)| *crossing == pos)
+    }
+
+    // The cell a move from `pos` towards `direction` ends on, going straight over any
+    // crossings. `None` if that leaves the maze, also past a crossing on the border, which
+    // a loaded maze can have.
+    pub(crate) fn beyond_crossings(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let mut neighbor = self.get_neighbor(pos, direction)?;
+
+        while self.is_crossing(neighbor) {
+            neighbor = self.get_neighbor(neighbor, direction)?;
+        }
+
+        Some(neighbor)
+    }
+
+    pub fn generate_weave(&mut self, tunnel_chance: f64) {
+        self.generate_weave_with_rng(tunnel_chance, &mut rand::rng());
+    }
+
+    /// A backtracker that, with probability `tunnel_chance` at each step where it can,
+    /// tunnels under a neighbouring straight passage to the unvisited cell beyond it instead
+    /// of carving to an adjacent one. Every tunnel adds a crossing.
+    ///
+    /// The solvers and the distance based functions all pass straight through crossings
+    /// and never turn on one.
+    pub fn generate_weave_with_rng<R: RngCore>(&mut self, tunnel_chance: f64, rng: &mut R) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        let mut explored = Array2::from_elem(self.size.as_array(), false);
+        let mut stack = vec![Position::new()];
+
+        explored[[0, 0]] = true;
+
+        while let Some(pos) = stack.last().copied() {
+            let unexplored = |neighbor: &Position| !explored[neighbor.as_array()];
+
+            let dirs: Vec<Direction> = Direction::iter()
+                .filter(|direction| self.get_neighbor(pos, *direction).is_some_and(|x| unexplored(&x)))
+                .collect();
+
+            let tunnels: Vec<Direction> = Direction::iter()
+                .filter(|direction| {
+                    self.get_tunnel_end(pos, *direction)
+                        .is_some_and(|x| unexplored(&x))
+                })
+                .collect();
+
+            if !tunnels.is_empty() && chance(rng, tunnel_chance) {
+                let direction = pick(rng, &tunnels).unwrap();
+                let under = pos.translate(direction);
+                let end = under.translate(direction);
+
+                self.set_wall(pos, direction, false).unwrap();
+                self.set_wall(under, direction, false).unwrap();
+                self.crossings.push((under, direction.get_axis()));
+
+                explored[end.as_array()] = true;
+                stack.push(end);
+
+                continue;
+            }
+
+            let Some(direction) = pick(rng, &dirs) else {
+                stack.pop();
+                continue;
+            };
+
+            let next = pos.translate(direction);
+
+            self.set_wall(pos, direction, false).unwrap();
+            explored[next.as_array()] = true;
+
+            stack.push(next);
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+
+    // The cell two steps away in `direction` if the one in between is a straight passage
+    // across that direction that a tunnel can run under
+    fn get_tunnel_end(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let under = self.get_neighbor(pos, direction)?;
+        let end = self.get_neighbor(under, direction)?;
+
+        let tile = self.get_tile(under).unwrap();
+
+        let crosswise = direction
+            .get_perpendiculars()
+            .iter()
+            .all(|side| !tile.get_side(*side));
+
+        let straight = tile.get_side(direction) && tile.get_side(direction.get_opposite());
+
+        (crosswise && straight && !self.is_crossing(under)).then_some(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Axis, Direction, Maze, Position, Size};
+
+    fn woven_maze() -> Maze {
+        let mut maze = Maze::new(Size(15, 15), true);
+        maze.generate_weave_with_rng(0.6, &mut Pcg32::new(9));
+
+        assert!(!maze.crossings.is_empty());
+        maze
+    }
+
+    // Each step goes to a neighbour and a path through a crossing keeps going straight
+    fn assert_walkable(maze: &Maze, path: &[Position]) {
+        for step in path.windows(2) {
+            assert_eq!(step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1), 1, "{:?}", step);
+        }
+
+        for step in path.windows(3) {
+            if maze.is_crossing(step[1]) {
+                assert!(step[0].0 == step[2].0 || step[0].1 == step[2].1, "turned at {:?}", step[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn distances_and_routes_go_straight_through_crossings() {
+        let maze = woven_maze();
+        let (start, goal) = (Position::new(), maze.size.get_max_pos());
+
+        let path = maze.shortest_path(start, goal).unwrap();
+
+        assert_walkable(&maze, &path);
+        assert_eq!(path.len(), maze.solve_bfs().len());
+        assert_eq!(maze.distances_from(start)[goal.as_array()], Some(path.len() as u32 - 1));
+
+        let targets = [Position(14, 0), Position(0, 14), Position(7, 7)];
+        let (route, _) = maze.solve_visiting_all(start, goal, &targets).unwrap();
+
+        assert_walkable(&maze, &route);
+    }
+
+    #[test]
+    fn a_crossing_on_the_border_leads_nowhere() {
+        // The passage under the top middle cell would come out above the maze
+        let mut maze = Maze::new(Size(3, 2), false);
+        maze.crossings.push((Position(1, 0), Axis(0)));
+
+        assert_eq!(maze.beyond_crossings(Position(1, 1), Direction::North), None);
+        assert_eq!(maze.beyond_crossings(Position(0, 0), Direction::East), Some(Position(2, 0)));
+        assert!(maze.get_moves(Position(1, 1)).iter().all(|(direction, _)| *direction != Direction::North));
+
+        assert_eq!(maze.solve_bfs().len(), 4);
+        assert_eq!(maze.solve_maze_with_rng(&mut Pcg32::new(1)).last(), Some(&Position(2, 1)));
+    }
+}
@@ -0,0 +1,117 @@
+use ndarray::Array2;
+use rand::RngCore;
+use strum::IntoEnumIterator;
+
+use crate::rng::{pick, random_index};
+use crate::{Direction, Maze, Position};
+
+impl Maze {
+    pub fn generate_wilson(&mut self) {
+        self.generate_wilson_with_rng(&mut rand::rng());
+    }
+
+    /// Grows a tree from one random cell with loop-erased random walks: each walk starts at
+    /// a cell outside the tree, wanders until it hits the tree and is carved without any
+    /// loops it made on the way. Every spanning tree of the grid is equally likely.
+    pub fn generate_wilson_with_rng<R: RngCore>(&mut self, rng: &mut R) {
+        let mut in_tree = Array2::from_elem(self.size.as_array(), false);
+        let mut exits: Array2<Option<Direction>> = Array2::from_elem(self.size.as_array(), None);
+
+        let mut cells: Vec<Position> = self.tiles
+            .indexed_iter()
+            .map(|((x, y), _)| Position(x, y))
+            .collect();
+
+        for i in 0..cells.len() {
+            let other = i + random_index(rng, cells.len() - i);
+            cells.swap(i, other);
+        }
+
+        let Some(root) = cells.first() else {
+            return;
+        };
+
+        in_tree[root.as_array()] = true;
+
+        for start in cells {
+            // Walking over a cell again overwrites the direction it was left in, which
+            // erases the loop in between
+            let mut pos = start;
+
+            while !in_tree[pos.as_array()] {
+                let directions: Vec<Direction> = Direction::iter()
+                    .filter(|direction| self.get_neighbor(pos, *direction).is_some())
+                    .collect();
+
+                let direction = pick(rng, &directions).unwrap();
+
+                exits[pos.as_array()] = Some(direction);
+                pos = pos.translate(direction);
+            }
+
+            let mut pos = start;
+
+            while !in_tree[pos.as_array()] {
+                let direction = exits[pos.as_array()].unwrap();
+
+                in_tree[pos.as_array()] = true;
+                self.set_wall(pos, direction, false).unwrap();
+
+                pos = pos.translate(direction);
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_consistent();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Pcg32;
+    use crate::{Maze, Position, Size};
+    use std::collections::HashMap;
+
+    #[test]
+    fn every_size_is_perfect() {
+        for size in [Size(1, 1), Size(1, 6), Size(6, 1), Size(2, 2), Size(11, 8)] {
+            for seed in 0..5 {
+                let mut maze = Maze::new(size, true);
+                maze.generate_wilson_with_rng(&mut Pcg32::new(seed));
+
+                let cells = size.0 * size.1;
+                let reached = maze.distances_from(Position(0, 0)).iter().filter(|x| x.is_some()).count();
+                let open: usize = maze.tiles.iter()
+                    .map(|tile| tile.get_sides().iter().filter(|(_, closed)| !*closed).count())
+                    .sum();
+
+                assert_eq!((reached, open / 2), (cells, cells - 1), "{size:?} seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn spanning_trees_are_equally_likely() {
+        // A 3x2 grid has 15 spanning trees
+        let mut counts: HashMap<String, usize> = HashMap::new();
+
+        for seed in 0..3000 {
+            let mut maze = Maze::new(Size(3, 2), true);
+            maze.generate_wilson_with_rng(&mut Pcg32::new(seed));
+
+            *counts.entry(maze.fingerprint()).or_default() += 1;
+        }
+
+        assert_eq!(counts.len(), 15);
+        assert!(counts.values().all(|count| (140..260).contains(count)), "{:?}", counts.values());
+    }
+
+    #[test]
+    fn an_empty_maze_stays_empty() {
+        let mut maze = Maze::new(Size(0, 0), true);
+        maze.generate_wilson_with_rng(&mut Pcg32::new(1));
+
+        assert!(maze.tiles.is_empty());
+    }
+}